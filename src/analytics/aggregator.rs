@@ -1,86 +1,309 @@
-use chrono::{Utc, Duration};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, TimeZone, Timelike, Utc, Weekday};
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::fs;
+use log::debug;
 
 use super::models::*;
-use super::calculator::CostCalculator;
+use super::calculator::{CostBreakdown, CostCalculator};
 
 /// Aggregates usage data into various analytics views
 pub struct UsageAggregator {
     cost_calculator: CostCalculator,
+    options: AggregationOptions,
+    /// Real paths already looked up for `resolve_symlinks`, keyed by normalized project path
+    resolved_paths: RefCell<HashMap<String, String>>,
 }
 
 impl UsageAggregator {
     pub fn new() -> Self {
+        Self::with_options(AggregationOptions::default())
+    }
+
+    /// Create an aggregator that applies the given options
+    pub fn with_options(options: AggregationOptions) -> Self {
         Self {
-            cost_calculator: CostCalculator::new(),
+            cost_calculator: CostCalculator::with_cache_write_fallback(options.cache_writes_as_input)
+                .with_pricing_history(options.pricing_history.clone()),
+            options,
+            resolved_paths: RefCell::new(HashMap::new()),
         }
     }
 
+    /// Combine per-category token counts using the configured total policy
+    fn token_total(&self, input: u64, output: u64, cache_read: u64, cache_creation: u64) -> u64 {
+        self.options.token_total_policy.total(input, output, cache_read, cache_creation)
+    }
+
     /// Filter entries by time range
     pub fn filter_by_time_range(&self, entries: &[UsageEntry], time_range: TimeRange) -> Vec<UsageEntry> {
-        let now = Utc::now();
-        println!("🕐 Current time: {}", now.format("%Y-%m-%d %H:%M:%S"));
+        self.filter_by_time_range_at(entries, time_range, Utc::now())
+    }
+
+    /// Filter entries by time range relative to `now`
+    pub fn filter_by_time_range_at(&self, entries: &[UsageEntry], time_range: TimeRange, now: DateTime<Utc>) -> Vec<UsageEntry> {
+        debug!("🕐 Current time: {}", now.format("%Y-%m-%d %H:%M:%S"));
         
         match time_range {
             TimeRange::AllTime => {
-                println!("📊 TimeRange::AllTime - returning all {} entries", entries.len());
+                debug!("📊 TimeRange::AllTime - returning all {} entries", entries.len());
                 entries.to_vec()
             }
             TimeRange::Last7Days => {
                 let cutoff = now - Duration::days(7);
-                println!("📊 TimeRange::Last7Days - filtering entries after {}", cutoff.format("%Y-%m-%d"));
+                debug!("📊 TimeRange::Last7Days - filtering entries after {}", cutoff.format("%Y-%m-%d"));
                 let filtered = entries.iter()
                     .filter(|e| e.timestamp >= cutoff)
                     .cloned()
                     .collect::<Vec<_>>();
-                println!("📊 Filtered from {} to {} entries", entries.len(), filtered.len());
+                debug!("📊 Filtered from {} to {} entries", entries.len(), filtered.len());
                 filtered
             }
             TimeRange::Last30Days => {
                 let cutoff = now - Duration::days(30);
-                println!("📊 TimeRange::Last30Days - filtering entries after {}", cutoff.format("%Y-%m-%d"));
+                debug!("📊 TimeRange::Last30Days - filtering entries after {}", cutoff.format("%Y-%m-%d"));
+                let filtered = entries.iter()
+                    .filter(|e| e.timestamp >= cutoff)
+                    .cloned()
+                    .collect::<Vec<_>>();
+                debug!("📊 Filtered from {} to {} entries", entries.len(), filtered.len());
+                filtered
+            }
+            TimeRange::Last24Hours => {
+                let cutoff = now - Duration::hours(24);
+                debug!("📊 TimeRange::Last24Hours - filtering entries after {}", cutoff.format("%Y-%m-%d %H:%M"));
                 let filtered = entries.iter()
                     .filter(|e| e.timestamp >= cutoff)
                     .cloned()
                     .collect::<Vec<_>>();
-                println!("📊 Filtered from {} to {} entries", entries.len(), filtered.len());
+                debug!("📊 Filtered from {} to {} entries", entries.len(), filtered.len());
                 filtered
             }
+            TimeRange::Today | TimeRange::Yesterday => {
+                let today = self.local_date(&now);
+                let day = if time_range == TimeRange::Today {
+                    today
+                } else {
+                    today - Duration::days(1)
+                };
+                debug!("📊 TimeRange::{:?} - filtering entries on local day {}", time_range, day.format("%Y-%m-%d"));
+                let filtered = entries.iter()
+                    .filter(|e| self.local_date(&e.timestamp) == day)
+                    .cloned()
+                    .collect::<Vec<_>>();
+                debug!("📊 Filtered from {} to {} entries", entries.len(), filtered.len());
+                filtered
+            }
+        }
+    }
+
+    /// Project an entry is grouped under: its normalized path, or with `resolve_symlinks`
+    /// the real path behind it. Paths that no longer exist are kept as they are.
+    pub fn project_key(&self, entry: &UsageEntry) -> String {
+        let key = entry.project_key();
+        // A relative cwd would resolve against the dashboard's own working directory
+        if !self.options.resolve_symlinks || entry.project_path.is_none() || is_relative_project_path(&key) {
+            return key;
+        }
+        self.resolved_paths
+            .borrow_mut()
+            .entry(key)
+            .or_insert_with_key(|key| {
+                fs::canonicalize(key)
+                    .map(|real| normalize_project_path(&real.to_string_lossy()))
+                    .unwrap_or_else(|_| key.clone())
+            })
+            .clone()
+    }
+
+    /// Whether the entry is left out of totals: its project or model is on the exclusion
+    /// lists, it falls inside `quiet_hours`, or it predates `analysis_start`
+    pub fn is_filtered_out(&self, entry: &UsageEntry) -> bool {
+        let project = self.project_key(entry);
+        if self.options.excluded_projects.iter().any(|p| normalize_project_path(p) == project) {
+            return true;
+        }
+        if self.options.quiet_hours.is_some_and(|quiet| quiet.contains(self.local_hour(&entry.timestamp))) {
+            return true;
+        }
+        if self.options.analysis_start.is_some_and(|start| entry.timestamp < start) {
+            return true;
+        }
+        if self.options.excluded_models.is_empty() {
+            return false;
+        }
+        // Model rows are keyed by family when grouping, so accept either form
+        let family = self.cost_calculator.get_model_family(&entry.model);
+        self.options.excluded_models.iter().any(|m| *m == entry.model || *m == family)
+    }
+
+    /// Entries as they count toward totals: excluded projects and models, quiet-hours
+    /// entries and anything before `analysis_start` dropped, symlinked project paths resolved
+    /// with `resolve_symlinks` and, with `ignore_cache`, cache tokens and their cost removed
+    pub fn counted_entries(&self, entries: Vec<UsageEntry>) -> Vec<UsageEntry> {
+        entries
+            .into_iter()
+            .filter(|e| !self.is_filtered_out(e))
+            .map(|e| self.with_resolved_project(e))
+            .map(|e| self.without_cache(e))
+            .collect()
+    }
+
+    fn with_resolved_project(&self, entry: UsageEntry) -> UsageEntry {
+        if !self.options.resolve_symlinks || entry.project_path.is_none() {
+            return entry;
+        }
+        UsageEntry {
+            project_path: Some(self.project_key(&entry)),
+            ..entry
+        }
+    }
+
+    fn without_cache(&self, entry: UsageEntry) -> UsageEntry {
+        if !self.options.ignore_cache {
+            return entry;
+        }
+        let cache_cost = self.cache_cost(&entry);
+        UsageEntry {
+            cache_read_tokens: 0,
+            cache_creation_tokens: 0,
+            cost: (sane_cost(entry.cost) - cache_cost).max(0.0),
+            computed_cost: (entry.computed_cost - cache_cost).max(0.0),
+            ..entry
         }
     }
 
+    /// Priced cost of an entry's cache reads and writes, at the rates in effect when it was made
+    fn cache_cost(&self, entry: &UsageEntry) -> f64 {
+        self.cost_calculator
+            .calculate_cost_breakdown_at(&entry.model, &entry.timestamp, 0, 0, entry.cache_read_tokens, entry.cache_creation_tokens)
+            .total()
+    }
+
+    /// Cost an entry contributes to totals under the current options
+    fn counted_cost(&self, entry: &UsageEntry) -> f64 {
+        if self.options.ignore_cache {
+            (sane_cost(entry.cost) - self.cache_cost(entry)).max(0.0)
+        } else {
+            sane_cost(entry.cost)
+        }
+    }
+
+    /// Calendar day of a timestamp in the configured timezone
+    pub fn local_date(&self, timestamp: &DateTime<Utc>) -> NaiveDate {
+        match self.options.timezone {
+            Some(tz) => timestamp.with_timezone(&tz).date_naive(),
+            None => timestamp.with_timezone(&Local).date_naive(),
+        }
+    }
+
+    /// Midnight at the start of a calendar day in the configured timezone
+    pub fn start_of_day(&self, date: NaiveDate) -> DateTime<Utc> {
+        let midnight = date.and_time(chrono::NaiveTime::MIN);
+        let start = match self.options.timezone {
+            Some(tz) => tz.from_local_datetime(&midnight).earliest().map(|dt| dt.with_timezone(&Utc)),
+            None => Local.from_local_datetime(&midnight).earliest().map(|dt| dt.with_timezone(&Utc)),
+        };
+        // Midnight can be skipped by a DST change; UTC midnight is close enough then
+        start.unwrap_or_else(|| Utc.from_utc_datetime(&midnight))
+    }
+
+    /// Hour of the day (0-23) of a timestamp in the configured timezone
+    pub fn local_hour(&self, timestamp: &DateTime<Utc>) -> u32 {
+        match self.options.timezone {
+            Some(tz) => timestamp.with_timezone(&tz).hour(),
+            None => timestamp.with_timezone(&Local).hour(),
+        }
+    }
+
+    /// Total cost so far in the current calendar month
+    pub fn month_to_date_cost(&self, entries: &[UsageEntry]) -> f64 {
+        self.month_to_date_cost_at(entries, Utc::now())
+    }
+
+    /// Total cost in the calendar month containing `now`, up to `now`
+    pub fn month_to_date_cost_at(&self, entries: &[UsageEntry], now: DateTime<Utc>) -> f64 {
+        let today = self.local_date(&now);
+        entries.iter()
+            .filter(|e| e.timestamp <= now && !self.is_filtered_out(e))
+            .filter(|e| {
+                let day = self.local_date(&e.timestamp);
+                day.year() == today.year() && day.month() == today.month()
+            })
+            .map(|e| self.counted_cost(e))
+            .sum()
+    }
+
+    /// Yearly cost at the average daily spend of the last `window_days` days
+    pub fn annualized_run_rate(&self, entries: &[UsageEntry], window_days: i64) -> Option<RunRate> {
+        self.annualized_run_rate_at(entries, window_days, Utc::now())
+    }
+
+    /// Run-rate over the `window_days` calendar days ending on the day of `now`. When the
+    /// history starts inside the window only the days since the first entry are averaged,
+    /// so a new install isn't diluted by days before it existed. None without spend to go on.
+    pub fn annualized_run_rate_at(&self, entries: &[UsageEntry], window_days: i64, now: DateTime<Utc>) -> Option<RunRate> {
+        if window_days <= 0 {
+            return None;
+        }
+        let today = self.local_date(&now);
+        let window_start = today - Duration::days(window_days - 1);
+        let counted: Vec<&UsageEntry> = entries
+            .iter()
+            .filter(|e| e.timestamp <= now && !self.is_filtered_out(e))
+            .collect();
+        let first_day = counted.iter().map(|e| self.local_date(&e.timestamp)).min()?;
+
+        let start = first_day.max(window_start);
+        let total: f64 = counted
+            .iter()
+            .filter(|e| self.local_date(&e.timestamp) >= start)
+            .map(|e| self.counted_cost(e))
+            .sum();
+        if total <= 0.0 {
+            return None;
+        }
+        let days_used = (today - start).num_days() + 1;
+        let daily_average = total / days_used as f64;
+        Some(RunRate {
+            daily_average,
+            annual_cost: daily_average * 365.0,
+            days_used,
+        })
+    }
+
     /// Calculate overall usage statistics with all breakdowns pre-computed
     pub fn calculate_usage_stats(&self, entries: &[UsageEntry]) -> UsageStats {
         if entries.is_empty() {
             return UsageStats::new();
         }
 
-        println!("🔄 Computing analytics for {} entries...", entries.len());
+        debug!("🔄 Computing analytics for {} entries...", entries.len());
         
-        let total_cost = entries.iter().map(|e| e.cost).sum();
-        let total_input_tokens = entries.iter().map(|e| e.input_tokens as u64).sum();
-        let total_output_tokens = entries.iter().map(|e| e.output_tokens as u64).sum();
-        let total_cache_read_tokens = entries.iter().map(|e| e.cache_read_tokens as u64).sum();
-        let total_cache_creation_tokens = entries.iter().map(|e| e.cache_creation_tokens as u64).sum();
+        let total_cost = entries.iter().map(|e| sane_cost(e.cost)).sum();
+        let total_input_tokens = entries.iter().map(|e| e.input_tokens).sum();
+        let total_output_tokens = entries.iter().map(|e| e.output_tokens).sum();
+        let total_cache_read_tokens = entries.iter().map(|e| e.cache_read_tokens).sum();
+        let total_cache_creation_tokens = entries.iter().map(|e| e.cache_creation_tokens).sum();
         
-        let total_tokens = total_input_tokens + total_output_tokens + total_cache_read_tokens + total_cache_creation_tokens;
+        let total_tokens = self.token_total(total_input_tokens, total_output_tokens, total_cache_read_tokens, total_cache_creation_tokens);
         
-        // Count unique sessions
+        // Count unique sessions, telling apart equal ids under different projects
         let session_count = entries.iter()
-            .filter_map(|e| e.session_id.as_ref())
+            .filter_map(|e| e.session_id.as_deref().map(|id| SessionStats::key_for(&self.project_key(e), id)))
             .collect::<std::collections::HashSet<_>>()
             .len();
 
-        println!("📊 Computing model stats...");
+        debug!("📊 Computing model stats...");
         let model_stats_vec = self.calculate_model_stats(entries);
         
-        println!("📂 Computing project stats...");
+        debug!("📂 Computing project stats...");
         let project_stats_vec = self.calculate_project_stats(entries);
         
-        println!("🔗 Computing session stats...");
+        debug!("🔗 Computing session stats...");
         let session_stats_vec = self.calculate_session_stats(entries);
         
-        println!("📅 Computing daily usage...");
+        debug!("📅 Computing daily usage...");
         let daily_usage_vec = self.calculate_daily_usage(entries);
 
         // Convert to hashmaps for faster lookups
@@ -96,7 +319,7 @@ impl UsageAggregator {
 
         let mut session_stats = HashMap::new();
         for stat in session_stats_vec {
-            session_stats.insert(stat.session_id.clone(), stat);
+            session_stats.insert(stat.key(), stat);
         }
 
         let mut daily_usage = HashMap::new();
@@ -104,7 +327,17 @@ impl UsageAggregator {
             daily_usage.insert(stat.date.clone(), stat);
         }
 
-        println!("✅ Analytics computation complete");
+        let requests_over_cost_alert = self
+            .options
+            .request_cost_alert
+            .map(|threshold| entries.iter().filter(|e| sane_cost(e.cost) > threshold).count())
+            .unwrap_or(0);
+        let most_expensive_request = entries
+            .iter()
+            .max_by(|a, b| sane_cost(a.cost).total_cmp(&sane_cost(b.cost)))
+            .cloned();
+
+        debug!("✅ Analytics computation complete");
 
         UsageStats {
             total_cost,
@@ -119,20 +352,59 @@ impl UsageAggregator {
             project_stats,
             session_stats,
             daily_usage,
+            requests_over_cost_alert,
+            most_expensive_request,
+            parse_errors: ParseErrorLog::default(),
         }
     }
 
     // Removed unused calculate_quick_stats method during cleanup
 
+    /// Key a model is listed under: its family when grouping by family, otherwise its id
+    fn model_group(&self, model: &str) -> String {
+        if self.options.group_models_by_family {
+            self.cost_calculator.get_model_family(model)
+        } else {
+            model.to_string()
+        }
+    }
+
+    /// How one model row's cost splits across token categories, pricing each entry at the
+    /// rates in effect when it was made so the split adds up to the row's total
+    pub fn model_cost_breakdown(&self, entries: &[UsageEntry], model: &str) -> CostBreakdown {
+        entries
+            .iter()
+            .filter(|e| self.model_group(&e.model) == model)
+            .fold(CostBreakdown::default(), |total, e| {
+                total
+                    + self.cost_calculator.calculate_cost_breakdown_at(
+                        &e.model,
+                        &e.timestamp,
+                        e.input_tokens,
+                        e.output_tokens,
+                        e.cache_read_tokens,
+                        e.cache_creation_tokens,
+                    )
+            })
+    }
+
     /// Calculate model-wise statistics
     pub fn calculate_model_stats(&self, entries: &[UsageEntry]) -> Vec<ModelStats> {
         let mut model_map: HashMap<String, ModelStats> = HashMap::new();
 
         for entry in entries {
-            let model_stat = model_map.entry(entry.model.clone()).or_insert_with(|| {
+            let (model, display_name) = if self.options.group_models_by_family {
+                let family = self.cost_calculator.get_model_family(&entry.model);
+                let display_name = self.cost_calculator.get_family_display_name(&family);
+                (family, display_name)
+            } else {
+                (entry.model.clone(), self.cost_calculator.get_model_display_name(&entry.model))
+            };
+
+            let model_stat = model_map.entry(model.clone()).or_insert_with(|| {
                 ModelStats {
-                    model: entry.model.clone(),
-                    display_name: self.cost_calculator.get_model_display_name(&entry.model),
+                    model,
+                    display_name,
                     total_cost: 0.0,
                     total_tokens: 0,
                     input_tokens: 0,
@@ -143,26 +415,132 @@ impl UsageAggregator {
                 }
             });
 
-            model_stat.total_cost += entry.cost;
-            model_stat.input_tokens += entry.input_tokens as u64;
-            model_stat.output_tokens += entry.output_tokens as u64;
-            model_stat.cache_read_tokens += entry.cache_read_tokens as u64;
-            model_stat.cache_creation_tokens += entry.cache_creation_tokens as u64;
-            model_stat.total_tokens = model_stat.input_tokens + model_stat.output_tokens;
+            model_stat.total_cost += sane_cost(entry.cost);
+            model_stat.input_tokens += entry.input_tokens;
+            model_stat.output_tokens += entry.output_tokens;
+            model_stat.cache_read_tokens += entry.cache_read_tokens;
+            model_stat.cache_creation_tokens += entry.cache_creation_tokens;
+            model_stat.total_tokens = self.token_total(model_stat.input_tokens, model_stat.output_tokens, model_stat.cache_read_tokens, model_stat.cache_creation_tokens);
             model_stat.request_count += 1;
         }
 
         let mut model_stats: Vec<ModelStats> = model_map.into_values().collect();
-        model_stats.sort_by(|a, b| b.total_cost.partial_cmp(&a.total_cost).unwrap_or(std::cmp::Ordering::Equal));
+        model_stats.sort_by(|a, b| b.total_cost.total_cmp(&a.total_cost));
         model_stats
     }
 
+    /// Compare per-project and per-model cost between two periods (B relative to A).
+    /// Entities seen in only one period appear with zero cost and requests for the other.
+    pub fn diff_periods(&self, entries: &[UsageEntry], range_a: Period, range_b: Period) -> PeriodDiff {
+        let in_period = |period: Period| {
+            self.counted_entries(entries.iter().filter(|e| period.contains(&e.timestamp)).cloned().collect())
+        };
+        let entries_a = in_period(range_a);
+        let entries_b = in_period(range_b);
+
+        let projects = Self::merge_deltas(
+            self.calculate_project_stats(&entries_a)
+                .into_iter()
+                .map(|p| (p.project_path, p.project_name, p.total_cost, p.request_count)),
+            self.calculate_project_stats(&entries_b)
+                .into_iter()
+                .map(|p| (p.project_path, p.project_name, p.total_cost, p.request_count)),
+        );
+        let models = Self::merge_deltas(
+            self.calculate_model_stats(&entries_a)
+                .into_iter()
+                .map(|m| (m.model, m.display_name, m.total_cost, m.request_count)),
+            self.calculate_model_stats(&entries_b)
+                .into_iter()
+                .map(|m| (m.model, m.display_name, m.total_cost, m.request_count)),
+        );
+
+        PeriodDiff {
+            total_cost_a: entries_a.iter().map(|e| sane_cost(e.cost)).sum(),
+            total_cost_b: entries_b.iter().map(|e| sane_cost(e.cost)).sum(),
+            projects,
+            models,
+        }
+    }
+
+    /// Pair up (key, name, cost, requests) rows from both periods by key
+    fn merge_deltas(
+        rows_a: impl Iterator<Item = (String, String, f64, usize)>,
+        rows_b: impl Iterator<Item = (String, String, f64, usize)>,
+    ) -> Vec<EntityDelta> {
+        let mut deltas: HashMap<String, EntityDelta> = HashMap::new();
+        let blank = |key: &str, name: String| EntityDelta {
+            key: key.to_string(),
+            name,
+            cost_a: 0.0,
+            cost_b: 0.0,
+            requests_a: 0,
+            requests_b: 0,
+        };
+        for (key, name, cost, requests) in rows_a {
+            let delta = deltas.entry(key.clone()).or_insert_with(|| blank(&key, name));
+            delta.cost_a = cost;
+            delta.requests_a = requests;
+        }
+        for (key, name, cost, requests) in rows_b {
+            let delta = deltas.entry(key.clone()).or_insert_with(|| blank(&key, name));
+            delta.cost_b = cost;
+            delta.requests_b = requests;
+        }
+
+        let mut deltas: Vec<EntityDelta> = deltas.into_values().collect();
+        deltas.sort_by(|a, b| {
+            b.cost_delta()
+                .abs()
+                .total_cmp(&a.cost_delta().abs())
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        deltas
+    }
+
+    /// Per-model split of one session's entries, costliest model first
+    pub fn session_model_breakdown(&self, entries: &[UsageEntry], project: &str, session_id: &str) -> Vec<ModelStats> {
+        let session_entries: Vec<UsageEntry> = self
+            .session_entries(entries, project, session_id)
+            .into_iter()
+            .cloned()
+            .collect();
+        self.calculate_model_stats(&session_entries)
+    }
+
+    /// One session's entries, oldest first. Sessions are matched within their project,
+    /// the same way `SessionStats` are keyed, since an id can recur across projects.
+    pub fn session_entries<'a>(&self, entries: &'a [UsageEntry], project: &str, session_id: &str) -> Vec<&'a UsageEntry> {
+        let mut session_entries: Vec<&UsageEntry> = entries
+            .iter()
+            .filter(|e| e.session_id.as_deref() == Some(session_id) && self.project_key(e) == project)
+            .collect();
+        session_entries.sort_by_key(|e| e.timestamp);
+        session_entries
+    }
+
+    /// Rank models by tokens per dollar, highest first; models without cost are left out
+    /// since the ratio is undefined for them
+    pub fn model_efficiency(&self, models: &[ModelStats]) -> Vec<ModelEfficiency> {
+        let mut ranking: Vec<ModelEfficiency> = models
+            .iter()
+            .filter(|m| m.total_cost > 0.0)
+            .map(|m| ModelEfficiency {
+                model: m.model.clone(),
+                display_name: m.display_name.clone(),
+                tokens_per_dollar: m.total_tokens as f64 / m.total_cost,
+            })
+            .collect();
+        ranking.sort_by(|a, b| b.tokens_per_dollar.total_cmp(&a.tokens_per_dollar));
+        ranking
+    }
+
     /// Calculate project-wise statistics
     pub fn calculate_project_stats(&self, entries: &[UsageEntry]) -> Vec<ProjectStats> {
         let mut project_map: HashMap<String, ProjectStats> = HashMap::new();
 
         for entry in entries {
-            let project_path = entry.project_path.clone().unwrap_or_else(|| "Unknown Project".to_string());
+            let project_path = self.project_key(entry);
             let project_name = self.extract_project_name(&project_path);
 
             let project_stat = project_map.entry(project_path.clone()).or_insert_with(|| {
@@ -177,16 +555,20 @@ impl UsageAggregator {
                     cache_creation_tokens: 0,
                     request_count: 0,
                     session_count: 0,
+                    cost_per_request: 0.0,
+                    avg_session_cost: 0.0,
+                    active_days: 0,
+                    sessions_per_active_day: 0.0,
                     last_used: entry.timestamp,
                 }
             });
 
-            project_stat.total_cost += entry.cost;
-            project_stat.input_tokens += entry.input_tokens as u64;
-            project_stat.output_tokens += entry.output_tokens as u64;
-            project_stat.cache_read_tokens += entry.cache_read_tokens as u64;
-            project_stat.cache_creation_tokens += entry.cache_creation_tokens as u64;
-            project_stat.total_tokens = project_stat.input_tokens + project_stat.output_tokens + project_stat.cache_read_tokens + project_stat.cache_creation_tokens;
+            project_stat.total_cost += sane_cost(entry.cost);
+            project_stat.input_tokens += entry.input_tokens;
+            project_stat.output_tokens += entry.output_tokens;
+            project_stat.cache_read_tokens += entry.cache_read_tokens;
+            project_stat.cache_creation_tokens += entry.cache_creation_tokens;
+            project_stat.total_tokens = self.token_total(project_stat.input_tokens, project_stat.output_tokens, project_stat.cache_read_tokens, project_stat.cache_creation_tokens);
             project_stat.request_count += 1;
 
             if entry.timestamp > project_stat.last_used {
@@ -194,36 +576,71 @@ impl UsageAggregator {
             }
         }
 
-        // Count unique sessions per project
+        // Count unique sessions and active days per project
         for project_stat in project_map.values_mut() {
-            let sessions: std::collections::HashSet<String> = entries.iter()
-                .filter(|e| e.project_path.as_ref() == Some(&project_stat.project_path))
+            let project_entries: Vec<&UsageEntry> = entries.iter()
+                .filter(|e| self.project_key(e) == project_stat.project_path)
+                .collect();
+            let sessions: std::collections::HashSet<&String> = project_entries.iter()
                 .filter_map(|e| e.session_id.as_ref())
-                .cloned()
+                .collect();
+            let days: std::collections::HashSet<NaiveDate> = project_entries.iter()
+                .map(|e| self.local_date(&e.timestamp))
                 .collect();
             project_stat.session_count = sessions.len();
+            project_stat.active_days = days.len();
+            project_stat.cost_per_request = Self::average_over(project_stat.total_cost, project_stat.request_count);
+            project_stat.avg_session_cost = Self::average_over(project_stat.total_cost, project_stat.session_count);
+            project_stat.sessions_per_active_day =
+                Self::average_over(project_stat.session_count as f64, project_stat.active_days);
         }
 
         let mut project_stats: Vec<ProjectStats> = project_map.into_values().collect();
-        project_stats.sort_by(|a, b| b.total_cost.partial_cmp(&a.total_cost).unwrap_or(std::cmp::Ordering::Equal));
+        project_stats.sort_by(|a, b| b.total_cost.total_cmp(&a.total_cost));
         project_stats
     }
 
+    /// `total / count`, or 0 when there is nothing to divide by
+    pub fn average_over(total: f64, count: usize) -> f64 {
+        if count > 0 {
+            total / count as f64
+        } else {
+            0.0
+        }
+    }
+
+    /// Order projects by the given sort key (highest first)
+    pub fn sort_projects(&self, projects: &mut [ProjectStats], sort: ProjectSort) {
+        match sort {
+            ProjectSort::TotalCost => projects.sort_by(|a, b| b.total_cost.total_cmp(&a.total_cost)),
+            ProjectSort::CostPerRequest => projects.sort_by(|a, b| b.cost_per_request.total_cmp(&a.cost_per_request)),
+        }
+    }
+
+    /// Take pinned projects out of an already sorted list, in pin order, leaving the
+    /// rest in their sorted order: (pinned, others)
+    pub fn split_pinned_projects(
+        projects: Vec<ProjectStats>,
+        pinned: &[String],
+    ) -> (Vec<ProjectStats>, Vec<ProjectStats>) {
+        let (mut pinned_projects, others): (Vec<ProjectStats>, Vec<ProjectStats>) =
+            projects.into_iter().partition(|p| pinned.contains(&p.project_path));
+        pinned_projects.sort_by_key(|p| pinned.iter().position(|path| *path == p.project_path));
+        (pinned_projects, others)
+    }
+
     /// Calculate session-wise statistics
     pub fn calculate_session_stats(&self, entries: &[UsageEntry]) -> Vec<SessionStats> {
         let mut session_map: HashMap<String, SessionStats> = HashMap::new();
 
         for entry in entries {
-            let session_key = format!(
-                "{}:{}",
-                entry.project_path.as_deref().unwrap_or("unknown"),
-                entry.session_id.as_deref().unwrap_or("unknown")
-            );
+            let project_path = self.project_key(entry);
+            let session_key = SessionStats::key_for(&project_path, entry.session_id.as_deref().unwrap_or("Unknown"));
 
             let session_stat = session_map.entry(session_key).or_insert_with(|| {
                 SessionStats {
                     session_id: entry.session_id.clone().unwrap_or_else(|| "Unknown".to_string()),
-                    project_path: entry.project_path.clone().unwrap_or_else(|| "Unknown Project".to_string()),
+                    project_path: project_path.clone(),
                     total_cost: 0.0,
                     total_tokens: 0,
                     input_tokens: 0,
@@ -231,25 +648,25 @@ impl UsageAggregator {
                     cache_read_tokens: 0,
                     cache_creation_tokens: 0,
                     request_count: 0,
-                    timestamp: entry.timestamp,
+                    start_time: entry.timestamp,
+                    end_time: entry.timestamp,
                 }
             });
 
-            session_stat.total_cost += entry.cost;
-            session_stat.input_tokens += entry.input_tokens as u64;
-            session_stat.output_tokens += entry.output_tokens as u64;
-            session_stat.cache_read_tokens += entry.cache_read_tokens as u64;
-            session_stat.cache_creation_tokens += entry.cache_creation_tokens as u64;
-            session_stat.total_tokens = session_stat.input_tokens + session_stat.output_tokens + session_stat.cache_read_tokens + session_stat.cache_creation_tokens;
+            session_stat.total_cost += sane_cost(entry.cost);
+            session_stat.input_tokens += entry.input_tokens;
+            session_stat.output_tokens += entry.output_tokens;
+            session_stat.cache_read_tokens += entry.cache_read_tokens;
+            session_stat.cache_creation_tokens += entry.cache_creation_tokens;
+            session_stat.total_tokens = self.token_total(session_stat.input_tokens, session_stat.output_tokens, session_stat.cache_read_tokens, session_stat.cache_creation_tokens);
             session_stat.request_count += 1;
 
-            if entry.timestamp > session_stat.timestamp {
-                session_stat.timestamp = entry.timestamp;
-            }
+            session_stat.start_time = session_stat.start_time.min(entry.timestamp);
+            session_stat.end_time = session_stat.end_time.max(entry.timestamp);
         }
 
         let mut session_stats: Vec<SessionStats> = session_map.into_values().collect();
-        session_stats.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        session_stats.sort_by(|a, b| b.end_time.cmp(&a.end_time));
         session_stats
     }
 
@@ -258,7 +675,7 @@ impl UsageAggregator {
         let mut daily_map: HashMap<String, DailyUsage> = HashMap::new();
 
         for entry in entries {
-            let date_key = entry.timestamp.format("%Y-%m-%d").to_string();
+            let date_key = self.local_date(&entry.timestamp).format("%Y-%m-%d").to_string();
 
             let daily_stat = daily_map.entry(date_key.clone()).or_insert_with(|| {
                 DailyUsage {
@@ -274,12 +691,12 @@ impl UsageAggregator {
                 }
             });
 
-            daily_stat.total_cost += entry.cost;
-            daily_stat.input_tokens += entry.input_tokens as u64;
-            daily_stat.output_tokens += entry.output_tokens as u64;
-            daily_stat.cache_read_tokens += entry.cache_read_tokens as u64;
-            daily_stat.cache_creation_tokens += entry.cache_creation_tokens as u64;
-            daily_stat.total_tokens = daily_stat.input_tokens + daily_stat.output_tokens + daily_stat.cache_read_tokens + daily_stat.cache_creation_tokens;
+            daily_stat.total_cost += sane_cost(entry.cost);
+            daily_stat.input_tokens += entry.input_tokens;
+            daily_stat.output_tokens += entry.output_tokens;
+            daily_stat.cache_read_tokens += entry.cache_read_tokens;
+            daily_stat.cache_creation_tokens += entry.cache_creation_tokens;
+            daily_stat.total_tokens = self.token_total(daily_stat.input_tokens, daily_stat.output_tokens, daily_stat.cache_read_tokens, daily_stat.cache_creation_tokens);
             daily_stat.request_count += 1;
 
             if !daily_stat.models_used.contains(&entry.model) {
@@ -292,6 +709,255 @@ impl UsageAggregator {
         daily_stats
     }
 
+    /// Order sessions for the Sessions table; ties fall back to newest first
+    pub fn sort_sessions(&self, sessions: &mut [SessionStats], sort: SessionSort) {
+        sessions.sort_by(|a, b| {
+            let ordering = match sort.column {
+                SessionSortColumn::Project => a.project_path.to_lowercase().cmp(&b.project_path.to_lowercase()),
+                SessionSortColumn::Date => a.start_time.cmp(&b.start_time),
+                SessionSortColumn::Cost => a.total_cost.total_cmp(&b.total_cost),
+                SessionSortColumn::Tokens => a.total_tokens.cmp(&b.total_tokens),
+                SessionSortColumn::Requests => a.request_count.cmp(&b.request_count),
+            };
+            let ordering = if sort.descending { ordering.reverse() } else { ordering };
+            ordering.then_with(|| b.start_time.cmp(&a.start_time))
+        });
+    }
+
+    /// Sessions whose project path or id contains `query`, ignoring case; a blank query keeps all
+    pub fn filter_sessions(sessions: Vec<SessionStats>, query: &str) -> Vec<SessionStats> {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return sessions;
+        }
+        sessions
+            .into_iter()
+            .filter(|s| s.project_path.to_lowercase().contains(&query) || s.session_id.to_lowercase().contains(&query))
+            .collect()
+    }
+
+    /// Project with the highest cost among `entries`; ties go to the alphabetically first name
+    pub fn busiest_project(&self, entries: &[UsageEntry]) -> Option<ProjectStats> {
+        self.calculate_project_stats(entries)
+            .into_iter()
+            .max_by(|a, b| {
+                a.total_cost
+                    .total_cmp(&b.total_cost)
+                    .then_with(|| b.project_name.cmp(&a.project_name))
+            })
+    }
+
+    /// The `limit` costliest items, highest first; equal costs are ordered by name so the
+    /// ranking doesn't shuffle between renders
+    pub fn top_by_cost<T>(
+        mut items: Vec<T>,
+        limit: usize,
+        cost_of: impl Fn(&T) -> f64,
+        name_of: impl Fn(&T) -> &str,
+    ) -> Vec<T> {
+        items.sort_by(|a, b| cost_of(b).total_cmp(&cost_of(a)).then_with(|| name_of(a).cmp(name_of(b))));
+        items.truncate(limit);
+        items
+    }
+
+    /// Split items into those shown individually and those below `threshold`, which are
+    /// grouped into a single "Other" row. Nothing is grouped unless at least two items fall
+    /// below the cutoff, since an "Other" row holding one item hides it for no gain.
+    pub fn split_minor_contributors<T>(
+        items: Vec<T>,
+        cost_of: impl Fn(&T) -> f64,
+        threshold: CostThreshold,
+    ) -> (Vec<T>, Vec<T>) {
+        if threshold.is_disabled() {
+            return (items, Vec::new());
+        }
+        let total: f64 = items.iter().map(&cost_of).sum();
+        let cutoff = threshold.cutoff(total);
+        let (major, minor): (Vec<T>, Vec<T>) = items.into_iter().partition(|item| cost_of(item) >= cutoff);
+        if minor.len() < 2 {
+            let mut all = major;
+            all.extend(minor);
+            return (all, Vec::new());
+        }
+        (major, minor)
+    }
+
+    /// Cache creation versus cache read tokens and their priced costs
+    pub fn cache_efficiency(&self, entries: &[UsageEntry]) -> CacheEfficiency {
+        entries.iter().fold(CacheEfficiency::default(), |mut cache, entry| {
+            let breakdown = self.cost_calculator.calculate_cost_breakdown_at(
+                &entry.model,
+                &entry.timestamp,
+                0,
+                0,
+                entry.cache_read_tokens,
+                entry.cache_creation_tokens,
+            );
+            cache.creation_tokens += entry.cache_creation_tokens;
+            cache.read_tokens += entry.cache_read_tokens;
+            cache.creation_cost += breakdown.cache_write_cost;
+            cache.read_cost += breakdown.cache_read_cost;
+            cache
+        })
+    }
+
+    /// Dates whose cost exceeds the mean by more than two standard deviations.
+    /// Returns nothing when there are too few active days for the statistics to mean much.
+    pub fn anomalous_days(&self, daily_usage: &[DailyUsage]) -> Vec<String> {
+        const MIN_ACTIVE_DAYS: usize = 5;
+
+        let active: Vec<&DailyUsage> = daily_usage.iter().filter(|d| d.request_count > 0).collect();
+        if active.len() < MIN_ACTIVE_DAYS {
+            return Vec::new();
+        }
+
+        let count = active.len() as f64;
+        let mean = active.iter().map(|d| d.total_cost).sum::<f64>() / count;
+        let variance = active.iter().map(|d| (d.total_cost - mean).powi(2)).sum::<f64>() / count;
+        let threshold = mean + 2.0 * variance.sqrt();
+
+        let mut dates: Vec<String> = active
+            .iter()
+            .filter(|d| d.total_cost > threshold)
+            .map(|d| d.date.clone())
+            .collect();
+        dates.sort();
+        dates
+    }
+
+    /// Trailing mean of daily cost over `window` entries, one value per entry in the given order.
+    /// Until `window` entries are available the mean covers the entries seen so far.
+    pub fn rolling_average(daily: &[DailyUsage], window: usize) -> Vec<f64> {
+        let window = window.max(1);
+        let mut sum = 0.0;
+        daily
+            .iter()
+            .enumerate()
+            .map(|(index, day)| {
+                sum += day.total_cost;
+                if index >= window {
+                    sum -= daily[index - window].total_cost;
+                }
+                sum / (index + 1).min(window) as f64
+            })
+            .collect()
+    }
+
+    /// Daily usage sorted by date, with zero-usage entries for any days missing in between
+    pub fn fill_missing_days(daily: &[DailyUsage]) -> Vec<DailyUsage> {
+        let mut days: Vec<(NaiveDate, &DailyUsage)> = daily
+            .iter()
+            .filter_map(|day| NaiveDate::parse_from_str(&day.date, "%Y-%m-%d").ok().map(|date| (date, day)))
+            .collect();
+        days.sort_by_key(|(date, _)| *date);
+        let (Some(&(first, _)), Some(&(last, _))) = (days.first(), days.last()) else {
+            return Vec::new();
+        };
+
+        let mut remaining = days.into_iter().peekable();
+        first
+            .iter_days()
+            .take_while(|date| *date <= last)
+            .map(|date| match remaining.next_if(|(day_date, _)| *day_date == date) {
+                Some((_, day)) => day.clone(),
+                None => DailyUsage {
+                    date: date.format("%Y-%m-%d").to_string(),
+                    total_cost: 0.0,
+                    total_tokens: 0,
+                    input_tokens: 0,
+                    output_tokens: 0,
+                    cache_read_tokens: 0,
+                    cache_creation_tokens: 0,
+                    request_count: 0,
+                    models_used: Vec::new(),
+                },
+            })
+            .collect()
+    }
+
+    /// Idle days and usage frequency over the full calendar span between the first and last
+    /// day with requests; None when nothing was used
+    pub fn usage_frequency(daily: &[DailyUsage]) -> Option<UsageFrequency> {
+        let active: Vec<DailyUsage> = daily.iter().filter(|day| day.request_count > 0).cloned().collect();
+        let calendar = Self::fill_missing_days(&active);
+        if calendar.is_empty() {
+            return None;
+        }
+
+        let active_days = calendar.iter().filter(|day| day.request_count > 0).count();
+        Some(UsageFrequency {
+            span_days: calendar.len(),
+            active_days,
+            idle_days: calendar.len() - active_days,
+        })
+    }
+
+    /// Start date of the week containing `date` for the given first weekday
+    pub fn week_start_for(date: NaiveDate, week_start: Weekday) -> NaiveDate {
+        let offset = (date.weekday().num_days_from_monday() + 7 - week_start.num_days_from_monday()) % 7;
+        date - Duration::days(offset as i64)
+    }
+
+    /// The seven weekdays in display order, beginning with `week_start`
+    pub fn weekdays_from(week_start: Weekday) -> [Weekday; 7] {
+        let mut day = week_start;
+        std::array::from_fn(|_| {
+            let current = day;
+            day = day.succ();
+            current
+        })
+    }
+
+    /// Total cost per local weekday, in the order given by `weekdays_from` for the configured week start
+    pub fn cost_by_weekday(&self, entries: &[UsageEntry]) -> [f64; 7] {
+        let first = self.options.week_start.num_days_from_monday();
+        let mut totals = [0.0; 7];
+        for entry in entries {
+            let weekday = self.local_date(&entry.timestamp).weekday().num_days_from_monday();
+            totals[((weekday + 7 - first) % 7) as usize] += sane_cost(entry.cost);
+        }
+        totals
+    }
+
+    /// Position of `date` in a calendar grid whose first column is the week containing `first_day`:
+    /// (week column, row within the week). `date` must not precede `first_day`.
+    pub fn calendar_cell(date: NaiveDate, first_day: NaiveDate, week_start: Weekday) -> (usize, usize) {
+        let first_column = Self::week_start_for(first_day, week_start);
+        let column_start = Self::week_start_for(date, week_start);
+        let column = (column_start - first_column).num_days() / 7;
+        let row = (date - column_start).num_days();
+        (column as usize, row as usize)
+    }
+
+    /// Group daily usage into weeks starting on the configured weekday
+    pub fn group_daily_usage_by_week(&self, daily_usage: &[DailyUsage]) -> Vec<WeeklyUsage> {
+        let mut weekly_map: HashMap<NaiveDate, WeeklyUsage> = HashMap::new();
+
+        for day in daily_usage {
+            let Ok(date) = NaiveDate::parse_from_str(&day.date, "%Y-%m-%d") else {
+                continue;
+            };
+            let week_start = Self::week_start_for(date, self.options.week_start);
+
+            let week = weekly_map.entry(week_start).or_insert_with(|| WeeklyUsage {
+                week_start: week_start.format("%Y-%m-%d").to_string(),
+                total_cost: 0.0,
+                total_tokens: 0,
+                request_count: 0,
+                days_count: 0,
+            });
+
+            week.total_cost += day.total_cost;
+            week.total_tokens += day.total_tokens;
+            week.request_count += day.request_count;
+            week.days_count += 1;
+        }
+
+        let mut weekly_usage: Vec<WeeklyUsage> = weekly_map.into_values().collect();
+        weekly_usage.sort_by(|a, b| a.week_start.cmp(&b.week_start));
+        weekly_usage
+    }
+
     /// Calculate average cost per session
     #[allow(dead_code)] // Feature planned for future implementation
     pub fn calculate_avg_cost_per_session(&self, stats: &UsageStats) -> f64 {
@@ -305,8 +971,8 @@ impl UsageAggregator {
     /// Count active days
     #[allow(dead_code)] // Feature planned for future implementation
     pub fn count_active_days(&self, entries: &[UsageEntry]) -> usize {
-        let unique_dates: std::collections::HashSet<String> = entries.iter()
-            .map(|e| e.timestamp.format("%Y-%m-%d").to_string())
+        let unique_dates: std::collections::HashSet<NaiveDate> = entries.iter()
+            .map(|e| self.local_date(&e.timestamp))
             .collect();
         unique_dates.len()
     }
@@ -318,14 +984,22 @@ impl UsageAggregator {
         if active_days == 0 {
             0.0
         } else {
-            let total_cost: f64 = entries.iter().map(|e| e.cost).sum();
+            let total_cost: f64 = entries.iter().map(|e| sane_cost(e.cost)).sum();
             total_cost / active_days as f64
         }
     }
 
     /// Extract project name from path
-    fn extract_project_name(&self, project_path: &str) -> String {
+    pub fn extract_project_name(&self, project_path: &str) -> String {
         // Split the path into components
+        let project_path = normalize_project_path(project_path);
+
+        // The markers below assume an absolute path; a relative one is shown as logged,
+        // flagged so it can't pass for the absolute project sharing its last directory
+        if is_relative_project_path(&project_path) {
+            let trimmed = project_path.trim_start_matches("./");
+            return format!("(relative) {}", if trimmed.is_empty() { "." } else { trimmed });
+        }
         let components: Vec<&str> = project_path.split('/').filter(|s| !s.is_empty()).collect();
         
         // Try to find common project directory patterns
@@ -353,8 +1027,1247 @@ impl UsageAggregator {
         components.last().unwrap_or(&"Unknown").to_string()
     }
     
-    /// Alias for calculate_usage_stats (compatibility)
+    /// Alias for calculate_usage_stats (compatibility).
+    /// Also the seam for building stats from in-memory entries without a `UsageProcessor`.
     pub fn aggregate_entries(&self, entries: Vec<UsageEntry>) -> UsageStats {
         self.calculate_usage_stats(&entries)
     }
-}
\ No newline at end of file
+
+    /// Stats for the entries that fall inside `time_range`
+    pub fn stats_for_range(&self, entries: &[UsageEntry], time_range: TimeRange) -> UsageStats {
+        self.stats_for_range_at(entries, time_range, Utc::now())
+    }
+
+    /// Stats for the entries that fall inside `time_range` relative to `now`
+    pub fn stats_for_range_at(&self, entries: &[UsageEntry], time_range: TimeRange, now: DateTime<Utc>) -> UsageStats {
+        let filtered = self.counted_entries(self.filter_by_time_range_at(entries, time_range, now));
+        self.calculate_usage_stats(&filtered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use chrono_tz::Tz;
+    #[cfg(unix)]
+    use std::path::{Path, PathBuf};
+
+    /// Directory unique to one test, removed on drop so a failed assertion doesn't leak it
+    #[cfg(unix)] // Only the symlink test needs real files
+    struct ScratchDir(PathBuf);
+
+    #[cfg(unix)]
+    impl ScratchDir {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("usage-dashboard-{}-{}", name, std::process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(&dir).unwrap();
+            Self(dir)
+        }
+
+        fn path(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    #[cfg(unix)]
+    impl Drop for ScratchDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn entry(project: &str, session: &str, cost: f64) -> UsageEntry {
+        UsageEntry {
+            timestamp: Utc.with_ymd_and_hms(2025, 7, 1, 12, 0, 0).unwrap(),
+            model: "claude-sonnet-4-20250514".to_string(),
+            project_path: Some(project.to_string()),
+            session_id: Some(session.to_string()),
+            request_id: None,
+            input_tokens: 100,
+            output_tokens: 50,
+            cache_read_tokens: 0,
+            cache_creation_tokens: 0,
+            cost,
+            computed_cost: cost,
+            logged_cost: None,
+            future_dated: false,
+            raw_line: None,
+        }
+    }
+
+    fn cached_entry(project: &str, session: &str) -> UsageEntry {
+        UsageEntry {
+            cache_read_tokens: 1_000,
+            cache_creation_tokens: 200,
+            ..entry(project, session, 0.10)
+        }
+    }
+
+    fn assert_totals_agree(stats: &UsageStats, expected: u64) {
+        assert_eq!(stats.total_tokens, expected);
+        let model_total: u64 = stats.model_stats.values().map(|m| m.total_tokens).sum();
+        let project_total: u64 = stats.project_stats.values().map(|p| p.total_tokens).sum();
+        let session_total: u64 = stats.session_stats.values().map(|s| s.total_tokens).sum();
+        let daily_total: u64 = stats.daily_usage.values().map(|d| d.total_tokens).sum();
+        assert_eq!(model_total, expected);
+        assert_eq!(project_total, expected);
+        assert_eq!(session_total, expected);
+        assert_eq!(daily_total, expected);
+    }
+
+    #[test]
+    fn test_token_total_policy_include_cache() {
+        let entries = vec![cached_entry("/Users/dev/a", "s1"), cached_entry("/Users/dev/b", "s2")];
+        let aggregator = UsageAggregator::with_options(AggregationOptions {
+            token_total_policy: TokenTotalPolicy::IncludeCache,
+            ..AggregationOptions::default()
+        });
+        let stats = aggregator.calculate_usage_stats(&entries);
+        assert_totals_agree(&stats, 2 * (100 + 50 + 1_000 + 200));
+    }
+
+    #[test]
+    fn test_ignore_cache_drops_exactly_the_cache_contribution() {
+        let now = Utc.with_ymd_and_hms(2025, 7, 2, 12, 0, 0).unwrap();
+        let calculator = CostCalculator::new();
+        let priced = |e: UsageEntry| UsageEntry {
+            cost: calculator.calculate_cost(&e.model, e.input_tokens, e.output_tokens, e.cache_read_tokens, e.cache_creation_tokens),
+            ..e
+        };
+        let entries = vec![
+            priced(cached_entry("/Users/dev/a", "s1")),
+            priced(cached_entry("/Users/dev/b", "s2")),
+        ];
+        // Sonnet 4: 1,000 reads at $0.30/M plus 200 writes at $3.75/M per entry
+        let cache_cost = 2.0 * (1_000.0 * 0.30 + 200.0 * 3.75) / 1_000_000.0;
+
+        let with_cache = UsageAggregator::new().stats_for_range_at(&entries, TimeRange::AllTime, now);
+        let ignoring = UsageAggregator::with_options(AggregationOptions {
+            ignore_cache: true,
+            ..AggregationOptions::default()
+        });
+        let without_cache = ignoring.stats_for_range_at(&entries, TimeRange::AllTime, now);
+
+        assert!((with_cache.total_cost - without_cache.total_cost - cache_cost).abs() < 1e-12);
+        assert_eq!(with_cache.total_tokens - without_cache.total_tokens, 2 * (1_000 + 200));
+        assert_eq!((without_cache.total_cache_read_tokens, without_cache.total_cache_creation_tokens), (0, 0));
+        assert_totals_agree(&without_cache, 2 * (100 + 50));
+        let model_cost: f64 = without_cache.model_stats.values().map(|m| m.total_cost).sum();
+        assert!((model_cost - without_cache.total_cost).abs() < 1e-12);
+        assert!(
+            (UsageAggregator::new().month_to_date_cost_at(&entries, now)
+                - ignoring.month_to_date_cost_at(&entries, now)
+                - cache_cost)
+                .abs()
+                < 1e-12
+        );
+    }
+
+    #[test]
+    fn test_token_total_policy_exclude_cache() {
+        let entries = vec![cached_entry("/Users/dev/a", "s1"), cached_entry("/Users/dev/b", "s2")];
+        let aggregator = UsageAggregator::with_options(AggregationOptions {
+            token_total_policy: TokenTotalPolicy::ExcludeCache,
+            ..AggregationOptions::default()
+        });
+        let stats = aggregator.calculate_usage_stats(&entries);
+        assert_totals_agree(&stats, 2 * (100 + 50));
+        // Category totals are unaffected by the policy
+        assert_eq!(stats.total_cache_read_tokens, 2_000);
+    }
+
+    #[test]
+    fn test_project_category_totals_match_overall() {
+        let entries = vec![
+            cached_entry("/Users/dev/a", "s1"),
+            entry("/Users/dev/b", "s2", 0.5),
+            cached_entry("/Users/dev/b", "s3"),
+        ];
+        let stats = UsageAggregator::new().calculate_usage_stats(&entries);
+        let project_total =
+            |category: TokenCategory| stats.project_stats.values().map(|p| p.tokens(category)).sum::<u64>();
+
+        assert_eq!(project_total(TokenCategory::Input), stats.total_input_tokens);
+        assert_eq!(project_total(TokenCategory::Output), stats.total_output_tokens);
+        assert_eq!(project_total(TokenCategory::CacheRead), stats.total_cache_read_tokens);
+        assert_eq!(project_total(TokenCategory::CacheCreation), stats.total_cache_creation_tokens);
+        assert_eq!(project_total(TokenCategory::CacheRead), 2_000);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlinked_projects_collapse() {
+        let scratch = ScratchDir::new("symlink");
+        let root = scratch.path();
+        let real = root.join("code").join("app");
+        fs::create_dir_all(&real).unwrap();
+        let link = root.join("app-link");
+        std::os::unix::fs::symlink(&real, &link).unwrap();
+        let real = fs::canonicalize(&real).unwrap().display().to_string();
+        let gone = root.join("deleted").display().to_string();
+
+        let entries = vec![
+            entry(&real, "s1", 1.0),
+            entry(&link.display().to_string(), "s2", 2.0),
+            entry(&gone, "s3", 4.0),
+        ];
+
+        // Off by default: the link is its own project
+        assert_eq!(UsageAggregator::new().calculate_project_stats(&entries).len(), 3);
+
+        let aggregator = UsageAggregator::with_options(AggregationOptions {
+            resolve_symlinks: true,
+            ..AggregationOptions::default()
+        });
+        let stats = aggregator.calculate_usage_stats(&aggregator.counted_entries(entries));
+        assert_eq!(stats.project_stats.len(), 2);
+        assert!((stats.project_stats[&real].total_cost - 3.0).abs() < 1e-9);
+        assert_eq!(stats.project_stats[&real].session_count, 2);
+        // A path that no longer exists is kept as-is
+        assert!((stats.project_stats[&gone].total_cost - 4.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_top_by_cost_ranking() {
+        let items = vec![("delta", 1.0), ("alpha", 3.0), ("charlie", 3.0), ("bravo", 5.0), ("echo", 0.5), ("foxtrot", 2.0)];
+        let names = |top: Vec<(&'static str, f64)>| top.into_iter().map(|(name, _)| name).collect::<Vec<_>>();
+
+        let top = UsageAggregator::top_by_cost(items.clone(), 5, |item| item.1, |item| item.0);
+        // Ties on $3.00 fall back to alphabetical order
+        assert_eq!(names(top), vec!["bravo", "alpha", "charlie", "foxtrot", "delta"]);
+
+        let few = UsageAggregator::top_by_cost(items[..2].to_vec(), 5, |item| item.1, |item| item.0);
+        assert_eq!(names(few), vec!["alpha", "delta"]);
+        assert!(UsageAggregator::top_by_cost(Vec::<(&str, f64)>::new(), 5, |item| item.1, |item| item.0).is_empty());
+    }
+
+    #[test]
+    fn test_diff_periods() {
+        let at = |day: u32| Utc.with_ymd_and_hms(2025, 7, day, 12, 0, 0).unwrap();
+        let dated = |day: u32, project: &str, model: &str, cost: f64| UsageEntry {
+            timestamp: at(day),
+            model: model.to_string(),
+            ..entry(project, "s1", cost)
+        };
+        let sonnet = "claude-sonnet-4-20250514";
+        let opus = "claude-opus-4-20250514";
+        let entries = vec![
+            // Week A (1-7 July)
+            dated(2, "/Users/dev/api", sonnet, 2.0),
+            dated(3, "/Users/dev/api", sonnet, 1.0),
+            dated(4, "/Users/dev/legacy", sonnet, 4.0),
+            // Week B (8-14 July)
+            dated(9, "/Users/dev/api", sonnet, 1.0),
+            dated(10, "/Users/dev/api", opus, 6.0),
+            dated(11, "/Users/dev/web", sonnet, 0.5),
+            // Outside both periods
+            dated(20, "/Users/dev/web", sonnet, 100.0),
+        ];
+        let range_b = Period::days_ending(at(15) - Duration::hours(12), 7);
+        let range_a = range_b.previous();
+        assert_eq!(range_a.end, range_b.start);
+
+        let diff = UsageAggregator::new().diff_periods(&entries, range_a, range_b);
+        assert!((diff.total_cost_a - 7.0).abs() < 1e-9);
+        assert!((diff.total_cost_b - 7.5).abs() < 1e-9);
+        assert!((diff.cost_change_ratio().unwrap() - 0.5 / 7.0).abs() < 1e-9);
+
+        let project = |name: &str| diff.projects.iter().find(|p| p.name == name).unwrap();
+        assert!((project("api").cost_delta() - 4.0).abs() < 1e-9);
+        assert!(project("legacy").is_removed());
+        assert!((project("legacy").cost_delta() + 4.0).abs() < 1e-9);
+        assert!(project("web").is_added());
+        assert_eq!(project("web").requests_b, 1);
+        // Equal-sized moves rank alphabetically, ahead of the small one
+        let order: Vec<&str> = diff.projects.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(order, vec!["api", "legacy", "web"]);
+
+        assert_eq!(diff.models.len(), 2);
+        let opus_delta = diff.models.iter().find(|m| m.key == opus).unwrap();
+        assert!(opus_delta.is_added());
+        let sonnet_delta = diff.models.iter().find(|m| m.key == sonnet).unwrap();
+        assert!((sonnet_delta.cost_delta() + 5.5).abs() < 1e-9);
+        assert!(!sonnet_delta.is_added() && !sonnet_delta.is_removed());
+    }
+
+    #[test]
+    fn test_cost_per_request_ranking() {
+        let aggregator = UsageAggregator::new();
+        let mut entries = Vec::new();
+        // Cheap but chatty: 10 requests at $0.50 = $5.00 total
+        for _ in 0..10 {
+            entries.push(entry("/Users/dev/chatty", "s1", 0.50));
+        }
+        // Expensive but rare: 1 request at $2.00
+        entries.push(entry("/Users/dev/rare", "s2", 2.00));
+
+        let mut projects = aggregator.calculate_project_stats(&entries);
+        assert_eq!(projects[0].project_name, "chatty");
+
+        aggregator.sort_projects(&mut projects, ProjectSort::CostPerRequest);
+        assert_eq!(projects[0].project_name, "rare");
+        assert!((projects[0].cost_per_request - 2.00).abs() < 1e-9);
+        assert_eq!(projects[1].project_name, "chatty");
+        assert!((projects[1].cost_per_request - 0.50).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_project_session_cost_and_frequency() {
+        let aggregator = UsageAggregator::with_options(AggregationOptions {
+            timezone: Some(Tz::UTC),
+            ..AggregationOptions::default()
+        });
+        let next_day = |session: &str, cost| UsageEntry {
+            timestamp: Utc.with_ymd_and_hms(2025, 7, 2, 9, 0, 0).unwrap(),
+            ..entry("/Users/dev/busy", session, cost)
+        };
+        let entries = vec![
+            // Three sessions over two days
+            entry("/Users/dev/busy", "s1", 1.0),
+            entry("/Users/dev/busy", "s1", 1.0),
+            entry("/Users/dev/busy", "s2", 2.0),
+            next_day("s3", 2.0),
+            // One session on one day
+            entry("/Users/dev/once", "s4", 0.75),
+        ];
+
+        let projects = aggregator.calculate_project_stats(&entries);
+        let busy = projects.iter().find(|p| p.project_name == "busy").unwrap();
+        assert_eq!((busy.session_count, busy.active_days), (3, 2));
+        assert!((busy.avg_session_cost - 2.0).abs() < 1e-9);
+        assert!((busy.sessions_per_active_day - 1.5).abs() < 1e-9);
+
+        let once = projects.iter().find(|p| p.project_name == "once").unwrap();
+        assert!((once.avg_session_cost - 0.75).abs() < 1e-9);
+        assert!((once.sessions_per_active_day - 1.0).abs() < 1e-9);
+
+        // Requests without a session id: no sessions to average over
+        let sessionless = UsageEntry { session_id: None, ..entry("/Users/dev/cli", "", 1.0) };
+        let projects = aggregator.calculate_project_stats(&[sessionless]);
+        assert_eq!((projects[0].session_count, projects[0].active_days), (0, 1));
+        assert_eq!(projects[0].avg_session_cost, 0.0);
+        assert_eq!(projects[0].sessions_per_active_day, 0.0);
+
+        // Nothing to divide by
+        assert_eq!(UsageAggregator::average_over(3.0, 0), 0.0);
+        assert_eq!(UsageAggregator::average_over(0.0, 0), 0.0);
+    }
+
+    #[test]
+    fn test_shared_session_id_across_projects() {
+        let entries = vec![
+            entry("/Users/dev/api", "shared", 1.0),
+            entry("/Users/dev/api", "shared", 2.0),
+            entry("/Users/dev/web", "shared", 4.0),
+        ];
+
+        let stats = UsageAggregator::new().calculate_usage_stats(&entries);
+        assert_eq!(stats.session_count, 2);
+        assert_eq!(stats.session_stats.len(), 2);
+        let api = &stats.session_stats[&SessionStats::key_for("/Users/dev/api", "shared")];
+        let web = &stats.session_stats[&SessionStats::key_for("/Users/dev/web", "shared")];
+        assert!((api.total_cost - 3.0).abs() < 1e-9);
+        assert_eq!(api.request_count, 2);
+        assert!((web.total_cost - 4.0).abs() < 1e-9);
+        assert_eq!(web.key(), SessionStats::key_for("/Users/dev/web", "shared"));
+    }
+
+    #[test]
+    fn test_pinned_projects_float_to_top() {
+        let aggregator = UsageAggregator::new();
+        let entries = vec![
+            entry("/Users/dev/big", "s1", 9.0),
+            entry("/Users/dev/medium", "s2", 5.0),
+            entry("/Users/dev/small", "s3", 1.0),
+            entry("/Users/dev/tiny", "s4", 0.5),
+        ];
+        let mut projects = aggregator.calculate_project_stats(&entries);
+        aggregator.sort_projects(&mut projects, ProjectSort::TotalCost);
+
+        // Pin order wins over cost; a pin for a project with no usage is ignored
+        let pinned = vec![
+            "/Users/dev/tiny".to_string(),
+            "/Users/dev/gone".to_string(),
+            "/Users/dev/medium".to_string(),
+        ];
+        let (pinned, others) = UsageAggregator::split_pinned_projects(projects, &pinned);
+        let names = |projects: &[ProjectStats]| projects.iter().map(|p| p.project_name.clone()).collect::<Vec<_>>();
+        assert_eq!(names(&pinned), vec!["tiny", "medium"]);
+        assert_eq!(names(&others), vec!["big", "small"]);
+
+        let (pinned, others) = UsageAggregator::split_pinned_projects(others, &[]);
+        assert!(pinned.is_empty());
+        assert_eq!(names(&others), vec!["big", "small"]);
+    }
+
+    #[test]
+    fn test_session_sort_columns() {
+        let session = |id: &str, project: &str, start: &str, cost: f64, tokens: u64, requests: usize| {
+            let start_time = DateTime::parse_from_rfc3339(start).unwrap().with_timezone(&Utc);
+            SessionStats {
+                session_id: id.to_string(),
+                project_path: project.to_string(),
+                total_cost: cost,
+                total_tokens: tokens,
+                input_tokens: tokens,
+                output_tokens: 0,
+                cache_read_tokens: 0,
+                cache_creation_tokens: 0,
+                request_count: requests,
+                start_time,
+                end_time: start_time + Duration::minutes(30),
+            }
+        };
+        let sessions = vec![
+            session("a", "/Users/dev/beta", "2025-07-01T09:00:00Z", 3.0, 500, 4),
+            // Offset timestamps compare by instant: 23:30-05:00 is 04:30 UTC on 2 July
+            session("b", "/Users/dev/Alpha", "2025-07-01T23:30:00-05:00", 1.0, 900, 12),
+            session("c", "/Users/dev/gamma", "2025-07-02T02:00:00+02:00", 7.5, 100, 1),
+        ];
+        let aggregator = UsageAggregator::new();
+        let order = |column: SessionSortColumn, descending: bool| {
+            let mut sorted = sessions.clone();
+            aggregator.sort_sessions(&mut sorted, SessionSort { column, descending });
+            sorted.iter().map(|s| s.session_id.clone()).collect::<Vec<_>>().join("")
+        };
+
+        assert_eq!(order(SessionSortColumn::Project, false), "bac");
+        assert_eq!(order(SessionSortColumn::Project, true), "cab");
+        assert_eq!(order(SessionSortColumn::Date, true), "bca");
+        assert_eq!(order(SessionSortColumn::Date, false), "acb");
+        assert_eq!(order(SessionSortColumn::Cost, true), "cab");
+        assert_eq!(order(SessionSortColumn::Tokens, true), "bac");
+        assert_eq!(order(SessionSortColumn::Requests, false), "cab");
+
+        // Re-clicking flips direction; a new column starts from its natural direction
+        let sort = SessionSort::default().toggled(SessionSortColumn::Date);
+        assert!(!sort.descending);
+        assert_eq!(sort.toggled(SessionSortColumn::Project), SessionSort { column: SessionSortColumn::Project, descending: false });
+
+        let filtered = UsageAggregator::filter_sessions(sessions, " ALPHA ");
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].session_id, "b");
+    }
+
+    #[test]
+    fn test_calendar_cell_positions() {
+        let date = |d| NaiveDate::from_ymd_opt(2025, 7, d).unwrap();
+        // 2025-07-03 is a Thursday, so the first column is a partial week
+        let first = date(3);
+
+        assert_eq!(UsageAggregator::calendar_cell(first, first, Weekday::Mon), (0, 3));
+        assert_eq!(UsageAggregator::calendar_cell(date(6), first, Weekday::Mon), (0, 6));
+        assert_eq!(UsageAggregator::calendar_cell(date(7), first, Weekday::Mon), (1, 0));
+        assert_eq!(UsageAggregator::calendar_cell(date(16), first, Weekday::Mon), (2, 2));
+
+        // Sunday-first weeks shift the rows and move Sunday into the next column
+        assert_eq!(UsageAggregator::calendar_cell(first, first, Weekday::Sun), (0, 4));
+        assert_eq!(UsageAggregator::calendar_cell(date(6), first, Weekday::Sun), (1, 0));
+    }
+
+    #[test]
+    fn test_weekly_grouping_respects_week_start() {
+        // 2025-07-05 is a Saturday and 2025-07-06 is a Sunday
+        let saturday = UsageEntry {
+            timestamp: Utc.with_ymd_and_hms(2025, 7, 5, 12, 0, 0).unwrap(),
+            ..entry("/Users/dev/a", "s1", 1.0)
+        };
+        let sunday = UsageEntry {
+            timestamp: Utc.with_ymd_and_hms(2025, 7, 6, 12, 0, 0).unwrap(),
+            ..entry("/Users/dev/a", "s1", 2.0)
+        };
+        let entries = vec![saturday, sunday];
+
+        let monday_start = UsageAggregator::with_options(AggregationOptions {
+            week_start: Weekday::Mon,
+            timezone: Some(Tz::UTC),
+            ..AggregationOptions::default()
+        });
+        let daily = monday_start.calculate_daily_usage(&entries);
+        let weeks = monday_start.group_daily_usage_by_week(&daily);
+        assert_eq!(weeks.len(), 1);
+        assert_eq!(weeks[0].week_start, "2025-06-30");
+        assert_eq!(weeks[0].days_count, 2);
+
+        let sunday_start = UsageAggregator::with_options(AggregationOptions {
+            week_start: Weekday::Sun,
+            ..AggregationOptions::default()
+        });
+        let weeks = sunday_start.group_daily_usage_by_week(&daily);
+        assert_eq!(weeks.len(), 2);
+        assert_eq!(weeks[0].week_start, "2025-06-29");
+        assert_eq!(weeks[1].week_start, "2025-07-06");
+        assert!((weeks[1].total_cost - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_cost_by_weekday() {
+        // 2025-07-07 is a Monday; 03:00 UTC on Saturday the 12th is still Friday in New York
+        let on = |day: u32, hour: u32, cost: f64| UsageEntry {
+            timestamp: Utc.with_ymd_and_hms(2025, 7, day, hour, 0, 0).unwrap(),
+            ..entry("/Users/dev/a", "s1", cost)
+        };
+        let entries = vec![
+            on(7, 12, 1.0),
+            on(14, 12, 2.0),
+            on(9, 12, 4.0),
+            on(12, 3, 8.0),
+            on(13, 12, 16.0),
+        ];
+        let aggregator = |week_start: Weekday, timezone: Tz| {
+            UsageAggregator::with_options(AggregationOptions {
+                week_start,
+                timezone: Some(timezone),
+                ..AggregationOptions::default()
+            })
+        };
+
+        let utc = aggregator(Weekday::Mon, Tz::UTC).cost_by_weekday(&entries);
+        assert_eq!(utc, [3.0, 0.0, 4.0, 0.0, 0.0, 8.0, 16.0]);
+        let new_york = aggregator(Weekday::Mon, Tz::America__New_York).cost_by_weekday(&entries);
+        assert_eq!(new_york, [3.0, 0.0, 4.0, 0.0, 8.0, 0.0, 16.0]);
+
+        // Sunday-first weeks rotate the buckets so Sunday leads
+        let sunday_first = aggregator(Weekday::Sun, Tz::UTC).cost_by_weekday(&entries);
+        assert_eq!(sunday_first, [16.0, 3.0, 0.0, 4.0, 0.0, 0.0, 8.0]);
+        assert_eq!(UsageAggregator::weekdays_from(Weekday::Sun)[0], Weekday::Sun);
+        assert_eq!(UsageAggregator::weekdays_from(Weekday::Sun)[6], Weekday::Sat);
+
+        // Buckets reconcile with the range total
+        let stats = aggregator(Weekday::Sun, Tz::UTC).calculate_usage_stats(&entries);
+        assert!((sunday_first.iter().sum::<f64>() - stats.total_cost).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_quiet_hours_exclude_local_night() {
+        let at = |day: u32, hour: u32, cost: f64| UsageEntry {
+            timestamp: Utc.with_ymd_and_hms(2025, 7, day, hour, 0, 0).unwrap(),
+            ..entry("/Users/dev/a", "s1", cost)
+        };
+        let entries = vec![
+            at(1, 21, 1.0),  // 21:00 UTC
+            at(1, 23, 2.0),  // 23:00 UTC, inside the window
+            at(2, 3, 4.0),   // 03:00 UTC, after midnight but inside the window
+            at(2, 6, 8.0),   // 06:00 UTC, the end hour is not quiet
+            at(2, 12, 16.0), // Midday
+        ];
+        let aggregator = |timezone: Tz| {
+            UsageAggregator::with_options(AggregationOptions {
+                timezone: Some(timezone),
+                quiet_hours: Some(QuietHours::default()),
+                ..AggregationOptions::default()
+            })
+        };
+
+        let utc = aggregator(Tz::UTC).stats_for_range(&entries, TimeRange::AllTime);
+        assert!((utc.total_cost - 25.0).abs() < 1e-9);
+
+        // In New York (UTC-4) the same entries fall at 17:00, 19:00, 23:00, 02:00 and 08:00
+        let new_york = aggregator(Tz::America__New_York).stats_for_range(&entries, TimeRange::AllTime);
+        assert!((new_york.total_cost - 19.0).abs() < 1e-9);
+
+        // A window that does not wrap, and an empty one
+        let daytime = QuietHours { start_hour: 9, end_hour: 17 };
+        assert!(daytime.contains(9) && daytime.contains(16));
+        assert!(!daytime.contains(17) && !daytime.contains(3));
+        assert!(!(QuietHours { start_hour: 5, end_hour: 5 }).contains(5));
+        assert_eq!(QuietHours::default().shifted(3, -7), QuietHours { start_hour: 1, end_hour: 23 });
+
+        // Disabled by default
+        let all = UsageAggregator::new().stats_for_range(&entries, TimeRange::AllTime);
+        assert!((all.total_cost - 31.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_analysis_start_excludes_earlier_entries_from_all_time() {
+        let on = |month: u32, day: u32, cost: f64| UsageEntry {
+            timestamp: Utc.with_ymd_and_hms(2025, month, day, 12, 0, 0).unwrap(),
+            ..entry("/Users/dev/a", "s1", cost)
+        };
+        let entries = vec![on(5, 20, 100.0), on(6, 30, 50.0), on(7, 1, 2.0), on(7, 15, 3.0)];
+        let berlin = aggregator_in(Tz::Europe__Berlin);
+        let floor = berlin.start_of_day(NaiveDate::from_ymd_opt(2025, 7, 1).unwrap());
+        // Local midnight in Berlin (UTC+2 in summer)
+        assert_eq!(floor, Utc.with_ymd_and_hms(2025, 6, 30, 22, 0, 0).unwrap());
+
+        let floored = UsageAggregator::with_options(AggregationOptions {
+            timezone: Some(Tz::Europe__Berlin),
+            analysis_start: Some(floor),
+            ..AggregationOptions::default()
+        });
+        let stats = floored.stats_for_range(&entries, TimeRange::AllTime);
+        assert!((stats.total_cost - 5.0).abs() < 1e-9);
+        assert_eq!(stats.entries.len(), 2);
+        assert!(stats.daily_usage.keys().all(|day| day.as_str() >= "2025-07-01"));
+
+        let unfloored = berlin.stats_for_range(&entries, TimeRange::AllTime);
+        assert!((unfloored.total_cost - 155.0).abs() < 1e-9);
+    }
+
+    fn aggregator_in(timezone: Tz) -> UsageAggregator {
+        UsageAggregator::with_options(AggregationOptions {
+            timezone: Some(timezone),
+            ..AggregationOptions::default()
+        })
+    }
+
+    #[test]
+    fn test_daily_usage_buckets_by_local_day() {
+        // 03:30 UTC on July 2nd is still the evening of July 1st in New York (UTC-4)
+        let late_night = UsageEntry {
+            timestamp: Utc.with_ymd_and_hms(2025, 7, 2, 3, 30, 0).unwrap(),
+            ..entry("/Users/dev/a", "s1", 1.0)
+        };
+        let entries = vec![late_night];
+
+        let daily = aggregator_in(chrono_tz::America::New_York).calculate_daily_usage(&entries);
+        assert_eq!(daily.len(), 1);
+        assert_eq!(daily[0].date, "2025-07-01");
+
+        let daily = aggregator_in(Tz::UTC).calculate_daily_usage(&entries);
+        assert_eq!(daily[0].date, "2025-07-02");
+
+        // 22:00 UTC on July 1st is already July 2nd in Tokyo (UTC+9)
+        let evening = UsageEntry {
+            timestamp: Utc.with_ymd_and_hms(2025, 7, 1, 22, 0, 0).unwrap(),
+            ..entry("/Users/dev/a", "s1", 1.0)
+        };
+        let daily = aggregator_in(chrono_tz::Asia::Tokyo).calculate_daily_usage(&[evening]);
+        assert_eq!(daily[0].date, "2025-07-02");
+    }
+
+    #[test]
+    fn test_today_and_yesterday_use_local_day() {
+        let aggregator = aggregator_in(chrono_tz::America::New_York);
+        // 01:00 UTC on July 3rd is 21:00 on July 2nd in New York
+        let now = Utc.with_ymd_and_hms(2025, 7, 3, 1, 0, 0).unwrap();
+        let late_night = UsageEntry {
+            timestamp: Utc.with_ymd_and_hms(2025, 7, 2, 3, 30, 0).unwrap(),
+            ..entry("/Users/dev/a", "s1", 1.0)
+        };
+        let this_evening = UsageEntry {
+            timestamp: Utc.with_ymd_and_hms(2025, 7, 2, 23, 0, 0).unwrap(),
+            ..entry("/Users/dev/a", "s1", 2.0)
+        };
+        let entries = vec![late_night, this_evening];
+
+        let today = aggregator.filter_by_time_range_at(&entries, TimeRange::Today, now);
+        assert_eq!(today.len(), 1);
+        assert!((today[0].cost - 2.0).abs() < 1e-9);
+
+        let yesterday = aggregator.filter_by_time_range_at(&entries, TimeRange::Yesterday, now);
+        assert_eq!(yesterday.len(), 1);
+        assert!((yesterday[0].cost - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_bad_costs_keep_totals_finite() {
+        let aggregator = aggregator_in(Tz::UTC);
+        let entries = vec![
+            entry("/Users/dev/a", "s1", f64::NAN),
+            entry("/Users/dev/a", "s1", -3.0),
+            entry("/Users/dev/b", "s2", 2.0),
+            entry("/Users/dev/b", "s2", f64::INFINITY),
+        ];
+
+        let stats = aggregator.calculate_usage_stats(&entries);
+        assert!((stats.total_cost - 2.0).abs() < 1e-9);
+        assert!(stats.model_stats.values().all(|m| m.total_cost.is_finite()));
+        assert!((stats.project_stats["/Users/dev/a"].total_cost).abs() < 1e-9);
+        assert!((stats.project_stats["/Users/dev/b"].total_cost - 2.0).abs() < 1e-9);
+        assert!(stats.daily_usage.values().all(|d| d.total_cost.is_finite() && d.total_cost >= 0.0));
+        assert!((stats.most_expensive_request.unwrap().cost - 2.0).abs() < 1e-9);
+        assert_eq!(aggregator.busiest_project(&entries).unwrap().project_path, "/Users/dev/b");
+    }
+
+    #[test]
+    fn test_last_24_hours_is_rolling() {
+        let aggregator = aggregator_in(Tz::UTC);
+        let now = Utc.with_ymd_and_hms(2025, 7, 2, 9, 0, 0).unwrap();
+        let entries = vec![
+            UsageEntry {
+                timestamp: now - Duration::hours(23),
+                ..entry("/Users/dev/a", "s1", 1.0)
+            },
+            UsageEntry {
+                timestamp: now - Duration::hours(25),
+                ..entry("/Users/dev/a", "s1", 2.0)
+            },
+        ];
+
+        let recent = aggregator.filter_by_time_range_at(&entries, TimeRange::Last24Hours, now);
+        assert_eq!(recent.len(), 1);
+        assert!((recent[0].cost - 1.0).abs() < 1e-9);
+
+        // 23 hours ago was yesterday on the calendar, so Today leaves it out
+        assert!(aggregator.filter_by_time_range_at(&entries, TimeRange::Today, now).is_empty());
+    }
+
+    #[test]
+    fn test_trailing_slash_project_paths_merge() {
+        let aggregator = UsageAggregator::new();
+        let entries = vec![
+            entry("/Users/dev/proj", "s1", 1.0),
+            entry("/Users/dev/proj/", "s1", 2.0),
+            entry("/Users//dev/proj//", "s2", 4.0),
+        ];
+
+        let projects = aggregator.calculate_project_stats(&entries);
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].project_path, "/Users/dev/proj");
+        assert_eq!(projects[0].project_name, "proj");
+        assert_eq!(projects[0].request_count, 3);
+        assert_eq!(projects[0].session_count, 2);
+        assert!((projects[0].total_cost - 7.0).abs() < 1e-9);
+
+        let sessions = aggregator.calculate_session_stats(&entries);
+        assert_eq!(sessions.len(), 2);
+        assert!(sessions.iter().all(|s| s.project_path == "/Users/dev/proj"));
+
+        assert_eq!(normalize_project_path("/"), "/");
+        assert_eq!(normalize_project_path("//a///b/"), "/a/b");
+    }
+
+    #[test]
+    fn test_relative_cwd_is_marked_apart_from_absolute_paths() {
+        let aggregator = UsageAggregator::new();
+        let entries = vec![
+            entry("/Users/alice/code/myapp", "s1", 1.0),
+            entry("code/myapp", "s2", 2.0),
+            entry("./code/myapp/", "s3", 4.0),
+        ];
+
+        let projects = aggregator.calculate_project_stats(&entries);
+        assert_eq!(projects.len(), 3);
+        let name_of = |path: &str| projects.iter().find(|p| p.project_path == path).unwrap().project_name.clone();
+        assert_eq!(name_of("/Users/alice/code/myapp"), "myapp");
+        assert_eq!(name_of("code/myapp"), "(relative) code/myapp");
+        assert_eq!(name_of("./code/myapp"), "(relative) code/myapp");
+
+        assert_eq!(aggregator.extract_project_name("."), "(relative) .");
+        assert!(!is_relative_project_path("C:\\Users\\dev\\myapp"));
+        assert!(!is_relative_project_path("~/code/myapp"));
+        assert!(!is_relative_project_path(UNKNOWN_PROJECT));
+    }
+
+    #[test]
+    fn test_split_minor_contributors() {
+        let costs = vec![10.0, 5.0, 0.4, 0.3, 0.2];
+        let split = |threshold| UsageAggregator::split_minor_contributors(costs.clone(), |c| *c, threshold);
+
+        let (major, minor) = split(CostThreshold::Absolute(1.0));
+        assert_eq!(major, vec![10.0, 5.0]);
+        assert_eq!(minor, vec![0.4, 0.3, 0.2]);
+        assert!((minor.iter().sum::<f64>() - 0.9).abs() < 1e-9);
+
+        // 3% of the 15.9 total is 0.477
+        let (major, minor) = split(CostThreshold::PercentOfTotal(3.0));
+        assert_eq!((major.len(), minor.len()), (2, 3));
+        // 20% is 3.18, which leaves 5.0 above and groups the rest
+        let (major, minor) = split(CostThreshold::PercentOfTotal(20.0));
+        assert_eq!((major, minor.len()), (vec![10.0, 5.0], 3));
+
+        // Disabled by default, and a single small item is never grouped alone
+        assert_eq!(split(CostThreshold::Absolute(0.0)).1.len(), 0);
+        let (major, minor) = split(CostThreshold::Absolute(0.25));
+        assert_eq!((major.len(), minor.len()), (5, 0));
+    }
+
+    #[test]
+    fn test_cache_efficiency_ratio() {
+        let aggregator = UsageAggregator::new();
+        let cached = |read: u64, creation: u64| UsageEntry {
+            cache_read_tokens: read,
+            cache_creation_tokens: creation,
+            ..entry("/Users/dev/app", "s1", 0.10)
+        };
+
+        // Sonnet 4: $0.30/M to read, $3.75/M to write
+        let reuse = aggregator.cache_efficiency(&[cached(6_000_000, 500_000), cached(4_000_000, 500_000)]);
+        assert_eq!((reuse.read_tokens, reuse.creation_tokens), (10_000_000, 1_000_000));
+        assert!((reuse.reads_per_creation().unwrap() - 10.0).abs() < 1e-9);
+        assert!((reuse.read_cost - 3.0).abs() < 1e-9);
+        assert!((reuse.creation_cost - 3.75).abs() < 1e-9);
+        assert_eq!(reuse.label(), "Good reuse");
+
+        let wasted = aggregator.cache_efficiency(&[cached(100, 1_000)]);
+        assert!((wasted.reads_per_creation().unwrap() - 0.1).abs() < 1e-9);
+        assert_eq!(wasted.label(), "Low reuse");
+
+        // No creation tokens never divides by zero
+        let reads_only = aggregator.cache_efficiency(&[cached(500, 0)]);
+        assert_eq!(reads_only.reads_per_creation(), None);
+        assert_eq!(reads_only.label(), "Reads only");
+        assert_eq!(aggregator.cache_efficiency(&[]).label(), "No caching");
+    }
+
+    #[test]
+    fn test_busiest_project_breaks_ties_by_name() {
+        let aggregator = UsageAggregator::new();
+        assert!(aggregator.busiest_project(&[]).is_none());
+
+        let entries = vec![
+            entry("/Users/dev/zeta", "s1", 2.0),
+            entry("/Users/dev/alpha", "s2", 1.0),
+            entry("/Users/dev/alpha", "s2", 1.0),
+            entry("/Users/dev/small", "s3", 0.5),
+        ];
+        let busiest = aggregator.busiest_project(&entries).unwrap();
+        assert_eq!(busiest.project_name, "alpha");
+        assert_eq!(busiest.request_count, 2);
+    }
+
+    #[test]
+    fn test_month_to_date_cost() {
+        let aggregator = aggregator_in(Tz::UTC);
+        let now = Utc.with_ymd_and_hms(2025, 7, 15, 12, 0, 0).unwrap();
+        let on = |month, day, cost| UsageEntry {
+            timestamp: Utc.with_ymd_and_hms(2025, month, day, 9, 0, 0).unwrap(),
+            ..entry("/Users/dev/app", "s1", cost)
+        };
+        let entries = vec![on(6, 30, 50.0), on(7, 1, 2.0), on(7, 15, 3.0), on(7, 20, 7.0)];
+
+        assert!((aggregator.month_to_date_cost_at(&entries, now) - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_annualized_run_rate() {
+        let aggregator = aggregator_in(Tz::UTC);
+        let now = Utc.with_ymd_and_hms(2025, 7, 30, 12, 0, 0).unwrap();
+        let on = |month, day, cost| UsageEntry {
+            timestamp: Utc.with_ymd_and_hms(2025, month, day, 9, 0, 0).unwrap(),
+            ..entry("/Users/dev/app", "s1", cost)
+        };
+
+        // $30 across the last 30 days (1 July to 30 July) is $1/day; June falls outside
+        let entries = vec![on(6, 1, 500.0), on(7, 1, 10.0), on(7, 15, 5.0), on(7, 30, 15.0)];
+        let rate = aggregator.annualized_run_rate_at(&entries, 30, now).unwrap();
+        assert_eq!(rate.days_used, 30);
+        assert!((rate.daily_average - 1.0).abs() < 1e-9);
+        assert!((rate.annual_cost - 365.0).abs() < 1e-9);
+
+        // History only reaches back 10 days (21 to 30 July): average over those
+        let recent = vec![on(7, 21, 4.0), on(7, 25, 16.0)];
+        let rate = aggregator.annualized_run_rate_at(&recent, 30, now).unwrap();
+        assert_eq!(rate.days_used, 10);
+        assert!((rate.daily_average - 2.0).abs() < 1e-9);
+        assert!((rate.annual_cost - 730.0).abs() < 1e-9);
+
+        // Entries after `now` are ignored, and nothing to go on gives no estimate
+        assert_eq!(aggregator.annualized_run_rate_at(&[on(7, 31, 9.0)], 30, now), None);
+        assert_eq!(aggregator.annualized_run_rate_at(&[], 30, now), None);
+        assert_eq!(aggregator.annualized_run_rate_at(&[on(6, 1, 500.0)], 30, now), None);
+        assert_eq!(aggregator.annualized_run_rate_at(&entries, 0, now), None);
+    }
+
+    #[test]
+    fn test_expensive_requests_over_alert() {
+        let aggregator = UsageAggregator::with_options(AggregationOptions {
+            request_cost_alert: Some(1.0),
+            ..AggregationOptions::default()
+        });
+        let pricey = UsageEntry {
+            model: "claude-opus-4-20250514".to_string(),
+            input_tokens: 90_000,
+            ..entry("/Users/dev/app", "s2", 4.5)
+        };
+        let entries = vec![
+            entry("/Users/dev/app", "s1", 0.02),
+            entry("/Users/dev/app", "s1", 0.05),
+            pricey.clone(),
+            entry("/Users/dev/app", "s1", 1.0),
+        ];
+
+        let stats = aggregator.calculate_usage_stats(&entries);
+        assert_eq!(stats.requests_over_cost_alert, 1);
+        let most_expensive = stats.most_expensive_request.unwrap();
+        assert_eq!(most_expensive.model, pricey.model);
+        assert_eq!(most_expensive.input_tokens, 90_000);
+        assert_eq!(most_expensive.session_id.as_deref(), Some("s2"));
+
+        // Without a threshold nothing is counted but the costliest request is still found
+        let stats = UsageAggregator::new().calculate_usage_stats(&entries);
+        assert_eq!(stats.requests_over_cost_alert, 0);
+        assert!((stats.most_expensive_request.unwrap().cost - 4.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_excluded_projects_and_models_drop_from_totals() {
+        let now = Utc.with_ymd_and_hms(2025, 7, 2, 12, 0, 0).unwrap();
+        let opus = UsageEntry {
+            model: "claude-opus-4-20250514".to_string(),
+            ..entry("/Users/dev/app", "s2", 4.0)
+        };
+        let entries = vec![
+            entry("/Users/dev/app", "s1", 1.0),
+            entry("/Users/dev/scratch", "s3", 2.0),
+            opus,
+        ];
+
+        let everything = UsageAggregator::new().stats_for_range_at(&entries, TimeRange::AllTime, now);
+        assert!((everything.total_cost - 7.0).abs() < 1e-9);
+
+        let aggregator = UsageAggregator::with_options(AggregationOptions {
+            excluded_projects: vec!["/Users/dev/scratch".to_string()],
+            // Family name matches the dated model id
+            excluded_models: vec!["claude-opus-4".to_string()],
+            ..AggregationOptions::default()
+        });
+        let stats = aggregator.stats_for_range_at(&entries, TimeRange::AllTime, now);
+        assert!((stats.total_cost - 1.0).abs() < 1e-9);
+        assert_eq!(stats.entries.len(), 1);
+        assert!(!stats.project_stats.contains_key("/Users/dev/scratch"));
+        assert!(!stats.model_stats.contains_key("claude-opus-4-20250514"));
+        assert!(!stats.session_stats.values().any(|s| s.session_id == "s2"));
+        assert!((aggregator.month_to_date_cost_at(&entries, now) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_model_efficiency_ranking() {
+        let aggregator = UsageAggregator::new();
+        let model = |name: &str, tokens: u64, cost: f64| ModelStats {
+            model: name.to_string(),
+            display_name: name.to_string(),
+            total_cost: cost,
+            total_tokens: tokens,
+            input_tokens: tokens,
+            output_tokens: 0,
+            cache_read_tokens: 0,
+            cache_creation_tokens: 0,
+            request_count: 1,
+        };
+        let models = vec![
+            model("opus", 1_000, 1.0),
+            model("unpriced", 50_000, 0.0),
+            model("sonnet", 10_000, 2.0),
+        ];
+
+        let ranking = aggregator.model_efficiency(&models);
+        let names: Vec<&str> = ranking.iter().map(|m| m.model.as_str()).collect();
+        assert_eq!(names, ["sonnet", "opus"]);
+        assert!((ranking[0].tokens_per_dollar - 5_000.0).abs() < 1e-9);
+        assert!((ranking[1].tokens_per_dollar - 1_000.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_cost_per_thousand_tokens() {
+        let model = |tokens: u64, cost: f64| ModelStats {
+            model: "sonnet".to_string(),
+            display_name: "Sonnet".to_string(),
+            total_cost: cost,
+            total_tokens: tokens,
+            input_tokens: tokens,
+            output_tokens: 0,
+            cache_read_tokens: 0,
+            cache_creation_tokens: 0,
+            request_count: 1,
+        };
+
+        let rate = model(250_000, 1.5).cost_per_thousand_tokens().unwrap();
+        assert!((rate - 0.006).abs() < 1e-12);
+        assert_eq!(model(2_000, 0.0).cost_per_thousand_tokens(), Some(0.0));
+        // No tokens, no rate, even if a cost was logged
+        assert_eq!(model(0, 0.3).cost_per_thousand_tokens(), None);
+        assert_eq!(model(0, 0.0).cost_per_thousand_tokens(), None);
+    }
+
+    #[test]
+    fn test_model_stats_grouped_by_family() {
+        let versioned = |model: &str, cost| UsageEntry {
+            model: model.to_string(),
+            ..entry("/Users/dev/app", "s1", cost)
+        };
+        let entries = vec![
+            versioned("claude-3-5-sonnet-20240620", 1.0),
+            versioned("claude-3-5-sonnet-20241022", 2.0),
+            versioned("claude-3-opus-20240229", 4.0),
+        ];
+
+        let per_version = UsageAggregator::new().calculate_model_stats(&entries);
+        assert_eq!(per_version.len(), 3);
+
+        let grouped = UsageAggregator::with_options(AggregationOptions {
+            group_models_by_family: true,
+            ..AggregationOptions::default()
+        })
+        .calculate_model_stats(&entries);
+        assert_eq!(grouped.len(), 2);
+        let sonnet = grouped.iter().find(|m| m.model == "claude-3-5-sonnet").unwrap();
+        assert_eq!(sonnet.display_name, "Claude 3.5 Sonnet");
+        assert_eq!(sonnet.request_count, 2);
+        assert!((sonnet.total_cost - 3.0).abs() < 1e-9);
+        assert_eq!(sonnet.input_tokens, 200);
+    }
+
+    #[test]
+    fn test_session_tracks_start_and_end() {
+        let aggregator = UsageAggregator::new();
+        let at = |hour, minute| UsageEntry {
+            timestamp: Utc.with_ymd_and_hms(2025, 7, 1, hour, minute, 0).unwrap(),
+            ..entry("/Users/dev/app", "s1", 1.0)
+        };
+        // Out of order on purpose: start/end must not depend on file order
+        let entries = vec![at(14, 30), at(9, 15), at(23, 45), at(11, 0)];
+
+        let sessions = aggregator.calculate_session_stats(&entries);
+        assert_eq!(sessions.len(), 1);
+        let session = &sessions[0];
+        assert_eq!(session.start_time, Utc.with_ymd_and_hms(2025, 7, 1, 9, 15, 0).unwrap());
+        assert_eq!(session.end_time, Utc.with_ymd_and_hms(2025, 7, 1, 23, 45, 0).unwrap());
+        assert_eq!(session.duration(), chrono::Duration::minutes(14 * 60 + 30));
+        assert_eq!(session.request_count, 4);
+    }
+
+    #[test]
+    fn test_breakdowns_follow_pricing_history_across_a_price_change() {
+        use crate::analytics::calculator::{HistoricalPricing, ModelPricing};
+
+        let date = |month| NaiveDate::from_ymd_opt(2025, month, 1).unwrap();
+        // Sonnet 4 at double today's rates until June
+        let pricing_history = vec![HistoricalPricing {
+            model_pattern: "sonnet-4".to_string(),
+            from: date(1),
+            until: date(6),
+            pricing: ModelPricing { input_price: 6.0, output_price: 30.0, cache_read_price: 0.60, cache_write_price: 7.50 },
+        }];
+        let calculator = CostCalculator::new().with_pricing_history(pricing_history.clone());
+        let may = UsageEntry {
+            timestamp: Utc.with_ymd_and_hms(2025, 5, 15, 12, 0, 0).unwrap(),
+            ..cached_entry("/Users/dev/app", "s1")
+        };
+        let entries = vec![may, cached_entry("/Users/dev/app", "s2")];
+        let priced = |e: &UsageEntry| {
+            calculator.calculate_cost_at(&e.model, &e.timestamp, e.input_tokens, e.output_tokens, e.cache_read_tokens, e.cache_creation_tokens)
+        };
+        let cache_priced = |e: &UsageEntry| calculator.calculate_cost_at(&e.model, &e.timestamp, 0, 0, e.cache_read_tokens, e.cache_creation_tokens);
+
+        let options = AggregationOptions { pricing_history, ..AggregationOptions::default() };
+        let aggregator = UsageAggregator::with_options(options.clone());
+
+        // Per-category split adds up to the loaded (historical) costs
+        let breakdown = aggregator.model_cost_breakdown(&entries, "claude-sonnet-4-20250514");
+        let loaded: f64 = entries.iter().map(priced).sum();
+        assert!((breakdown.total() - loaded).abs() < 1e-12);
+        assert!((breakdown.cache_write_cost - 200.0 * (7.50 + 3.75) / 1_000_000.0).abs() < 1e-12);
+
+        let cache = aggregator.cache_efficiency(&entries);
+        assert!((cache.creation_cost + cache.read_cost - entries.iter().map(cache_priced).sum::<f64>()).abs() < 1e-12);
+
+        // Ignoring cache takes off what the cache cost at the time, not at today's rates
+        let ignoring = UsageAggregator::with_options(AggregationOptions { ignore_cache: true, ..options });
+        let historical = UsageEntry { cost: priced(&entries[0]), ..entries[0].clone() };
+        let counted = ignoring.counted_entries(vec![historical]);
+        assert!((counted[0].cost - calculator.calculate_cost_at(&counted[0].model, &counted[0].timestamp, 100, 50, 0, 0)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_session_model_breakdown() {
+        let aggregator = UsageAggregator::new();
+        let opus = |session, cost| UsageEntry {
+            model: "claude-opus-4-20250514".to_string(),
+            ..entry("/Users/dev/app", session, cost)
+        };
+        let entries = vec![
+            entry("/Users/dev/app", "s1", 0.5),
+            opus("s1", 2.0),
+            opus("s1", 1.0),
+            opus("s2", 9.0),
+            entry("/Users/dev/app", "s2", 4.0),
+        ];
+
+        let breakdown = aggregator.session_model_breakdown(&entries, "/Users/dev/app", "s1");
+        let split: Vec<(&str, f64, usize, u64)> = breakdown
+            .iter()
+            .map(|m| (m.model.as_str(), m.total_cost, m.request_count, m.total_tokens))
+            .collect();
+        assert_eq!(
+            split,
+            vec![
+                ("claude-opus-4-20250514", 3.0, 2, 300),
+                ("claude-sonnet-4-20250514", 0.5, 1, 150),
+            ]
+        );
+
+        let single = aggregator.session_model_breakdown(&[entry("/Users/dev/app", "s3", 1.0)], "/Users/dev/app", "s3");
+        assert_eq!(single.len(), 1);
+        assert!(aggregator.session_model_breakdown(&entries, "/Users/dev/app", "missing").is_empty());
+    }
+
+    #[test]
+    fn test_session_drill_down_stays_within_its_project() {
+        let aggregator = UsageAggregator::new();
+        let entries = vec![
+            entry("/Users/dev/api", "shared", 1.0),
+            entry("/Users/dev/web", "shared", 4.0),
+            entry("/Users/dev/api", "shared", 2.0),
+        ];
+
+        let api = aggregator.session_entries(&entries, "/Users/dev/api", "shared");
+        assert_eq!(api.len(), 2);
+        assert!(api.iter().all(|e| e.project_key() == "/Users/dev/api"));
+        let api_models = aggregator.session_model_breakdown(&entries, "/Users/dev/api", "shared");
+        assert_eq!((api_models[0].request_count, api_models[0].total_cost), (2, 3.0));
+
+        let web = aggregator.session_model_breakdown(&entries, "/Users/dev/web", "shared");
+        assert_eq!(web.len(), 1);
+        assert_eq!((web[0].request_count, web[0].total_cost), (1, 4.0));
+    }
+
+    #[test]
+    fn test_stats_for_range_pipeline() {
+        let aggregator = aggregator_in(Tz::UTC);
+        let now = Utc.with_ymd_and_hms(2025, 7, 31, 12, 0, 0).unwrap();
+        let entries = vec![
+            UsageEntry {
+                timestamp: now - Duration::days(2),
+                ..entry("/Users/dev/a", "s1", 1.0)
+            },
+            UsageEntry {
+                timestamp: now - Duration::days(20),
+                ..entry("/Users/dev/b", "s2", 2.0)
+            },
+            UsageEntry {
+                timestamp: now - Duration::days(90),
+                ..entry("/Users/dev/c", "s3", 4.0)
+            },
+        ];
+
+        let all = aggregator.stats_for_range_at(&entries, TimeRange::AllTime, now);
+        assert!((all.total_cost - 7.0).abs() < 1e-9);
+        assert_eq!(all.project_stats.len(), 3);
+
+        let month = aggregator.stats_for_range_at(&entries, TimeRange::Last30Days, now);
+        assert!((month.total_cost - 3.0).abs() < 1e-9);
+        assert_eq!(month.session_count, 2);
+
+        let week = aggregator.stats_for_range_at(&entries, TimeRange::Last7Days, now);
+        assert!((week.total_cost - 1.0).abs() < 1e-9);
+        assert_eq!(week.daily_usage.len(), 1);
+    }
+
+    #[test]
+    fn test_token_counts_above_u32_are_preserved() {
+        let huge = u32::MAX as u64 + 1_000;
+        let entries = vec![
+            UsageEntry {
+                input_tokens: huge,
+                ..entry("/Users/dev/a", "s1", 1.0)
+            },
+            entry("/Users/dev/a", "s1", 1.0),
+        ];
+
+        let stats = UsageAggregator::new().calculate_usage_stats(&entries);
+        assert_eq!(stats.total_input_tokens, huge + 100);
+        assert_eq!(stats.total_tokens, huge + 100 + 2 * 50);
+        assert_eq!(stats.project_stats["/Users/dev/a"].input_tokens, huge + 100);
+    }
+
+    #[test]
+    fn test_daily_models_used_has_no_duplicates() {
+        let opus = UsageEntry {
+            model: "claude-opus-4-20250514".to_string(),
+            ..entry("/Users/dev/a", "s1", 1.0)
+        };
+        let entries = vec![
+            entry("/Users/dev/a", "s1", 1.0),
+            opus.clone(),
+            entry("/Users/dev/b", "s2", 1.0),
+            opus,
+        ];
+
+        let daily = aggregator_in(Tz::UTC).calculate_daily_usage(&entries);
+        assert_eq!(daily.len(), 1);
+        let mut models = daily[0].models_used.clone();
+        models.sort();
+        models.dedup();
+        assert_eq!(models.len(), daily[0].models_used.len());
+        assert_eq!(models.len(), 2);
+    }
+
+    fn daily(date: &str, total_cost: f64) -> DailyUsage {
+        DailyUsage {
+            date: date.to_string(),
+            total_cost,
+            total_tokens: 0,
+            input_tokens: 0,
+            output_tokens: 0,
+            cache_read_tokens: 0,
+            cache_creation_tokens: 0,
+            request_count: 1,
+            models_used: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_anomalous_days_flags_clear_outlier() {
+        let aggregator = UsageAggregator::new();
+        let mut days: Vec<DailyUsage> = (1..=9)
+            .map(|day| daily(&format!("2025-07-{:02}", day), 1.0 + day as f64 * 0.1))
+            .collect();
+        days.push(daily("2025-07-10", 25.0));
+
+        assert_eq!(aggregator.anomalous_days(&days), vec!["2025-07-10".to_string()]);
+
+        // Too few active days to judge
+        let few = vec![daily("2025-07-01", 1.0), daily("2025-07-02", 1.0), daily("2025-07-03", 50.0)];
+        assert!(aggregator.anomalous_days(&few).is_empty());
+    }
+
+    #[test]
+    fn test_rolling_average_ramps_up_then_slides() {
+        let days: Vec<DailyUsage> = [1.0, 3.0, 5.0, 7.0, 9.0]
+            .iter()
+            .enumerate()
+            .map(|(i, cost)| daily(&format!("2025-07-{:02}", i + 1), *cost))
+            .collect();
+
+        let averages = UsageAggregator::rolling_average(&days, 3);
+        // First two values only average the days available so far
+        let expected = [1.0, 2.0, 3.0, 5.0, 7.0];
+        assert_eq!(averages.len(), expected.len());
+        for (actual, expected) in averages.iter().zip(expected) {
+            assert!((actual - expected).abs() < 1e-9, "{} != {}", actual, expected);
+        }
+
+        // A window longer than the data never stops ramping up
+        let long = UsageAggregator::rolling_average(&days, 7);
+        assert!((long[4] - 5.0).abs() < 1e-9);
+        assert!(UsageAggregator::rolling_average(&[], 7).is_empty());
+    }
+
+    #[test]
+    fn test_usage_frequency_counts_gaps() {
+        let active = |date: &str| daily(date, 1.0);
+        let unused = |date: &str| DailyUsage { request_count: 0, ..daily(date, 0.0) };
+        let days = vec![
+            active("2025-07-10"),
+            active("2025-07-01"),
+            active("2025-07-02"),
+            // Present without requests: one before the active span, one inside it
+            unused("2025-06-28"),
+            unused("2025-07-05"),
+            active("2025-07-06"),
+        ];
+
+        let frequency = UsageAggregator::usage_frequency(&days).unwrap();
+        assert_eq!(frequency, UsageFrequency { span_days: 10, active_days: 4, idle_days: 6 });
+        assert!((frequency.frequency() - 0.4).abs() < 1e-9);
+
+        let single = UsageAggregator::usage_frequency(&[active("2025-07-01")]).unwrap();
+        assert_eq!((single.idle_days, single.frequency()), (0, 1.0));
+        assert_eq!(UsageAggregator::usage_frequency(&[unused("2025-07-01")]), None);
+    }
+
+    #[test]
+    fn test_fill_missing_days_inserts_zero_days() {
+        let days = vec![daily("2025-07-04", 4.0), daily("2025-07-01", 1.0)];
+        let filled = UsageAggregator::fill_missing_days(&days);
+
+        let dates: Vec<&str> = filled.iter().map(|d| d.date.as_str()).collect();
+        assert_eq!(dates, ["2025-07-01", "2025-07-02", "2025-07-03", "2025-07-04"]);
+        assert_eq!(filled[1].total_cost, 0.0);
+        assert_eq!(filled[3].total_cost, 4.0);
+    }
+}
@@ -1,32 +1,132 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::ops::Add;
+
 /// Cost calculator for AI models with accurate pricing
-pub struct CostCalculator;
+pub struct CostCalculator {
+    /// Bill cache-creation tokens at the input rate for models that have no cache write price
+    cache_writes_as_input: bool,
+    /// Superseded rates, used instead of the current ones for entries dated inside their range
+    pricing_history: Vec<HistoricalPricing>,
+}
 
 impl CostCalculator {
     pub fn new() -> Self {
-        Self
+        Self::with_cache_write_fallback(false)
+    }
+
+    /// Calculator that optionally bills unpriced cache writes as regular input
+    pub fn with_cache_write_fallback(cache_writes_as_input: bool) -> Self {
+        Self {
+            cache_writes_as_input,
+            pricing_history: Vec::new(),
+        }
+    }
+
+    /// Calculator that costs entries at the rates in effect on their date
+    pub fn with_pricing_history(mut self, pricing_history: Vec<HistoricalPricing>) -> Self {
+        self.pricing_history = pricing_history;
+        self
     }
 
-    /// Calculate cost for a usage entry
+    /// Calculate cost for a usage entry at current rates
+    #[allow(dead_code)] // Loading goes through `calculate_cost_at`
     pub fn calculate_cost(
         &self,
         model: &str,
-        input_tokens: u32,
-        output_tokens: u32,
-        cache_read_tokens: u32,
-        cache_creation_tokens: u32,
+        input_tokens: u64,
+        output_tokens: u64,
+        cache_read_tokens: u64,
+        cache_creation_tokens: u64,
+    ) -> f64 {
+        self.calculate_cost_breakdown(model, input_tokens, output_tokens, cache_read_tokens, cache_creation_tokens)
+            .total()
+    }
+
+    /// Calculate cost for a usage entry at the rates in effect when it was made
+    pub fn calculate_cost_at(
+        &self,
+        model: &str,
+        timestamp: &DateTime<Utc>,
+        input_tokens: u64,
+        output_tokens: u64,
+        cache_read_tokens: u64,
+        cache_creation_tokens: u64,
     ) -> f64 {
-        let pricing = self.get_model_pricing(model);
-        
-        let input_cost = (input_tokens as f64 / 1_000_000.0) * pricing.input_price;
-        let output_cost = (output_tokens as f64 / 1_000_000.0) * pricing.output_price;
-        let cache_read_cost = (cache_read_tokens as f64 / 1_000_000.0) * pricing.cache_read_price;
-        let cache_write_cost = (cache_creation_tokens as f64 / 1_000_000.0) * pricing.cache_write_price;
-        
-        input_cost + output_cost + cache_read_cost + cache_write_cost
+        self.calculate_cost_breakdown_at(model, timestamp, input_tokens, output_tokens, cache_read_tokens, cache_creation_tokens)
+            .total()
+    }
+
+    /// Calculate the cost contributed by each token category
+    pub fn calculate_cost_breakdown(
+        &self,
+        model: &str,
+        input_tokens: u64,
+        output_tokens: u64,
+        cache_read_tokens: u64,
+        cache_creation_tokens: u64,
+    ) -> CostBreakdown {
+        Self::breakdown(self.billed_pricing(model), input_tokens, output_tokens, cache_read_tokens, cache_creation_tokens)
+    }
+
+    /// Calculate the cost contributed by each token category at the rates in effect when it was made
+    pub fn calculate_cost_breakdown_at(
+        &self,
+        model: &str,
+        timestamp: &DateTime<Utc>,
+        input_tokens: u64,
+        output_tokens: u64,
+        cache_read_tokens: u64,
+        cache_creation_tokens: u64,
+    ) -> CostBreakdown {
+        let pricing = self.apply_cache_write_fallback(self.get_model_pricing_at(model, timestamp.date_naive()));
+        Self::breakdown(pricing, input_tokens, output_tokens, cache_read_tokens, cache_creation_tokens)
+    }
+
+    fn breakdown(
+        pricing: ModelPricing,
+        input_tokens: u64,
+        output_tokens: u64,
+        cache_read_tokens: u64,
+        cache_creation_tokens: u64,
+    ) -> CostBreakdown {
+        CostBreakdown {
+            input_cost: (input_tokens as f64 / 1_000_000.0) * pricing.input_price,
+            output_cost: (output_tokens as f64 / 1_000_000.0) * pricing.output_price,
+            cache_read_cost: (cache_read_tokens as f64 / 1_000_000.0) * pricing.cache_read_price,
+            cache_write_cost: (cache_creation_tokens as f64 / 1_000_000.0) * pricing.cache_write_price,
+        }
+    }
+
+    /// Pricing actually charged for a model, after the cache-write fallback is applied
+    pub fn billed_pricing(&self, model: &str) -> ModelPricing {
+        self.apply_cache_write_fallback(self.get_model_pricing(model))
+    }
+
+    fn apply_cache_write_fallback(&self, pricing: ModelPricing) -> ModelPricing {
+        if self.cache_writes_as_input && pricing.cache_write_price == 0.0 && pricing.input_price > 0.0 {
+            ModelPricing {
+                cache_write_price: pricing.input_price,
+                ..pricing
+            }
+        } else {
+            pricing
+        }
+    }
+
+    /// Pricing for a model on a given (UTC) date: the first history entry covering the date,
+    /// otherwise the current rates
+    pub fn get_model_pricing_at(&self, model: &str, date: NaiveDate) -> ModelPricing {
+        self.pricing_history
+            .iter()
+            .find(|period| period.applies_to(model, date))
+            .map(|period| period.pricing)
+            .unwrap_or_else(|| self.get_model_pricing(model))
     }
 
     /// Get pricing information for a model
-    fn get_model_pricing(&self, model: &str) -> ModelPricing {
+    pub fn get_model_pricing(&self, model: &str) -> ModelPricing {
         // Model pricing (per million tokens) - matching reference implementation exactly
         if model.contains("opus-4") || model.contains("claude-opus-4") {
             ModelPricing {
@@ -54,6 +154,18 @@ impl CostCalculator {
         }
     }
 
+    /// Pair each model id with whether it has pricing; unrecognized ids come first, then by name
+    pub fn classify_models(&self, models: &HashSet<String>) -> Vec<(String, bool)> {
+        let mut classified: Vec<(String, bool)> = models
+            .iter()
+            .map(|model| (model.clone(), !self.get_model_pricing(model).is_zero()))
+            .collect();
+        classified.sort_by(|(a_model, a_known), (b_model, b_known)| {
+            a_known.cmp(b_known).then_with(|| a_model.cmp(b_model))
+        });
+        classified
+    }
+
     /// Get display name for a model
     pub fn get_model_display_name(&self, model: &str) -> String {
         if model.contains("opus-4") || model.contains("claude-opus-4") {
@@ -66,8 +178,66 @@ impl CostCalculator {
         }
     }
 
+    /// Normalize a model id to its family by dropping provider prefixes and version/date suffixes
+    ///
+    /// e.g. `claude-3-5-sonnet-20240620` and `claude-3-5-sonnet-20241022` both become `claude-3-5-sonnet`
+    pub fn get_model_family(&self, model: &str) -> String {
+        let mut family = model.trim().to_lowercase();
+
+        // Bedrock ids look like `us.anthropic.claude-...-v1:0`
+        if let Some(start) = family.find("claude") {
+            family = family[start..].to_string();
+        }
+        if let Some(end) = family.find([':', '@']) {
+            family.truncate(end);
+        }
+
+        while let Some((rest, suffix)) = family.rsplit_once('-') {
+            let is_date = suffix.len() == 8 && suffix.chars().all(|c| c.is_ascii_digit());
+            let is_revision = suffix.len() > 1
+                && suffix.starts_with('v')
+                && suffix[1..].chars().all(|c| c.is_ascii_digit());
+            if is_date || is_revision || suffix == "latest" {
+                family = rest.to_string();
+            } else {
+                break;
+            }
+        }
+
+        family
+    }
+
+    /// Human-readable name for a model family, e.g. `claude-3-5-sonnet` -> "Claude 3.5 Sonnet"
+    pub fn get_family_display_name(&self, family: &str) -> String {
+        if !family.starts_with("claude") {
+            return family.to_string();
+        }
+
+        let mut words: Vec<String> = Vec::new();
+        let mut previous_was_number = false;
+        for part in family.split('-').filter(|part| !part.is_empty()) {
+            let is_number = part.chars().all(|c| c.is_ascii_digit());
+            if is_number && previous_was_number {
+                // Consecutive numbers are a dotted version: 3-5 -> 3.5
+                if let Some(last) = words.last_mut() {
+                    last.push('.');
+                    last.push_str(part);
+                }
+            } else {
+                let mut chars = part.chars();
+                let word = match chars.next() {
+                    Some(first) => first.to_uppercase().chain(chars).collect(),
+                    None => String::new(),
+                };
+                words.push(word);
+            }
+            previous_was_number = is_number;
+        }
+
+        words.join(" ")
+    }
+
     /// Get color for a model (for UI display)
-    #[allow(dead_code)] // Feature planned for future implementation
     pub fn get_model_color(&self, model: &str) -> &'static str {
         if model.contains("opus") {
             "#8B5CF6" // Purple for Opus
@@ -81,10 +251,219 @@ impl CostCalculator {
     }
 }
 
+/// Cost split by token category
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CostBreakdown {
+    pub input_cost: f64,
+    pub output_cost: f64,
+    pub cache_read_cost: f64,
+    pub cache_write_cost: f64,
+}
+
+impl CostBreakdown {
+    pub fn total(&self) -> f64 {
+        self.input_cost + self.output_cost + self.cache_read_cost + self.cache_write_cost
+    }
+}
+
+/// Sum of two breakdowns, category by category
+impl Add for CostBreakdown {
+    type Output = CostBreakdown;
+
+    fn add(self, other: CostBreakdown) -> CostBreakdown {
+        CostBreakdown {
+            input_cost: self.input_cost + other.input_cost,
+            output_cost: self.output_cost + other.output_cost,
+            cache_read_cost: self.cache_read_cost + other.cache_read_cost,
+            cache_write_cost: self.cache_write_cost + other.cache_write_cost,
+        }
+    }
+}
+
+/// Rates that applied to matching models between two dates
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoricalPricing {
+    /// Matched against model ids the same way as the built-in table (e.g. "sonnet-4")
+    pub model_pattern: String,
+    /// First day these rates applied
+    pub from: NaiveDate,
+    /// Day the next rates took over (not covered)
+    pub until: NaiveDate,
+    pub pricing: ModelPricing,
+}
+
+impl HistoricalPricing {
+    fn applies_to(&self, model: &str, date: NaiveDate) -> bool {
+        model.contains(&self.model_pattern) && self.from <= date && date < self.until
+    }
+}
+
 /// Pricing information for an AI model
-struct ModelPricing {
-    input_price: f64,      // Per million tokens
-    output_price: f64,     // Per million tokens
-    cache_read_price: f64, // Per million tokens
-    cache_write_price: f64, // Per million tokens
-}
\ No newline at end of file
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ModelPricing {
+    pub input_price: f64,      // Per million tokens
+    pub output_price: f64,     // Per million tokens
+    pub cache_read_price: f64, // Per million tokens
+    pub cache_write_price: f64, // Per million tokens
+}
+
+impl ModelPricing {
+    /// Whether the model fell through to the unknown-model pricing, so all its costs are zero
+    pub fn is_zero(&self) -> bool {
+        self.input_price == 0.0
+            && self.output_price == 0.0
+            && self.cache_read_price == 0.0
+            && self.cache_write_price == 0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{} != {}", actual, expected);
+    }
+
+    #[test]
+    fn test_cost_breakdown_per_category() {
+        let calculator = CostCalculator::new();
+        let breakdown = calculator.calculate_cost_breakdown(
+            "claude-opus-4-20250514",
+            1_000_000,
+            2_000_000,
+            4_000_000,
+            1_000_000,
+        );
+
+        assert_close(breakdown.input_cost, 15.0);
+        assert_close(breakdown.output_cost, 150.0);
+        assert_close(breakdown.cache_read_cost, 6.0);
+        assert_close(breakdown.cache_write_cost, 18.75);
+        assert_close(breakdown.total(), 189.75);
+        assert_close(
+            calculator.calculate_cost("claude-opus-4-20250514", 1_000_000, 2_000_000, 4_000_000, 1_000_000),
+            breakdown.total(),
+        );
+    }
+
+    #[test]
+    fn test_cost_breakdown_unknown_model_is_zero() {
+        let breakdown = CostCalculator::new().calculate_cost_breakdown("mystery-model", 500, 500, 500, 500);
+        assert_eq!(breakdown, CostBreakdown::default());
+    }
+
+    #[test]
+    fn test_cache_write_fallback_only_for_qualifying_models() {
+        let legacy = ModelPricing {
+            input_price: 3.0,
+            output_price: 15.0,
+            cache_read_price: 0.0,
+            cache_write_price: 0.0,
+        };
+        let default_calculator = CostCalculator::new();
+        let fallback_calculator = CostCalculator::with_cache_write_fallback(true);
+
+        // Off by default: unpriced cache writes stay free
+        assert_eq!(default_calculator.apply_cache_write_fallback(legacy), legacy);
+        let billed = fallback_calculator.apply_cache_write_fallback(legacy);
+        assert_close(billed.cache_write_price, 3.0);
+        assert_eq!(ModelPricing { cache_write_price: 0.0, ..billed }, legacy);
+
+        // Models with real cache pricing, and models with no pricing at all, are untouched
+        for model in ["claude-sonnet-4-20250514", "mystery-model"] {
+            assert_eq!(
+                fallback_calculator.calculate_cost_breakdown(model, 1_000, 1_000, 1_000, 1_000_000),
+                default_calculator.calculate_cost_breakdown(model, 1_000, 1_000, 1_000, 1_000_000),
+            );
+        }
+    }
+
+    #[test]
+    fn test_costs_follow_pricing_history() {
+        let date = |month: u32, day: u32| NaiveDate::from_ymd_opt(2025, month, day).unwrap();
+        let at = |month: u32, day: u32| date(month, day).and_hms_opt(12, 0, 0).unwrap().and_utc();
+        // Sonnet 4 at double today's rates until June 1st
+        let calculator = CostCalculator::new().with_pricing_history(vec![HistoricalPricing {
+            model_pattern: "sonnet-4".to_string(),
+            from: date(1, 1),
+            until: date(6, 1),
+            pricing: ModelPricing {
+                input_price: 6.0,
+                output_price: 30.0,
+                cache_read_price: 0.60,
+                cache_write_price: 7.50,
+            },
+        }]);
+        let sonnet = "claude-sonnet-4-20250514";
+        let cost_at = |month: u32, day: u32| calculator.calculate_cost_at(sonnet, &at(month, day), 1_000_000, 1_000_000, 0, 0);
+
+        assert_close(cost_at(5, 31), 36.0);
+        assert_close(cost_at(6, 1), 18.0);
+        // Before the history starts, and for models it doesn't cover, current rates apply
+        assert_close(calculator.calculate_cost_at(sonnet, &at(1, 1), 1_000_000, 0, 0, 0), 6.0);
+        assert_close(
+            calculator.calculate_cost_at(sonnet, &(at(1, 1) - chrono::Duration::days(1)), 1_000_000, 0, 0, 0),
+            3.0,
+        );
+        assert_close(
+            calculator.calculate_cost_at("claude-opus-4-20250514", &at(3, 1), 1_000_000, 0, 0, 0),
+            15.0,
+        );
+        assert_eq!(calculator.get_model_pricing_at(sonnet, date(7, 1)), calculator.get_model_pricing(sonnet));
+    }
+
+    #[test]
+    fn test_model_family_normalization() {
+        let calculator = CostCalculator::new();
+        let cases = [
+            ("claude-3-5-sonnet-20240620", "claude-3-5-sonnet", "Claude 3.5 Sonnet"),
+            ("claude-3-5-sonnet-20241022", "claude-3-5-sonnet", "Claude 3.5 Sonnet"),
+            ("claude-3-5-haiku-latest", "claude-3-5-haiku", "Claude 3.5 Haiku"),
+            ("claude-3-opus-20240229", "claude-3-opus", "Claude 3 Opus"),
+            ("claude-sonnet-4-20250514", "claude-sonnet-4", "Claude Sonnet 4"),
+            ("claude-opus-4-1-20250805", "claude-opus-4-1", "Claude Opus 4.1"),
+            ("claude-3-7-sonnet@20250219", "claude-3-7-sonnet", "Claude 3.7 Sonnet"),
+            ("us.anthropic.claude-3-5-sonnet-20240620-v1:0", "claude-3-5-sonnet", "Claude 3.5 Sonnet"),
+            ("<synthetic>", "<synthetic>", "<synthetic>"),
+        ];
+
+        for (model, family, display) in cases {
+            assert_eq!(calculator.get_model_family(model), family, "family of {}", model);
+            assert_eq!(calculator.get_family_display_name(family), display, "display of {}", family);
+        }
+    }
+
+    #[test]
+    fn test_classify_models() {
+        let models: HashSet<String> = [
+            "claude-sonnet-4-20250514",
+            "claude-opus-4-1-20250805",
+            "claude-3-5-haiku-20241022",
+            "<synthetic>",
+        ]
+        .iter()
+        .map(|m| m.to_string())
+        .collect();
+
+        let classified = CostCalculator::new().classify_models(&models);
+        assert_eq!(
+            classified,
+            vec![
+                ("<synthetic>".to_string(), false),
+                ("claude-3-5-haiku-20241022".to_string(), false),
+                ("claude-opus-4-1-20250805".to_string(), true),
+                ("claude-sonnet-4-20250514".to_string(), true),
+            ]
+        );
+        assert!(CostCalculator::new().classify_models(&HashSet::new()).is_empty());
+    }
+
+    #[test]
+    fn test_unknown_model_pricing_is_zero() {
+        let calculator = CostCalculator::new();
+        assert!(!calculator.get_model_pricing("claude-sonnet-4-20250514").is_zero());
+        assert!(!calculator.get_model_pricing("claude-opus-4").is_zero());
+        assert!(calculator.get_model_pricing("claude-3-5-haiku-20241022").is_zero());
+    }
+}
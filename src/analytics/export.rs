@@ -0,0 +1,765 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::models::{DailyUsage, ModelStats, ParseErrorLog, ProjectStats, SessionStats, UsageEntry, UsageStats};
+use crate::utils::{formatting, system};
+
+/// File format used when exporting the active view
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+    Markdown,
+}
+
+impl ExportFormat {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "CSV",
+            ExportFormat::Json => "JSON",
+            ExportFormat::Markdown => "Markdown",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+            ExportFormat::Markdown => "md",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            ExportFormat::Csv => ExportFormat::Json,
+            ExportFormat::Json => ExportFormat::Markdown,
+            ExportFormat::Markdown => ExportFormat::Csv,
+        }
+    }
+}
+
+/// Rows of one dashboard view, ready to be written in any `ExportFormat`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportTable {
+    pub headers: Vec<&'static str>,
+    /// Per column, whether its cells hold numbers (JSON numbers, right-aligned in Markdown)
+    /// rather than text that merely looks numeric, like a project named "2024"
+    pub numeric: Vec<bool>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl ExportTable {
+    pub fn render(&self, format: ExportFormat) -> String {
+        match format {
+            ExportFormat::Csv => self.to_csv(),
+            ExportFormat::Json => self.to_json(),
+            ExportFormat::Markdown => self.to_markdown(),
+        }
+    }
+
+    fn to_csv(&self) -> String {
+        let mut csv = self.headers.join(",");
+        csv.push('\n');
+        for row in &self.rows {
+            let cells: Vec<String> = row.iter().map(|cell| csv_field(cell)).collect();
+            csv.push_str(&cells.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+
+    /// Tab-separated values for pasting into a spreadsheet (clipboard only, not a file format)
+    pub fn to_tsv(&self) -> String {
+        let mut tsv = self.headers.join("\t");
+        tsv.push('\n');
+        for row in &self.rows {
+            let cells: Vec<String> = row.iter().map(|cell| tsv_field(cell)).collect();
+            tsv.push_str(&cells.join("\t"));
+            tsv.push('\n');
+        }
+        tsv
+    }
+
+    fn is_numeric(&self, column: usize) -> bool {
+        self.numeric.get(column).copied().unwrap_or(false)
+    }
+
+    /// An array of objects keyed by header; cells in numeric columns become JSON numbers
+    fn to_json(&self) -> String {
+        let records: Vec<Value> = self
+            .rows
+            .iter()
+            .map(|row| {
+                let mut record = Map::new();
+                for (column, (header, cell)) in self.headers.iter().zip(row).enumerate() {
+                    let number = if self.is_numeric(column) {
+                        cell.parse::<u64>()
+                            .map(Value::from)
+                            .or_else(|_| cell.parse::<f64>().map(Value::from))
+                            .ok()
+                    } else {
+                        None
+                    };
+                    let value = number.unwrap_or_else(|| Value::from(cell.as_str()));
+                    record.insert(header.to_string(), value);
+                }
+                Value::Object(record)
+            })
+            .collect();
+        // Plain strings and numbers always serialize
+        serde_json::to_string_pretty(&records).unwrap_or_default()
+    }
+
+    /// Markdown table with numeric columns right-aligned
+    fn to_markdown(&self) -> String {
+        let headers: Vec<(&str, bool)> = self
+            .headers
+            .iter()
+            .enumerate()
+            .map(|(column, header)| (*header, self.is_numeric(column)))
+            .collect();
+        markdown_table(&headers, &self.rows)
+    }
+}
+
+/// Per-model totals, most expensive first
+pub fn models_table(stats: &UsageStats) -> ExportTable {
+    let mut models: Vec<&ModelStats> = stats.model_stats.values().collect();
+    models.sort_by(|a, b| b.total_cost.partial_cmp(&a.total_cost).unwrap_or(std::cmp::Ordering::Equal));
+
+    ExportTable {
+        headers: vec!["model", "requests", "input_tokens", "output_tokens", "cache_read_tokens", "cache_creation_tokens", "total_tokens", "cost"],
+        numeric: vec![false, true, true, true, true, true, true, true],
+        rows: models
+            .iter()
+            .map(|m| {
+                vec![
+                    m.model.clone(),
+                    m.request_count.to_string(),
+                    m.input_tokens.to_string(),
+                    m.output_tokens.to_string(),
+                    m.cache_read_tokens.to_string(),
+                    m.cache_creation_tokens.to_string(),
+                    m.total_tokens.to_string(),
+                    format!("{:.6}", m.total_cost),
+                ]
+            })
+            .collect(),
+    }
+}
+
+/// Per-project totals in the order given (e.g. as sorted on the Projects tab)
+pub fn project_rows(projects: &[ProjectStats]) -> ExportTable {
+    ExportTable {
+        headers: vec!["project", "project_path", "sessions", "requests", "total_tokens", "cost", "last_used"],
+        numeric: vec![false, false, true, true, true, true, false],
+        rows: projects
+            .iter()
+            .map(|p| {
+                vec![
+                    p.project_name.clone(),
+                    p.project_path.clone(),
+                    p.session_count.to_string(),
+                    p.request_count.to_string(),
+                    p.total_tokens.to_string(),
+                    format!("{:.6}", p.total_cost),
+                    p.last_used.to_rfc3339(),
+                ]
+            })
+            .collect(),
+    }
+}
+
+/// Per-session totals in the order given (e.g. as listed on the Sessions tab), with start and
+/// end times both in UTC and in `timezone` (system local when None)
+pub fn session_rows(sessions: &[SessionStats], timezone: Option<Tz>) -> ExportTable {
+    ExportTable {
+        headers: vec![
+            "session_id",
+            "project_path",
+            "start_time",
+            "start_time_local",
+            "end_time",
+            "end_time_local",
+            "requests",
+            "total_tokens",
+            "cost",
+        ],
+        numeric: vec![false, false, false, false, false, false, true, true, true],
+        rows: sessions
+            .iter()
+            .map(|s| {
+                vec![
+                    s.session_id.clone(),
+                    s.project_path.clone(),
+                    s.start_time.to_rfc3339(),
+                    local_timestamp(&s.start_time, timezone),
+                    s.end_time.to_rfc3339(),
+                    local_timestamp(&s.end_time, timezone),
+                    s.request_count.to_string(),
+                    s.total_tokens.to_string(),
+                    format!("{:.6}", s.total_cost),
+                ]
+            })
+            .collect(),
+    }
+}
+
+/// Per-day totals for days with usage, oldest first
+pub fn daily_table(daily: &[DailyUsage]) -> ExportTable {
+    let mut days: Vec<&DailyUsage> = daily.iter().collect();
+    days.sort_by(|a, b| a.date.cmp(&b.date));
+
+    ExportTable {
+        headers: vec!["date", "requests", "total_tokens", "cost", "models_used"],
+        numeric: vec![false, true, true, true, false],
+        rows: days
+            .iter()
+            .map(|d| {
+                vec![
+                    d.date.clone(),
+                    d.request_count.to_string(),
+                    d.total_tokens.to_string(),
+                    format!("{:.6}", d.total_cost),
+                    d.models_used.join(";"),
+                ]
+            })
+            .collect(),
+    }
+}
+
+/// Quote a CSV field when it contains a delimiter, quote, or newline
+fn csv_field(value: &str) -> String {
+    if value.contains(',') || value.contains('"') || value.contains('\n') {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Tabs and line breaks would split the cell, so they become spaces
+fn tsv_field(value: &str) -> String {
+    value.replace(['\t', '\n', '\r'], " ")
+}
+
+/// RFC 3339 timestamp in `timezone` (system local when None), offset included
+pub fn local_timestamp(timestamp: &DateTime<Utc>, timezone: Option<Tz>) -> String {
+    match timezone {
+        Some(tz) => timestamp.with_timezone(&tz).to_rfc3339(),
+        None => timestamp.with_timezone(&Local).to_rfc3339(),
+    }
+}
+
+/// Serialize raw usage entries as CSV, one row per entry; `timestamp` stays in UTC and
+/// `local_time` repeats it in `timezone` (system local when None)
+pub fn export_entries_csv(entries: &[UsageEntry], timezone: Option<Tz>) -> String {
+    let mut csv = String::from(
+        "timestamp,local_time,model,project_path,session_id,request_id,input_tokens,output_tokens,cache_read_tokens,cache_creation_tokens,cost\n",
+    );
+
+    for entry in entries {
+        let row = [
+            entry.timestamp.to_rfc3339(),
+            local_timestamp(&entry.timestamp, timezone),
+            csv_field(&entry.model),
+            csv_field(entry.project_path.as_deref().unwrap_or("")),
+            csv_field(entry.session_id.as_deref().unwrap_or("")),
+            csv_field(entry.request_id.as_deref().unwrap_or("")),
+            entry.input_tokens.to_string(),
+            entry.output_tokens.to_string(),
+            entry.cache_read_tokens.to_string(),
+            entry.cache_creation_tokens.to_string(),
+            format!("{:.6}", entry.cost),
+        ];
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+
+    csv
+}
+
+/// Serialize daily usage as CSV, one row per calendar day from the first to the last day,
+/// with zero rows for days without usage so gaps are explicit
+pub fn export_daily_csv(daily: &[DailyUsage]) -> String {
+    let mut csv = String::from(
+        "date,cost,total_tokens,input_tokens,output_tokens,cache_read_tokens,cache_creation_tokens,requests,models_used\n",
+    );
+
+    let mut days: Vec<(NaiveDate, &DailyUsage)> = daily
+        .iter()
+        .filter_map(|day| NaiveDate::parse_from_str(&day.date, "%Y-%m-%d").ok().map(|date| (date, day)))
+        .collect();
+    days.sort_by_key(|(date, _)| *date);
+    let (Some((first, _)), Some((last, _))) = (days.first(), days.last()) else {
+        return csv;
+    };
+
+    let mut remaining = days.iter().peekable();
+    for date in first.iter_days().take_while(|date| date <= last) {
+        let row = match remaining.next_if(|(day_date, _)| *day_date == date) {
+            Some((_, day)) => [
+                date.to_string(),
+                format!("{:.6}", day.total_cost),
+                day.total_tokens.to_string(),
+                day.input_tokens.to_string(),
+                day.output_tokens.to_string(),
+                day.cache_read_tokens.to_string(),
+                day.cache_creation_tokens.to_string(),
+                day.request_count.to_string(),
+                csv_field(&day.models_used.join(";")),
+            ],
+            None => [
+                date.to_string(),
+                "0.000000".to_string(),
+                "0".to_string(),
+                "0".to_string(),
+                "0".to_string(),
+                "0".to_string(),
+                "0".to_string(),
+                "0".to_string(),
+                String::new(),
+            ],
+        };
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+
+    csv
+}
+
+/// Serialize entries as JSON Lines, oldest first, with fields in declaration order
+pub fn export_merged_jsonl(entries: &[UsageEntry]) -> String {
+    let mut sorted: Vec<&UsageEntry> = entries.iter().collect();
+    sorted.sort_by_key(|e| e.timestamp);
+
+    let mut jsonl = String::new();
+    for entry in sorted {
+        // UsageEntry only holds plain data, so serialization can't fail
+        if let Ok(line) = serde_json::to_string(entry) {
+            jsonl.push_str(&line);
+            jsonl.push('\n');
+        }
+    }
+    jsonl
+}
+
+/// Escape characters that would break a Markdown table cell
+fn markdown_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', " ")
+}
+
+/// Build a GitHub-flavored Markdown table; numeric columns are right-aligned
+fn markdown_table(headers: &[(&str, bool)], rows: &[Vec<String>]) -> String {
+    let header_row: Vec<&str> = headers.iter().map(|(name, _)| *name).collect();
+    let separator_row: Vec<&str> = headers
+        .iter()
+        .map(|(_, right_aligned)| if *right_aligned { "---:" } else { "---" })
+        .collect();
+
+    let mut table = format!("| {} |\n| {} |\n", header_row.join(" | "), separator_row.join(" | "));
+    for row in rows {
+        let cells: Vec<String> = row.iter().map(|cell| markdown_cell(cell)).collect();
+        table.push_str(&format!("| {} |\n", cells.join(" | ")));
+    }
+    table
+}
+
+/// Per-model breakdown as a Markdown table, most expensive first
+pub fn export_models_markdown(stats: &UsageStats) -> String {
+    let mut models: Vec<&ModelStats> = stats.model_stats.values().collect();
+    models.sort_by(|a, b| b.total_cost.partial_cmp(&a.total_cost).unwrap_or(std::cmp::Ordering::Equal));
+
+    let rows: Vec<Vec<String>> = models
+        .iter()
+        .map(|m| {
+            vec![
+                m.display_name.clone(),
+                m.request_count.to_string(),
+                m.total_tokens.to_string(),
+                format!("${:.2}", m.total_cost),
+            ]
+        })
+        .collect();
+
+    markdown_table(
+        &[("Model", false), ("Requests", true), ("Tokens", true), ("Cost", true)],
+        &rows,
+    )
+}
+
+/// Per-project breakdown as a Markdown table, most expensive first
+pub fn export_projects_markdown(stats: &UsageStats) -> String {
+    let mut projects: Vec<&ProjectStats> = stats.project_stats.values().collect();
+    projects.sort_by(|a, b| b.total_cost.partial_cmp(&a.total_cost).unwrap_or(std::cmp::Ordering::Equal));
+
+    let rows: Vec<Vec<String>> = projects
+        .iter()
+        .map(|p| {
+            vec![
+                p.project_name.clone(),
+                p.session_count.to_string(),
+                p.request_count.to_string(),
+                p.total_tokens.to_string(),
+                format!("${:.2}", p.total_cost),
+            ]
+        })
+        .collect();
+
+    markdown_table(
+        &[
+            ("Project", false),
+            ("Sessions", true),
+            ("Requests", true),
+            ("Tokens", true),
+            ("Cost", true),
+        ],
+        &rows,
+    )
+}
+
+/// Rows listed per section of the shareable summary
+const SUMMARY_TOP_ROWS: usize = 3;
+
+/// Overview totals as plain text for sharing, headed by the time range and any filters
+/// (such as exclusions) that shaped the numbers.
+///
+/// This stands in for an image export: GPUI cannot yet render a view offscreen to a
+/// bitmap, so the summary is shared as text instead.
+pub fn overview_summary(stats: &UsageStats, range_label: &str, filters: &[String]) -> String {
+    let request_count: usize = stats.model_stats.values().map(|m| m.request_count).sum();
+    let share = |cost: f64| if stats.total_cost > 0.0 { cost / stats.total_cost * 100.0 } else { 0.0 };
+
+    let mut summary = format!("Claude Code usage · {}\n", range_label);
+    if !filters.is_empty() {
+        summary.push_str(&format!("Filters: {}\n", filters.join("; ")));
+    }
+    summary.push_str(&format!(
+        "\nTotal cost: ${:.2}\nTokens: {}\nRequests: {}\nSessions: {}\n",
+        stats.total_cost,
+        formatting::format_grouped(stats.total_tokens),
+        formatting::format_grouped(request_count as u64),
+        formatting::format_grouped(stats.session_count as u64),
+    ));
+
+    let mut models: Vec<&ModelStats> = stats.model_stats.values().filter(|m| m.request_count > 0).collect();
+    models.sort_by(|a, b| b.total_cost.total_cmp(&a.total_cost).then_with(|| a.display_name.cmp(&b.display_name)));
+    if !models.is_empty() {
+        summary.push_str("\nTop models\n");
+        for model in models.iter().take(SUMMARY_TOP_ROWS) {
+            summary.push_str(&format!("  {} · ${:.2} ({:.0}%)\n", model.display_name, model.total_cost, share(model.total_cost)));
+        }
+    }
+
+    let mut projects: Vec<&ProjectStats> = stats.project_stats.values().collect();
+    projects.sort_by(|a, b| b.total_cost.total_cmp(&a.total_cost).then_with(|| a.project_name.cmp(&b.project_name)));
+    if !projects.is_empty() {
+        summary.push_str("\nTop projects\n");
+        for project in projects.iter().take(SUMMARY_TOP_ROWS) {
+            summary.push_str(&format!("  {} · ${:.2} ({:.0}%)\n", project.project_name, project.total_cost, share(project.total_cost)));
+        }
+    }
+    summary
+}
+
+/// File the parse errors log is written to
+pub const PARSE_ERRORS_FILE_NAME: &str = "parse-errors.log";
+
+/// One line per skipped line or file as `path:line: reason`, for debugging offline.
+/// Whole-file failures have no line number; errors past the cap are noted at the end.
+pub fn parse_errors_log(log: &ParseErrorLog) -> String {
+    let mut contents = String::new();
+    for error in &log.errors {
+        let location = match error.line {
+            Some(line) => format!("{}:{}", error.file.display(), line),
+            None => error.file.display().to_string(),
+        };
+        contents.push_str(&format!("{}: {}\n", location, error.reason.replace('\n', " ")));
+    }
+    if log.dropped > 0 {
+        contents.push_str(&format!("... {} more not recorded (limit reached)\n", log.dropped));
+    }
+    contents
+}
+
+/// Entries belonging to a single project
+pub fn entries_for_project(entries: &[UsageEntry], project_path: &str) -> Vec<UsageEntry> {
+    entries
+        .iter()
+        .filter(|e| e.project_key() == project_path)
+        .cloned()
+        .collect()
+}
+
+/// Entries belonging to a single session within a project
+pub fn entries_for_session(entries: &[UsageEntry], project_path: &str, session_id: &str) -> Vec<UsageEntry> {
+    entries
+        .iter()
+        .filter(|e| e.project_key() == project_path)
+        .filter(|e| e.session_id.as_deref().unwrap_or("Unknown") == session_id)
+        .cloned()
+        .collect()
+}
+
+/// Build a filesystem-safe file name from a project or session name
+pub fn suggested_file_name(name: &str, extension: &str) -> String {
+    let sanitized: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '-' })
+        .collect();
+    let trimmed = sanitized.trim_matches('-');
+    let stem = if trimmed.is_empty() { "export" } else { trimmed };
+    format!("claude-usage-{}.{}", stem, extension)
+}
+
+/// Write export contents into the user's downloads directory
+pub fn write_export(file_name: &str, contents: &str) -> Result<PathBuf> {
+    let export_dir = dirs::download_dir().unwrap_or_else(system::home_dir);
+    let path = export_dir.join(file_name);
+    fs::write(&path, contents).with_context(|| format!("Failed to write export: {:?}", path))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn entry(project: &str, session: &str, cost: f64) -> UsageEntry {
+        UsageEntry {
+            timestamp: Utc.with_ymd_and_hms(2025, 7, 1, 12, 0, 0).unwrap(),
+            model: "claude-sonnet-4-20250514".to_string(),
+            project_path: Some(project.to_string()),
+            session_id: Some(session.to_string()),
+            request_id: Some("req_1".to_string()),
+            input_tokens: 100,
+            output_tokens: 50,
+            cache_read_tokens: 10,
+            cache_creation_tokens: 5,
+            cost,
+            computed_cost: cost,
+            logged_cost: None,
+            future_dated: false,
+            raw_line: None,
+        }
+    }
+
+    #[test]
+    fn test_export_single_project_csv() {
+        let entries = vec![
+            entry("/Users/dev/alpha", "s1", 0.25),
+            entry("/Users/dev/beta", "s2", 1.50),
+            entry("/Users/dev/alpha", "s3", 0.75),
+        ];
+
+        let alpha = entries_for_project(&entries, "/Users/dev/alpha");
+        assert_eq!(alpha.len(), 2);
+
+        let csv = export_entries_csv(&alpha, Some(Tz::UTC));
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("timestamp,local_time,model,project_path"));
+        assert!(lines.iter().skip(1).all(|line| line.contains("/Users/dev/alpha")));
+        assert!(!csv.contains("/Users/dev/beta"));
+        assert!(lines[1].ends_with(",100,50,10,5,0.250000"));
+
+        let session = entries_for_session(&entries, "/Users/dev/beta", "s2");
+        assert_eq!(export_entries_csv(&session, Some(Tz::UTC)).lines().count(), 2);
+    }
+
+    #[test]
+    fn test_timestamps_in_configured_timezone() {
+        let entries = vec![entry("/Users/dev/alpha", "s1", 0.25)];
+
+        // 12:00 UTC on 1 July is 14:00 in Berlin (CEST) and 08:00 in New York (EDT)
+        let csv = export_entries_csv(&entries, Some(Tz::Europe__Berlin));
+        let row = csv.lines().nth(1).unwrap();
+        assert!(row.starts_with("2025-07-01T12:00:00+00:00,2025-07-01T14:00:00+02:00,"));
+
+        let winter = Utc.with_ymd_and_hms(2025, 1, 15, 3, 30, 0).unwrap();
+        assert_eq!(local_timestamp(&winter, Some(Tz::America__New_York)), "2025-01-14T22:30:00-05:00");
+
+        let session = SessionStats {
+            session_id: "s1".to_string(),
+            project_path: "/Users/dev/alpha".to_string(),
+            total_cost: 0.25,
+            total_tokens: 165,
+            input_tokens: 100,
+            output_tokens: 50,
+            cache_read_tokens: 10,
+            cache_creation_tokens: 5,
+            request_count: 1,
+            start_time: entries[0].timestamp,
+            end_time: entries[0].timestamp,
+        };
+        let table = session_rows(&[session], Some(Tz::Asia__Kolkata));
+        let start_local = table.headers.iter().position(|h| *h == "start_time_local").unwrap();
+        assert_eq!(table.rows[0][start_local], "2025-07-01T17:30:00+05:30");
+    }
+
+    #[test]
+    fn test_suggested_file_name() {
+        assert_eq!(suggested_file_name("my project", "csv"), "claude-usage-my-project.csv");
+        assert_eq!(suggested_file_name("///", "csv"), "claude-usage-export.csv");
+    }
+
+    #[test]
+    fn test_markdown_tables() {
+        use crate::analytics::aggregator::UsageAggregator;
+
+        let entries = vec![
+            entry("/Users/dev/alpha", "s1", 0.25),
+            entry("/Users/dev/be|ta", "s2", 1.50),
+        ];
+        let stats = UsageAggregator::new().calculate_usage_stats(&entries);
+
+        let models = export_models_markdown(&stats);
+        let lines: Vec<&str> = models.lines().collect();
+        assert_eq!(lines[0], "| Model | Requests | Tokens | Cost |");
+        assert_eq!(lines[1], "| --- | ---: | ---: | ---: |");
+        assert_eq!(lines[2], "| Sonnet 4 | 2 | 330 | $1.75 |");
+        assert_eq!(lines.len(), 3);
+
+        let projects = export_projects_markdown(&stats);
+        let lines: Vec<&str> = projects.lines().collect();
+        assert_eq!(lines[0], "| Project | Sessions | Requests | Tokens | Cost |");
+        assert_eq!(lines[1], "| --- | ---: | ---: | ---: | ---: |");
+        // Most expensive first, with pipes in names escaped
+        assert_eq!(lines[2], "| be\\|ta | 1 | 1 | 165 | $1.50 |");
+        assert_eq!(lines[3], "| alpha | 1 | 1 | 165 | $0.25 |");
+    }
+
+    #[test]
+    fn test_overview_summary() {
+        use crate::analytics::aggregator::UsageAggregator;
+
+        let entries = vec![
+            entry("/Users/dev/alpha", "s1", 0.25),
+            entry("/Users/dev/beta", "s2", 1.50),
+            entry("/Users/dev/beta", "s2", 2.25),
+        ];
+        let stats = UsageAggregator::new().calculate_usage_stats(&entries);
+
+        let summary = overview_summary(&stats, "Last 30 Days", &["Since 2025-07-01".to_string()]);
+        let lines: Vec<&str> = summary.lines().collect();
+        assert_eq!(lines[0], "Claude Code usage · Last 30 Days");
+        assert_eq!(lines[1], "Filters: Since 2025-07-01");
+        assert!(lines.contains(&"Total cost: $4.00"));
+        assert!(lines.contains(&"Requests: 3"));
+        assert!(lines.contains(&"Sessions: 2"));
+        assert!(lines.contains(&"  Sonnet 4 · $4.00 (100%)"));
+        // Projects costliest first
+        let beta = lines.iter().position(|l| *l == "  beta · $3.75 (94%)").unwrap();
+        let alpha = lines.iter().position(|l| *l == "  alpha · $0.25 (6%)").unwrap();
+        assert!(beta < alpha);
+
+        // No filter line without filters, and no sections without data
+        let empty = overview_summary(&UsageStats::new(), "Today", &[]);
+        assert!(!empty.contains("Filters") && !empty.contains("Top models"));
+        assert!(empty.contains("Total cost: $0.00"));
+    }
+
+    #[test]
+    fn test_daily_csv_fills_gaps_and_joins_models() {
+        let day = |date: &str, cost: f64, models: &[&str]| DailyUsage {
+            date: date.to_string(),
+            total_cost: cost,
+            total_tokens: 150,
+            input_tokens: 100,
+            output_tokens: 50,
+            cache_read_tokens: 0,
+            cache_creation_tokens: 0,
+            request_count: 1,
+            models_used: models.iter().map(|m| m.to_string()).collect(),
+        };
+        // Out of order, with a two-day gap
+        let daily = vec![
+            day("2025-07-04", 2.0, &["claude-opus-4"]),
+            day("2025-07-01", 1.0, &["claude-sonnet-4", "claude-opus-4"]),
+        ];
+
+        let csv = export_daily_csv(&daily);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert!(lines[0].starts_with("date,cost,"));
+        assert_eq!(lines[1], "2025-07-01,1.000000,150,100,50,0,0,1,claude-sonnet-4;claude-opus-4");
+        assert_eq!(lines[2], "2025-07-02,0.000000,0,0,0,0,0,0,");
+        assert_eq!(lines[3], "2025-07-03,0.000000,0,0,0,0,0,0,");
+        assert_eq!(lines[4], "2025-07-04,2.000000,150,100,50,0,0,1,claude-opus-4");
+
+        assert_eq!(export_daily_csv(&[]).lines().count(), 1);
+    }
+
+    #[test]
+    fn test_export_table_formats() {
+        use crate::analytics::aggregator::UsageAggregator;
+
+        let entries = vec![
+            entry("/Users/dev/alpha", "s1", 0.25),
+            entry("/Users/dev/be,ta", "s2", 1.50),
+        ];
+        let aggregator = UsageAggregator::new();
+        let stats = aggregator.calculate_usage_stats(&entries);
+        let mut projects: Vec<ProjectStats> = stats.project_stats.values().cloned().collect();
+        aggregator.sort_projects(&mut projects, crate::analytics::models::ProjectSort::TotalCost);
+        let table = project_rows(&projects);
+
+        let csv = table.render(ExportFormat::Csv);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "project,project_path,sessions,requests,total_tokens,cost,last_used");
+        assert!(lines[1].starts_with("\"be,ta\",\"/Users/dev/be,ta\",1,1,165,1.500000,"));
+
+        let json: Value = serde_json::from_str(&table.render(ExportFormat::Json)).unwrap();
+        assert_eq!(json[1]["project"], "alpha");
+        assert_eq!(json[1]["requests"], 1);
+        assert_eq!(json[1]["cost"], 0.25);
+
+        let markdown = table.render(ExportFormat::Markdown);
+        let lines: Vec<&str> = markdown.lines().collect();
+        assert_eq!(lines[1], "| --- | --- | ---: | ---: | ---: | ---: | --- |");
+        assert_eq!(lines.len(), 4);
+    }
+
+    #[test]
+    fn test_numeric_looking_names_stay_text() {
+        use crate::analytics::aggregator::UsageAggregator;
+
+        let stats = UsageAggregator::new().calculate_usage_stats(&[entry("/Users/dev/2024", "s1", 0.25)]);
+        let alpha = stats.project_stats.values().next().unwrap().clone();
+        let project = |name: &str| ProjectStats { project_name: name.to_string(), ..alpha.clone() };
+        let table = project_rows(&[project("2024"), project("NaN")]);
+
+        let json: Value = serde_json::from_str(&table.render(ExportFormat::Json)).unwrap();
+        assert_eq!(json[0]["project"], "2024");
+        assert_eq!(json[1]["project"], "NaN");
+        assert_eq!(json[0]["sessions"], 1);
+
+        // Alignment follows the column's type, not whether its cells happen to parse
+        let markdown = table.render(ExportFormat::Markdown);
+        assert_eq!(markdown.lines().nth(1), Some("| --- | --- | ---: | ---: | ---: | ---: | --- |"));
+    }
+
+    #[test]
+    fn test_export_table_tsv() {
+        let table = ExportTable {
+            headers: vec!["project", "requests", "cost"],
+            numeric: vec![false, true, true],
+            rows: vec![
+                vec!["alpha".to_string(), "3".to_string(), "1.250000".to_string()],
+                vec!["be\tta, inc".to_string(), "1".to_string(), "0.100000".to_string()],
+            ],
+        };
+
+        assert_eq!(
+            table.to_tsv(),
+            "project\trequests\tcost\nalpha\t3\t1.250000\nbe ta, inc\t1\t0.100000\n"
+        );
+        assert_eq!(ExportTable { headers: vec!["date"], numeric: vec![false], rows: Vec::new() }.to_tsv(), "date\n");
+    }
+}
@@ -2,6 +2,7 @@ pub mod models;
 pub mod processor;
 pub mod calculator;
 pub mod aggregator;
+pub mod export;
 
 pub use models::{UsageStats, ModelStats, ProjectStats, SessionStats, DailyUsage};
 // Unused exports removed during cleanup
\ No newline at end of file
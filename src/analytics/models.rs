@@ -1,19 +1,109 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Utc, Weekday};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use super::calculator::HistoricalPricing;
 
 /// Raw usage entry from JSONL file
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct UsageEntry {
     pub timestamp: DateTime<Utc>,
     pub model: String,
     pub project_path: Option<String>,
     pub session_id: Option<String>,
     pub request_id: Option<String>,
-    pub input_tokens: u32,
-    pub output_tokens: u32,
-    pub cache_read_tokens: u32,
-    pub cache_creation_tokens: u32,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_creation_tokens: u64,
+    /// Cost used for reporting: `logged_cost` when present, otherwise `computed_cost`
+    /// (always `computed_cost` when costs are recomputed)
     pub cost: f64,
+    /// Cost derived from token counts via the pricing table
+    #[serde(default)]
+    pub computed_cost: f64,
+    /// `costUSD` as written in the log, kept so the reported cost can be switched without a reload
+    #[serde(default)]
+    pub logged_cost: Option<f64>,
+    /// Timestamp was further ahead of the clock than `FUTURE_ENTRY_TOLERANCE_MINUTES` when loaded
+    #[serde(default)]
+    pub future_dated: bool,
+    /// The JSONL line the entry was read from, kept only when raw capture is on
+    #[serde(skip)]
+    pub raw_line: Option<String>,
+}
+
+/// How far ahead of the current time an entry may be before it counts as clock-skewed
+pub const FUTURE_ENTRY_TOLERANCE_MINUTES: i64 = 60;
+
+/// Relative difference between logged and computed cost above which an entry is flagged
+pub const COST_DIVERGENCE_THRESHOLD: f64 = 0.10;
+
+/// Canonical form of a project path: repeated slashes collapsed and any trailing slash removed
+pub fn normalize_project_path(path: &str) -> String {
+    let mut normalized = String::with_capacity(path.len());
+    for c in path.chars() {
+        if c == '/' && normalized.ends_with('/') {
+            continue;
+        }
+        normalized.push(c);
+    }
+    if normalized.len() > 1 && normalized.ends_with('/') {
+        normalized.pop();
+    }
+    normalized
+}
+
+/// Key used for entries logged without a cwd
+pub const UNKNOWN_PROJECT: &str = "Unknown Project";
+
+/// Whether a logged cwd is relative, so it can't be placed on disk or named like an absolute path.
+/// Unix roots, Windows drive letters and UNC shares, and `~` all count as anchored.
+pub fn is_relative_project_path(path: &str) -> bool {
+    let mut chars = path.chars();
+    let anchored = match (chars.next(), chars.next()) {
+        (None, _) => true,
+        (Some('/' | '\\' | '~'), _) => true,
+        (Some(drive), Some(':')) => drive.is_ascii_alphabetic(),
+        _ => false,
+    };
+    !anchored && path != UNKNOWN_PROJECT
+}
+
+/// Cost that is safe to add into totals: NaN and infinities count as nothing, negatives as free
+pub fn sane_cost(cost: f64) -> f64 {
+    if cost.is_finite() {
+        cost.max(0.0)
+    } else {
+        0.0
+    }
+}
+
+impl UsageEntry {
+    /// Normalized project path used to group entries, or "Unknown Project" when missing
+    pub fn project_key(&self) -> String {
+        self.project_path
+            .as_deref()
+            .map(normalize_project_path)
+            .unwrap_or_else(|| UNKNOWN_PROJECT.to_string())
+    }
+
+    /// Whether the logged cost differs from the computed cost by more than the threshold
+    pub fn is_cost_divergent(&self) -> bool {
+        self.logged_cost.is_some_and(|logged| {
+            logged > 0.0 && (logged - self.computed_cost).abs() / logged > COST_DIVERGENCE_THRESHOLD
+        })
+    }
+
+    /// The entry reported at its logged cost, or at the computed cost when `recompute` is set
+    pub fn with_cost_source(self, recompute: bool) -> Self {
+        let cost = match self.logged_cost {
+            Some(logged) if !recompute => logged,
+            _ => self.computed_cost,
+        };
+        Self { cost, ..self }
+    }
 }
 
 /// Aggregated statistics for the dashboard
@@ -31,6 +121,12 @@ pub struct UsageStats {
     pub project_stats: std::collections::HashMap<String, ProjectStats>,
     pub session_stats: std::collections::HashMap<String, SessionStats>,
     pub daily_usage: std::collections::HashMap<String, DailyUsage>,
+    /// Number of single requests that cost more than the request cost alert
+    pub requests_over_cost_alert: usize,
+    /// The single costliest request
+    pub most_expensive_request: Option<UsageEntry>,
+    /// Lines skipped while loading; only the full load carries these, filtered views leave it empty
+    pub parse_errors: ParseErrorLog,
 }
 
 impl UsageStats {
@@ -48,6 +144,9 @@ impl UsageStats {
             project_stats: std::collections::HashMap::new(),
             session_stats: std::collections::HashMap::new(),
             daily_usage: std::collections::HashMap::new(),
+            requests_over_cost_alert: 0,
+            most_expensive_request: None,
+            parse_errors: ParseErrorLog::default(),
         }
     }
 
@@ -57,6 +156,58 @@ impl UsageStats {
     }
 }
 
+/// How regularly usage happened between the first and last active day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UsageFrequency {
+    /// Calendar days from the first to the last active day, inclusive
+    pub span_days: usize,
+    pub active_days: usize,
+    /// Days inside the span without a single request
+    pub idle_days: usize,
+}
+
+impl UsageFrequency {
+    /// Share of days in the span with any usage (0.0 - 1.0)
+    pub fn frequency(&self) -> f64 {
+        self.active_days as f64 / self.span_days as f64
+    }
+}
+
+/// Cache writes versus cache reads across a set of entries
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CacheEfficiency {
+    pub creation_tokens: u64,
+    pub read_tokens: u64,
+    pub creation_cost: f64,
+    pub read_cost: f64,
+}
+
+impl CacheEfficiency {
+    /// Cache read tokens per cache creation token; None when nothing was written to the cache
+    pub fn reads_per_creation(&self) -> Option<f64> {
+        (self.creation_tokens > 0).then(|| self.read_tokens as f64 / self.creation_tokens as f64)
+    }
+
+    /// Short interpretation of the ratio
+    pub fn label(&self) -> &'static str {
+        match self.reads_per_creation() {
+            Some(ratio) if ratio >= 5.0 => "Good reuse",
+            Some(ratio) if ratio >= 1.0 => "Some reuse",
+            Some(_) => "Low reuse",
+            None if self.read_tokens > 0 => "Reads only",
+            None => "No caching",
+        }
+    }
+}
+
+/// Tokens bought per dollar for one model
+#[derive(Debug, Clone)]
+pub struct ModelEfficiency {
+    pub model: String,
+    pub display_name: String,
+    pub tokens_per_dollar: f64,
+}
+
 /// Model usage breakdown
 #[derive(Debug, Clone)]
 pub struct ModelStats {
@@ -71,6 +222,13 @@ pub struct ModelStats {
     pub request_count: usize,
 }
 
+impl ModelStats {
+    /// Blended cost of a thousand tokens across every token type; None without tokens
+    pub fn cost_per_thousand_tokens(&self) -> Option<f64> {
+        (self.total_tokens > 0).then(|| self.total_cost / self.total_tokens as f64 * 1000.0)
+    }
+}
+
 /// Project usage breakdown
 #[derive(Debug, Clone)]
 pub struct ProjectStats {
@@ -84,9 +242,28 @@ pub struct ProjectStats {
     pub cache_creation_tokens: u64,
     pub request_count: usize,
     pub session_count: usize,
+    pub cost_per_request: f64,
+    /// Total cost spread over the project's sessions (0 without sessions)
+    pub avg_session_cost: f64,
+    /// Local calendar days with at least one request
+    pub active_days: usize,
+    /// Sessions started per active day (0 without active days)
+    pub sessions_per_active_day: f64,
     pub last_used: DateTime<Utc>,
 }
 
+impl ProjectStats {
+    /// Token count for one category
+    pub fn tokens(&self, category: TokenCategory) -> u64 {
+        match category {
+            TokenCategory::Input => self.input_tokens,
+            TokenCategory::Output => self.output_tokens,
+            TokenCategory::CacheRead => self.cache_read_tokens,
+            TokenCategory::CacheCreation => self.cache_creation_tokens,
+        }
+    }
+}
+
 /// Session usage breakdown
 #[derive(Debug, Clone)]
 pub struct SessionStats {
@@ -99,8 +276,26 @@ pub struct SessionStats {
     pub cache_read_tokens: u64,
     pub cache_creation_tokens: u64,
     pub request_count: usize,
-    pub timestamp: DateTime<Utc>,
-    // Removed unused date field during cleanup
+    /// Earliest entry in the session
+    pub start_time: DateTime<Utc>,
+    /// Latest entry in the session
+    pub end_time: DateTime<Utc>,
+}
+
+impl SessionStats {
+    /// Key in `UsageStats::session_stats`; the same session id can show up under several projects
+    pub fn key(&self) -> String {
+        Self::key_for(&self.project_path, &self.session_id)
+    }
+
+    pub fn key_for(project_path: &str, session_id: &str) -> String {
+        format!("{}:{}", project_path, session_id)
+    }
+
+    /// Wall-clock span between the first and last request
+    pub fn duration(&self) -> chrono::Duration {
+        self.end_time - self.start_time
+    }
 }
 
 /// Daily usage for timeline
@@ -117,12 +312,531 @@ pub struct DailyUsage {
     pub models_used: Vec<String>,
 }
 
+/// Which token categories count toward "Total Tokens"
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TokenTotalPolicy {
+    IncludeCache,
+    ExcludeCache,
+}
+
+impl TokenTotalPolicy {
+    /// Combine per-category token counts into a headline total
+    pub fn total(&self, input: u64, output: u64, cache_read: u64, cache_creation: u64) -> u64 {
+        match self {
+            TokenTotalPolicy::IncludeCache => input + output + cache_read + cache_creation,
+            TokenTotalPolicy::ExcludeCache => input + output,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TokenTotalPolicy::IncludeCache => "Incl. Cache",
+            TokenTotalPolicy::ExcludeCache => "Excl. Cache",
+        }
+    }
+}
+
+/// Token categories tracked for every usage entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenCategory {
+    Input,
+    Output,
+    CacheRead,
+    CacheCreation,
+}
+
+impl TokenCategory {
+    pub fn all() -> [TokenCategory; 4] {
+        [
+            TokenCategory::Input,
+            TokenCategory::Output,
+            TokenCategory::CacheRead,
+            TokenCategory::CacheCreation,
+        ]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TokenCategory::Input => "Input Tokens",
+            TokenCategory::Output => "Output Tokens",
+            TokenCategory::CacheRead => "Cache Read",
+            TokenCategory::CacheCreation => "Cache Creation",
+        }
+    }
+
+    pub fn is_cache(&self) -> bool {
+        matches!(self, TokenCategory::CacheRead | TokenCategory::CacheCreation)
+    }
+}
+
+/// Options that control how usage entries are aggregated
+#[derive(Debug, Clone)]
+pub struct AggregationOptions {
+    pub token_total_policy: TokenTotalPolicy,
+    /// First day of the week for weekly grouping
+    pub week_start: Weekday,
+    /// Timezone used to decide which calendar day an entry belongs to (None = system local)
+    pub timezone: Option<Tz>,
+    /// Merge dated model versions (e.g. `claude-3-5-sonnet-20240620`) into one row per family
+    pub group_models_by_family: bool,
+    /// Single requests costing more than this (USD) are counted as expensive; None disables the count
+    pub request_cost_alert: Option<f64>,
+    /// Project paths left out of every total
+    pub excluded_projects: Vec<String>,
+    /// Model ids (or model families) left out of every total
+    pub excluded_models: Vec<String>,
+    /// Drop cache read/creation tokens and their cost from every total
+    pub ignore_cache: bool,
+    /// Cache writes were billed at the input rate while loading (see `ProcessingOptions`)
+    pub cache_writes_as_input: bool,
+    /// Collapse symlinked project directories onto their real path (touches the filesystem)
+    pub resolve_symlinks: bool,
+    /// Entries made during these local hours are left out of every total
+    pub quiet_hours: Option<QuietHours>,
+    /// Entries before this instant are left out of every total, whatever the time range
+    pub analysis_start: Option<DateTime<Utc>>,
+    /// Earlier rates, so cache and per-category costs match the ones computed while loading
+    pub pricing_history: Vec<HistoricalPricing>,
+}
+
+impl Default for AggregationOptions {
+    fn default() -> Self {
+        Self {
+            token_total_policy: TokenTotalPolicy::IncludeCache,
+            week_start: Weekday::Mon,
+            timezone: None,
+            group_models_by_family: false,
+            request_cost_alert: None,
+            excluded_projects: Vec::new(),
+            excluded_models: Vec::new(),
+            ignore_cache: false,
+            cache_writes_as_input: false,
+            resolve_symlinks: false,
+            quiet_hours: None,
+            analysis_start: None,
+            pricing_history: Vec::new(),
+        }
+    }
+}
+
+/// Local hours from `start_hour` up to (not including) `end_hour`; wraps past midnight
+/// when the start is later than the end (22 to 6 covers the night)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuietHours {
+    pub start_hour: u32,
+    pub end_hour: u32,
+}
+
+impl Default for QuietHours {
+    fn default() -> Self {
+        Self {
+            start_hour: 22,
+            end_hour: 6,
+        }
+    }
+}
+
+impl QuietHours {
+    /// Whether `hour` (0-23) falls in the window; equal start and end hours make an empty window
+    pub fn contains(&self, hour: u32) -> bool {
+        if self.start_hour <= self.end_hour {
+            self.start_hour <= hour && hour < self.end_hour
+        } else {
+            hour >= self.start_hour || hour < self.end_hour
+        }
+    }
+
+    /// Move the start and end by whole hours, wrapping around the clock
+    pub fn shifted(self, start_delta: i32, end_delta: i32) -> Self {
+        let shift = |hour: u32, delta: i32| (hour as i32 + delta).rem_euclid(24) as u32;
+        Self {
+            start_hour: shift(self.start_hour, start_delta),
+            end_hour: shift(self.end_hour, end_delta),
+        }
+    }
+
+    pub fn label(&self) -> String {
+        format!("{:02}:00–{:02}:00", self.start_hour, self.end_hour)
+    }
+}
+
+/// Options that control how usage files are discovered and parsed
+#[derive(Debug, Clone)]
+pub struct ProcessingOptions {
+    /// Maximum directory depth to descend below the data directory
+    pub max_depth: usize,
+    /// Directory names that are never descended into
+    pub ignored_dirs: Vec<String>,
+    /// Entries older than this many days are not loaded at all (None keeps everything)
+    pub max_history_days: Option<u32>,
+    /// What to do with entries dated too far in the future
+    pub future_entries: FutureEntryPolicy,
+    /// Top-level `type` values that count as billable; records of any other type are
+    /// skipped, records without a `type` are always kept, and an empty list keeps everything
+    pub billable_entry_types: Vec<String>,
+    /// Bill cache-creation tokens at the input rate for models without cache write pricing
+    pub cache_writes_as_input: bool,
+    /// Ignore logged `costUSD` values and report every entry at its computed cost
+    pub recompute_costs: bool,
+    /// Earlier rates used to compute costs for entries made while they applied
+    pub pricing_history: Vec<HistoricalPricing>,
+    /// How long to keep checking for a data directory that is momentarily missing
+    pub data_dir_retry: DataDirRetry,
+    /// Keep each entry's source line for inspection; costs memory, so off by default
+    pub capture_raw_lines: bool,
+    /// Most skipped lines to keep details for; later ones are only counted
+    pub max_parse_errors: usize,
+}
+
+impl Default for ProcessingOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: 5,
+            ignored_dirs: vec![
+                "node_modules".to_string(),
+                ".git".to_string(),
+                "target".to_string(),
+            ],
+            max_history_days: None,
+            future_entries: FutureEntryPolicy::Keep,
+            billable_entry_types: vec!["assistant".to_string()],
+            cache_writes_as_input: false,
+            recompute_costs: false,
+            pricing_history: Vec::new(),
+            data_dir_retry: DataDirRetry::default(),
+            capture_raw_lines: false,
+            max_parse_errors: MAX_PARSE_ERRORS,
+        }
+    }
+}
+
+/// Default cap on parse errors kept per load, so a pathological file can't grow the log without bound
+pub const MAX_PARSE_ERRORS: usize = 1000;
+
+/// A line, or a whole file, the processor had to skip
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub file: PathBuf,
+    /// 1-based line number; None when the whole file failed
+    pub line: Option<usize>,
+    pub reason: String,
+}
+
+/// Parse errors collected during one load, with a count of those past the cap
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParseErrorLog {
+    pub errors: Vec<ParseError>,
+    /// Errors that happened after `errors` was full
+    pub dropped: usize,
+}
+
+impl ParseErrorLog {
+    /// Keep the error if there is room under `limit`, otherwise just count it
+    pub fn record(&mut self, error: ParseError, limit: usize) {
+        if self.errors.len() < limit {
+            self.errors.push(error);
+        } else {
+            self.dropped += 1;
+        }
+    }
+
+    /// Every error seen, including the dropped ones
+    pub fn total(&self) -> usize {
+        self.errors.len() + self.dropped
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.total() == 0
+    }
+}
+
+/// Backoff for a data directory that is briefly missing, such as a network mount reconnecting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DataDirRetry {
+    /// Checks made after the first one; 0 gives up straight away
+    pub attempts: u32,
+    /// Wait before the first retry, doubled for each one after it
+    pub initial_delay_ms: u64,
+}
+
+impl Default for DataDirRetry {
+    fn default() -> Self {
+        Self {
+            attempts: 3,
+            initial_delay_ms: 250,
+        }
+    }
+}
+
+impl DataDirRetry {
+    /// Longest single wait, however many retries are configured
+    const MAX_DELAY_MS: u64 = 5_000;
+
+    /// Wait before retry number `attempt` (counting from 0)
+    pub fn delay(&self, attempt: u32) -> std::time::Duration {
+        let delay = self.initial_delay_ms.saturating_mul(1 << attempt.min(16));
+        std::time::Duration::from_millis(delay.min(Self::MAX_DELAY_MS))
+    }
+}
+
+/// Handling for entries whose timestamp is ahead of the clock (e.g. from clock skew)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FutureEntryPolicy {
+    /// Keep the entry as-is but flag it
+    Keep,
+    /// Move the timestamp back to the load time and flag it
+    Clamp,
+    /// Skip the entry entirely
+    Drop,
+}
+
+impl FutureEntryPolicy {
+    pub fn label(&self) -> &'static str {
+        match self {
+            FutureEntryPolicy::Keep => "Keep and flag",
+            FutureEntryPolicy::Clamp => "Clamp to now",
+            FutureEntryPolicy::Drop => "Drop",
+        }
+    }
+}
+
+/// Cost below which a model or project is folded into an "Other" row
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum CostThreshold {
+    /// Fixed amount in USD
+    Absolute(f64),
+    /// Percentage (0-100) of the breakdown's total
+    PercentOfTotal(f64),
+}
+
+impl CostThreshold {
+    /// Cost cutoff for a breakdown summing to `total`
+    pub fn cutoff(&self, total: f64) -> f64 {
+        match self {
+            CostThreshold::Absolute(amount) => *amount,
+            CostThreshold::PercentOfTotal(percent) => total * percent / 100.0,
+        }
+    }
+
+    pub fn is_disabled(&self) -> bool {
+        match self {
+            CostThreshold::Absolute(value) | CostThreshold::PercentOfTotal(value) => *value <= 0.0,
+        }
+    }
+}
+
+/// Sort order options for the project breakdown
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProjectSort {
+    TotalCost,
+    CostPerRequest,
+}
+
+impl ProjectSort {
+    pub const ALL: [ProjectSort; 2] = [ProjectSort::TotalCost, ProjectSort::CostPerRequest];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ProjectSort::TotalCost => "Total Cost",
+            ProjectSort::CostPerRequest => "Cost/Request",
+        }
+    }
+}
+
+/// Columns the Sessions table can be sorted by
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SessionSortColumn {
+    Project,
+    Date,
+    Cost,
+    Tokens,
+    Requests,
+}
+
+impl SessionSortColumn {
+    pub const ALL: [SessionSortColumn; 5] = [
+        SessionSortColumn::Project,
+        SessionSortColumn::Date,
+        SessionSortColumn::Cost,
+        SessionSortColumn::Tokens,
+        SessionSortColumn::Requests,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SessionSortColumn::Project => "Project",
+            SessionSortColumn::Date => "Date",
+            SessionSortColumn::Cost => "Cost",
+            SessionSortColumn::Tokens => "Tokens",
+            SessionSortColumn::Requests => "Requests",
+        }
+    }
+
+    /// Names read best A-Z; everything else biggest or newest first
+    pub fn default_descending(&self) -> bool {
+        !matches!(self, SessionSortColumn::Project)
+    }
+}
+
+/// Sort applied to the Sessions table
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SessionSort {
+    pub column: SessionSortColumn,
+    pub descending: bool,
+}
+
+impl Default for SessionSort {
+    fn default() -> Self {
+        Self {
+            column: SessionSortColumn::Date,
+            descending: true,
+        }
+    }
+}
+
+impl SessionSort {
+    /// Clicking the sorted column flips its direction; another column starts at its default
+    pub fn toggled(self, column: SessionSortColumn) -> Self {
+        if self.column == column {
+            Self {
+                descending: !self.descending,
+                ..self
+            }
+        } else {
+            Self {
+                column,
+                descending: column.default_descending(),
+            }
+        }
+    }
+}
+
+/// Span of time from `start` up to (not including) `end`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Period {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl Period {
+    /// The `days` days leading up to `end`
+    pub fn days_ending(end: DateTime<Utc>, days: i64) -> Self {
+        Self {
+            start: end - chrono::Duration::days(days),
+            end,
+        }
+    }
+
+    /// Period of the same length immediately before this one
+    pub fn previous(&self) -> Self {
+        Self {
+            start: self.start - (self.end - self.start),
+            end: self.start,
+        }
+    }
+
+    pub fn contains(&self, timestamp: &DateTime<Utc>) -> bool {
+        self.start <= *timestamp && *timestamp < self.end
+    }
+}
+
+/// How one project or model changed between two periods
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntityDelta {
+    /// Project path or model id
+    pub key: String,
+    pub name: String,
+    pub cost_a: f64,
+    pub cost_b: f64,
+    pub requests_a: usize,
+    pub requests_b: usize,
+}
+
+impl EntityDelta {
+    pub fn cost_delta(&self) -> f64 {
+        self.cost_b - self.cost_a
+    }
+
+    /// Only used in the second period
+    pub fn is_added(&self) -> bool {
+        self.requests_a == 0 && self.requests_b > 0
+    }
+
+    /// Only used in the first period
+    pub fn is_removed(&self) -> bool {
+        self.requests_a > 0 && self.requests_b == 0
+    }
+}
+
+/// Yearly cost extrapolated from the recent daily average; an estimate, not a forecast
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RunRate {
+    /// Average spend per calendar day over the days used
+    pub daily_average: f64,
+    /// The daily average over a 365-day year
+    pub annual_cost: f64,
+    /// Days averaged over: the requested window, or fewer when the history is shorter
+    pub days_used: i64,
+}
+
+/// Changes from period A to period B; deltas are B minus A, largest change first
+#[derive(Debug, Clone, PartialEq)]
+pub struct PeriodDiff {
+    pub total_cost_a: f64,
+    pub total_cost_b: f64,
+    pub projects: Vec<EntityDelta>,
+    pub models: Vec<EntityDelta>,
+}
+
+impl PeriodDiff {
+    pub fn cost_delta(&self) -> f64 {
+        self.total_cost_b - self.total_cost_a
+    }
+
+    /// Relative change in total cost; None when period A cost nothing
+    pub fn cost_change_ratio(&self) -> Option<f64> {
+        (self.total_cost_a > 0.0).then(|| self.cost_delta() / self.total_cost_a)
+    }
+}
+
+/// Weekly usage rollup for the timeline
+#[derive(Debug, Clone)]
+pub struct WeeklyUsage {
+    pub week_start: String,
+    pub total_cost: f64,
+    pub total_tokens: u64,
+    pub request_count: usize,
+    pub days_count: usize,
+}
+
+/// Period size used to group the timeline chart
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TimelineGranularity {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+impl TimelineGranularity {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TimelineGranularity::Daily => "Daily",
+            TimelineGranularity::Weekly => "Weekly",
+            TimelineGranularity::Monthly => "Monthly",
+        }
+    }
+}
+
 /// Time range filter options
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum TimeRange {
     AllTime,
     Last7Days,
     Last30Days,
+    /// Rolling window ending now, unlike the calendar-day `Today`
+    Last24Hours,
+    Today,
+    Yesterday,
 }
 
 impl TimeRange {
@@ -132,6 +846,9 @@ impl TimeRange {
             TimeRange::AllTime => "All Time",
             TimeRange::Last7Days => "7 Days",
             TimeRange::Last30Days => "30 Days",
+            TimeRange::Last24Hours => "24 Hours",
+            TimeRange::Today => "Today",
+            TimeRange::Yesterday => "Yesterday",
         }
     }
 }
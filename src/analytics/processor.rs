@@ -1,44 +1,200 @@
 use anyhow::{Result, Context};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use serde_json::Value;
-use std::collections::HashSet;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
 use std::fs;
+use std::panic::{self, AssertUnwindSafe};
 use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
+use log::{debug, info, warn};
 
-use super::models::UsageEntry;
+use super::models::{
+    FutureEntryPolicy, ParseError, ParseErrorLog, ProcessingOptions, UsageEntry, FUTURE_ENTRY_TOLERANCE_MINUTES,
+};
 use super::calculator::CostCalculator;
+use crate::utils::system;
+
+/// Known shapes of a usage record, tried in this order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum LogSchema {
+    /// Flat `UsageEntry` lines written by this dashboard's JSONL export
+    Archived,
+    /// Claude Code: `usage`, `model` and `id` nested under `message` (or its content blocks)
+    ClaudeCode,
+    /// Claude Desktop: `usage`, `model` and `id` at the top level with no `message` envelope
+    ClaudeDesktop,
+}
+
+impl LogSchema {
+    pub fn label(&self) -> &'static str {
+        match self {
+            LogSchema::Archived => "Dashboard export",
+            LogSchema::ClaudeCode => "Claude Code",
+            LogSchema::ClaudeDesktop => "Claude Desktop",
+        }
+    }
+}
 
 /// Processes JSONL files from usage data
 pub struct UsageProcessor {
     data_dir: PathBuf,
     cost_calculator: CostCalculator,
+    options: ProcessingOptions,
+    /// Entries before this instant are skipped (derived from `max_history_days`)
+    history_cutoff: Option<DateTime<Utc>>,
+    /// How many loaded entries matched each schema, for diagnostics
+    schema_counts: RefCell<HashMap<LogSchema, usize>>,
+    /// Logged costs that were NaN, infinite or negative and had to be replaced
+    invalid_costs: Cell<usize>,
+    /// Lines that only parsed after stripping a BOM or trailing commas
+    repaired_lines: Cell<usize>,
+    /// Lines and files that had to be skipped, for the parse errors log
+    parse_errors: RefCell<ParseErrorLog>,
 }
 
 impl UsageProcessor {
-    /// Create a new processor instance
-    pub fn new() -> Result<Self> {
-        let home_dir = dirs::home_dir().context("Could not find home directory")?;
-        let data_dir = home_dir.join(".claude").join("projects");
-        
-        if !data_dir.exists() {
-            return Err(anyhow::anyhow!("Data directory not found at ~/.claude/projects"));
-        }
-        
-        Ok(Self {
+    /// Location of Claude Code's usage logs (~/.claude/projects), whether or not it exists
+    pub fn default_data_dir() -> PathBuf {
+        system::home_dir().join(".claude").join("projects")
+    }
+
+    /// Create a processor for ~/.claude/projects that applies the given options.
+    /// A missing directory is not an error; check `has_data_dir` before relying on the results.
+    pub fn with_options(options: ProcessingOptions) -> Self {
+        Self::with_data_dir(Self::default_data_dir(), options)
+    }
+
+    /// Create a processor for an arbitrary data directory
+    pub fn with_data_dir(data_dir: PathBuf, options: ProcessingOptions) -> Self {
+        let history_cutoff = options
+            .max_history_days
+            .map(|days| Utc::now() - Duration::days(days as i64));
+        Self {
             data_dir,
-            cost_calculator: CostCalculator::new(),
-        })
+            cost_calculator: CostCalculator::with_cache_write_fallback(options.cache_writes_as_input)
+                .with_pricing_history(options.pricing_history.clone()),
+            options,
+            history_cutoff,
+            schema_counts: RefCell::new(HashMap::new()),
+            invalid_costs: Cell::new(0),
+            repaired_lines: Cell::new(0),
+            parse_errors: RefCell::new(ParseErrorLog::default()),
+        }
+    }
+
+    /// Number of entries loaded so far per record schema
+    pub fn schema_counts(&self) -> HashMap<LogSchema, usize> {
+        self.schema_counts.borrow().clone()
+    }
+
+    fn record_schema(&self, schema: LogSchema) {
+        *self.schema_counts.borrow_mut().entry(schema).or_insert(0) += 1;
+    }
+
+    /// Number of logged costs replaced because they were NaN, infinite or negative
+    pub fn invalid_cost_count(&self) -> usize {
+        self.invalid_costs.get()
+    }
+
+    /// Keep bad logged costs out of totals: NaN and infinities fall back to the computed
+    /// cost and negatives become zero
+    fn sanitize_cost(&self, cost: f64, computed_cost: f64) -> f64 {
+        if cost.is_finite() && cost >= 0.0 {
+            return cost;
+        }
+        self.invalid_costs.set(self.invalid_costs.get() + 1);
+        if cost.is_finite() {
+            0.0
+        } else {
+            computed_cost
+        }
+    }
+
+    /// Number of lines that needed a BOM or trailing comma removed before parsing
+    pub fn repaired_line_count(&self) -> usize {
+        self.repaired_lines.get()
+    }
+
+    /// Lines and files skipped so far, up to `max_parse_errors` of them in detail
+    pub fn parse_errors(&self) -> ParseErrorLog {
+        self.parse_errors.borrow().clone()
+    }
+
+    fn record_parse_error(&self, file: &Path, line: Option<usize>, reason: String) {
+        let error = ParseError { file: file.to_path_buf(), line, reason };
+        self.parse_errors.borrow_mut().record(error, self.options.max_parse_errors);
+    }
+
+    /// Drop trailing commas (and the whitespace around them) that hand-edited or
+    /// exported logs sometimes leave after each object
+    fn repair_line<'a>(&self, line: &'a str) -> &'a str {
+        let trimmed = line.trim_end();
+        let repaired = trimmed.trim_end_matches(|c: char| c == ',' || c.is_whitespace());
+        if repaired.len() != trimmed.len() {
+            debug!("🔧 Stripped trailing comma from line");
+            self.repaired_lines.set(self.repaired_lines.get() + 1);
+        }
+        repaired
+    }
+
+    /// Whether the data directory exists
+    pub fn has_data_dir(&self) -> bool {
+        self.data_dir.is_dir()
+    }
+
+    /// Give a missing data directory a few chances to appear (a network mount reconnecting,
+    /// say), sleeping with backoff between checks. Returns whether it is there now.
+    pub fn wait_for_data_dir(&self) -> bool {
+        self.wait_for_data_dir_with(std::thread::sleep)
+    }
+
+    fn wait_for_data_dir_with(&self, mut sleep: impl FnMut(std::time::Duration)) -> bool {
+        let retry = self.options.data_dir_retry;
+        for attempt in 0..retry.attempts {
+            if self.has_data_dir() {
+                return true;
+            }
+            let delay = retry.delay(attempt);
+            info!("⏳ Data directory {:?} unavailable, retrying in {:?}", self.data_dir, delay);
+            sleep(delay);
+        }
+        self.has_data_dir()
+    }
+
+    /// Directory the processor reads from
+    pub fn data_dir(&self) -> &Path {
+        &self.data_dir
     }
 
     // Removed unused new_fallback method during cleanup
 
-    /// Find all JSONL files in the data directory
+    /// Find all JSONL files in the data directory (none when it doesn't exist)
     pub fn find_jsonl_files(&self) -> Result<Vec<PathBuf>> {
         let mut files = Vec::new();
+        if !self.has_data_dir() {
+            info!("📂 No usage data directory at {:?}", self.data_dir);
+            return Ok(files);
+        }
+        
+        // Symlinks are not followed, so link loops can't trap the walk
+        let walker = WalkDir::new(&self.data_dir)
+            .max_depth(self.options.max_depth)
+            .follow_links(false)
+            .into_iter()
+            .filter_entry(|entry| !self.is_ignored_dir(entry));
         
-        for entry in WalkDir::new(&self.data_dir) {
-            let entry = entry?;
+        for entry in walker {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => {
+                    debug!("  Warning: Skipping unreadable path: {}", e);
+                    continue;
+                }
+            };
+            if !entry.file_type().is_file() {
+                continue;
+            }
             if let Some(extension) = entry.path().extension() {
                 if extension == "jsonl" {
                     files.push(entry.path().to_path_buf());
@@ -59,34 +215,82 @@ impl UsageProcessor {
         Ok(files)
     }
 
+    /// Check whether a directory matches the ignore list
+    fn is_ignored_dir(&self, entry: &walkdir::DirEntry) -> bool {
+        entry.depth() > 0
+            && entry.file_type().is_dir()
+            && entry
+                .file_name()
+                .to_str()
+                .map(|name| self.options.ignored_dirs.iter().any(|ignored| ignored == name))
+                .unwrap_or(false)
+    }
+
     /// Process all JSONL files and return usage entries
     pub fn process_all_files(&self) -> Result<Vec<UsageEntry>> {
         let files = self.find_jsonl_files()?;
-        println!("📁 Found {} JSONL files to process", files.len());
-        
+        info!("📁 Found {} JSONL files to process", files.len());
+        Ok(self.process_files_with(&files, |file_path, dedup| self.process_file(file_path, dedup)))
+    }
+
+    /// Run `process` over each file, skipping (and recording) any file it fails or panics on
+    fn process_files_with(
+        &self,
+        files: &[PathBuf],
+        mut process: impl FnMut(&Path, &mut HashSet<String>) -> Result<Vec<UsageEntry>>,
+    ) -> Vec<UsageEntry> {
+
         let mut all_entries = Vec::new();
         let mut global_deduplication = HashSet::new();
         
         for (i, file_path) in files.iter().enumerate() {
-            println!("📄 Processing file {}/{}: {:?}", i + 1, files.len(), file_path);
+            debug!("📄 Processing file {}/{}: {:?}", i + 1, files.len(), file_path);
             
-            match self.process_file(file_path, &mut global_deduplication) {
-                Ok(entries) => {
-                    println!("  ✅ Processed {} entries", entries.len());
+            // A panic inside one file is contained so the rest still load; dedup keys
+            // added before the panic only ever suppress entries, never corrupt them
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                process(file_path, &mut global_deduplication)
+            }));
+            match result {
+                Ok(Ok(entries)) => {
+                    debug!("  ✅ Processed {} entries", entries.len());
                     all_entries.extend(entries);
                 }
-                Err(e) => {
-                    eprintln!("  ❌ Error processing file: {}", e);
+                Ok(Err(e)) => {
+                    warn!("  ❌ Error processing file: {}", e);
+                    self.record_parse_error(file_path, None, format!("{:#}", e));
+                    continue;
+                }
+                Err(_) => {
+                    warn!("  ❌ Skipping file after unexpected panic: {:?}", file_path);
+                    self.record_parse_error(file_path, None, "Unexpected panic while processing".to_string());
                     continue;
                 }
             }
         }
         
+        self.apply_future_entry_policy(&mut all_entries, Utc::now());
+        
         // Sort by timestamp (newest first)
         all_entries.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
         
-        println!("✅ Total entries processed: {}", all_entries.len());
-        Ok(all_entries)
+        info!("✅ Total entries processed: {}", all_entries.len());
+        let mut schemas: Vec<(LogSchema, usize)> = self.schema_counts().into_iter().collect();
+        schemas.sort();
+        for (schema, count) in schemas {
+            info!("🧩 {} entries in {} format", count, schema.label());
+        }
+        if self.invalid_cost_count() > 0 {
+            warn!("⚠️ Replaced {} invalid (NaN, infinite or negative) logged costs", self.invalid_cost_count());
+        }
+        if self.repaired_line_count() > 0 {
+            info!("🔧 Repaired {} lines with a UTF-8 BOM or trailing commas", self.repaired_line_count());
+        }
+        let skipped = self.parse_errors.borrow().total();
+        if skipped > 0 {
+            warn!("⚠️ Skipped {} unparseable lines or files", skipped);
+        }
+        all_entries
     }
 
     // Removed unused process_recent_files method during cleanup
@@ -95,6 +299,15 @@ impl UsageProcessor {
     pub fn process_file(&self, file_path: &Path, global_dedup: &mut HashSet<String>) -> Result<Vec<UsageEntry>> {
         let content = fs::read_to_string(file_path)
             .with_context(|| format!("Failed to read file: {:?}", file_path))?;
+        // A UTF-8 BOM would otherwise make the first line invalid JSON
+        let content = match content.strip_prefix('\u{feff}') {
+            Some(rest) => {
+                debug!("🔧 Stripped UTF-8 BOM from {:?}", file_path);
+                self.repaired_lines.set(self.repaired_lines.get() + 1);
+                rest
+            }
+            None => content.as_str(),
+        };
         
         let mut entries = Vec::new();
         let mut local_dedup = HashSet::new();
@@ -112,10 +325,16 @@ impl UsageProcessor {
             }
             
             match self.process_line(line, &session_id, &mut local_dedup, global_dedup) {
-                Ok(Some(entry)) => entries.push(entry),
+                Ok(Some(mut entry)) => {
+                    if self.options.capture_raw_lines {
+                        entry.raw_line = Some(line.to_string());
+                    }
+                    entries.push(entry);
+                }
                 Ok(None) => continue, // Filtered out or duplicate
                 Err(e) => {
-                    eprintln!("  Warning: Line {}: {}", line_num + 1, e);
+                    debug!("  Warning: Line {}: {}", line_num + 1, e);
+                    self.record_parse_error(file_path, Some(line_num + 1), format!("{:#}", e));
                     continue;
                 }
             }
@@ -124,6 +343,36 @@ impl UsageProcessor {
         Ok(entries)
     }
 
+    /// Object holding the `usage` field: normally `message` itself, but some entries
+    /// carry `message` as an array of content blocks, in which case the blocks and
+    /// then the top-level object are searched instead
+    fn usage_source<'a>(json_value: &'a Value, message: &'a Value) -> Option<&'a Value> {
+        let has_usage = |value: &Value| value.get("usage").is_some_and(|usage| !usage.is_null());
+
+        match message {
+            Value::Array(blocks) => blocks
+                .iter()
+                .find(|block| has_usage(block))
+                .or_else(|| Some(json_value).filter(|value| has_usage(value))),
+            _ => Some(message).filter(|value| has_usage(value)),
+        }
+    }
+
+    /// Identify a record's schema and the object holding its `usage`, trying each known shape in order
+    fn detect_schema(json_value: &Value) -> Option<(LogSchema, &Value)> {
+        match json_value.get("message") {
+            Some(message) => Self::usage_source(json_value, message).map(|source| (LogSchema::ClaudeCode, source)),
+            None => Some(json_value)
+                .filter(|value| value.get("usage").is_some_and(|usage| usage.is_object()))
+                .map(|value| (LogSchema::ClaudeDesktop, value)),
+        }
+    }
+
+    /// First of the given usage fields that holds a count
+    fn token_count(usage: &Value, names: &[&str]) -> u64 {
+        names.iter().find_map(|name| usage.get(*name).and_then(|v| v.as_u64())).unwrap_or(0)
+    }
+
     /// Process a single line from a JSONL file
     fn process_line(
         &self, 
@@ -132,22 +381,49 @@ impl UsageProcessor {
         local_dedup: &mut HashSet<String>,
         global_dedup: &mut HashSet<String>
     ) -> Result<Option<UsageEntry>> {
+        let line = self.repair_line(line);
+
+        // Skip old entries before paying for a full JSON parse
+        if let Some(timestamp) = Self::peek_timestamp(line) {
+            if self.is_before_history_cutoff(&timestamp) {
+                return Ok(None);
+            }
+        }
+        
         let json_value: Value = serde_json::from_str(line)
             .context("Failed to parse JSON")?;
         
+        // Lines re-exported by this dashboard are already normalized entries
+        if Self::is_archived_entry(&json_value) {
+            let entry = self.process_archived_entry(json_value, local_dedup, global_dedup)?;
+            if entry.is_some() {
+                self.record_schema(LogSchema::Archived);
+            }
+            return Ok(entry);
+        }
+        
+        // User, system and tool-result records can carry usage too; only count billable types
+        if !self.is_billable_type(&json_value) {
+            return Ok(None);
+        }
+        
         // Extract basic information
         let timestamp = self.extract_timestamp(&json_value)?;
-        let message = json_value.get("message")
-            .context("Missing message field")?;
+        if self.is_before_history_cutoff(&timestamp) {
+            return Ok(None);
+        }
         
-        // Check if this entry has usage data
-        let usage = match message.get("usage") {
-            Some(usage_value) if !usage_value.is_null() => usage_value,
-            _ => return Ok(None), // No valid usage data
+        // Check if this entry has usage data, in any of the known shapes
+        let (schema, message) = match Self::detect_schema(&json_value) {
+            Some(detected) => detected,
+            None => return Ok(None), // No valid usage data
         };
+        let usage = &message["usage"];
         
         // Extract identifiers for deduplication
-        let message_id = message.get("id").and_then(|v| v.as_str());
+        let message_id = message.get("id")
+            .or_else(|| json_value.get("id"))
+            .and_then(|v| v.as_str());
         let request_id = json_value.get("requestId").and_then(|v| v.as_str());
         
         // Create deduplication key
@@ -164,10 +440,11 @@ impl UsageProcessor {
         }
         
         // Extract token counts
-        let input_tokens = usage.get("input_tokens").and_then(|v| v.as_u64()).unwrap_or(0) as u32;
-        let output_tokens = usage.get("output_tokens").and_then(|v| v.as_u64()).unwrap_or(0) as u32;
-        let cache_read_tokens = usage.get("cache_read_input_tokens").and_then(|v| v.as_u64()).unwrap_or(0) as u32;
-        let cache_creation_tokens = usage.get("cache_creation_input_tokens").and_then(|v| v.as_u64()).unwrap_or(0) as u32;
+        let input_tokens = Self::token_count(usage, &["input_tokens"]);
+        let output_tokens = Self::token_count(usage, &["output_tokens"]);
+        let cache_read_tokens = Self::token_count(usage, &["cache_read_input_tokens", "cache_read_tokens"]);
+        let cache_creation_tokens =
+            Self::token_count(usage, &["cache_creation_input_tokens", "cache_creation_tokens"]);
         
         // Filter out zero-token entries (like Claudia does)
         if input_tokens == 0 && output_tokens == 0 && cache_read_tokens == 0 && cache_creation_tokens == 0 {
@@ -176,6 +453,7 @@ impl UsageProcessor {
         
         // Extract model and project information
         let model = message.get("model")
+            .or_else(|| json_value.get("model"))
             .and_then(|v| v.as_str())
             .unwrap_or("unknown")
             .to_string();
@@ -184,13 +462,13 @@ impl UsageProcessor {
             .and_then(|v| v.as_str())
             .map(|s| s.to_string());
         
-        // Calculate cost (use provided cost or calculate it)
-        let cost = json_value.get("costUSD")
+        // Calculate cost (use provided cost unless recomputing, but keep both for auditing)
+        let computed_cost = self.cost_calculator.calculate_cost_at(&model, &timestamp, input_tokens, output_tokens, cache_read_tokens, cache_creation_tokens);
+        let logged_cost = json_value.get("costUSD")
             .and_then(|v| v.as_f64())
-            .unwrap_or_else(|| {
-                self.cost_calculator.calculate_cost(&model, input_tokens, output_tokens, cache_read_tokens, cache_creation_tokens)
-            });
+            .map(|cost| self.sanitize_cost(cost, computed_cost));
         
+        self.record_schema(schema);
         Ok(Some(UsageEntry {
             timestamp,
             model,
@@ -201,8 +479,111 @@ impl UsageProcessor {
             output_tokens,
             cache_read_tokens,
             cache_creation_tokens,
-            cost,
-        }))
+            cost: computed_cost,
+            computed_cost,
+            logged_cost,
+            future_dated: false,
+            raw_line: None,
+        }
+        .with_cost_source(self.options.recompute_costs)))
+    }
+
+    /// Whether the record's top-level `type` is one we count (records without a `type` always are)
+    fn is_billable_type(&self, json_value: &Value) -> bool {
+        let billable = &self.options.billable_entry_types;
+        match json_value.get("type").and_then(|v| v.as_str()) {
+            Some(entry_type) => billable.is_empty() || billable.iter().any(|t| t == entry_type),
+            None => true,
+        }
+    }
+
+    /// Check whether a line uses the flat `UsageEntry` shape written by the JSONL export
+    fn is_archived_entry(json_value: &Value) -> bool {
+        json_value.get("message").is_none() && json_value.get("input_tokens").is_some()
+    }
+
+    /// Parse a line from a merged JSONL export back into a usage entry
+    fn process_archived_entry(
+        &self,
+        json_value: Value,
+        local_dedup: &mut HashSet<String>,
+        global_dedup: &mut HashSet<String>
+    ) -> Result<Option<UsageEntry>> {
+        let has_computed_cost = json_value.get("computed_cost").is_some();
+        let mut entry: UsageEntry = serde_json::from_value(json_value)
+            .context("Failed to parse archived entry")?;
+        if self.is_before_history_cutoff(&entry.timestamp) {
+            return Ok(None);
+        }
+        
+        // Exports written before computed costs were tracked lack the field
+        if !has_computed_cost {
+            entry.computed_cost = self.cost_calculator.calculate_cost_at(
+                &entry.model,
+                &entry.timestamp,
+                entry.input_tokens,
+                entry.output_tokens,
+                entry.cache_read_tokens,
+                entry.cache_creation_tokens,
+            );
+        }
+        entry.cost = self.sanitize_cost(entry.cost, entry.computed_cost);
+        // Older exports lack `logged_cost`; a cost that differs from the computed one was logged
+        if entry.logged_cost.is_none() && entry.cost != entry.computed_cost {
+            entry.logged_cost = Some(entry.cost);
+        }
+        let entry = entry.with_cost_source(self.options.recompute_costs);
+        
+        // Archived entries no longer carry a message id, so key on request id and time
+        if let Some(ref req_id) = entry.request_id {
+            let dedup_key = format!("archive:{}:{}", req_id, entry.timestamp.to_rfc3339());
+            if local_dedup.contains(&dedup_key) || global_dedup.contains(&dedup_key) {
+                return Ok(None); // Duplicate
+            }
+            local_dedup.insert(dedup_key.clone());
+            global_dedup.insert(dedup_key);
+        }
+        
+        Ok(Some(entry))
+    }
+
+    /// Whether a timestamp falls outside the configured history window
+    fn is_before_history_cutoff(&self, timestamp: &DateTime<Utc>) -> bool {
+        self.history_cutoff.is_some_and(|cutoff| *timestamp < cutoff)
+    }
+
+    /// Cheaply read the top-level timestamp from a raw line without parsing the JSON
+    fn peek_timestamp(line: &str) -> Option<DateTime<Utc>> {
+        const KEY: &str = "\"timestamp\":\"";
+        let start = line.find(KEY)? + KEY.len();
+        let end = start + line[start..].find('"')?;
+        DateTime::parse_from_rfc3339(&line[start..end])
+            .ok()
+            .map(|dt| dt.with_timezone(&Utc))
+    }
+
+    /// Flag, clamp or drop entries dated further ahead of `now` than the tolerance
+    fn apply_future_entry_policy(&self, entries: &mut Vec<UsageEntry>, now: DateTime<Utc>) {
+        let limit = now + Duration::minutes(FUTURE_ENTRY_TOLERANCE_MINUTES);
+        let policy = self.options.future_entries;
+        
+        let affected = entries.iter().filter(|e| e.timestamp > limit).count();
+        if affected == 0 {
+            return;
+        }
+        info!("⏰ {} future-dated entries ({})", affected, policy.label());
+        
+        match policy {
+            FutureEntryPolicy::Drop => entries.retain(|e| e.timestamp <= limit),
+            FutureEntryPolicy::Keep | FutureEntryPolicy::Clamp => {
+                for entry in entries.iter_mut().filter(|e| e.timestamp > limit) {
+                    entry.future_dated = true;
+                    if policy == FutureEntryPolicy::Clamp {
+                        entry.timestamp = now;
+                    }
+                }
+            }
+        }
     }
 
     /// Extract timestamp from JSON value
@@ -215,4 +596,471 @@ impl UsageProcessor {
             .map(|dt| dt.with_timezone(&Utc))
             .with_context(|| format!("Failed to parse timestamp: {}", timestamp_str))
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Data directory unique to one test, removed on drop so a failed assertion doesn't leak it
+    struct ScratchDir(PathBuf);
+
+    impl ScratchDir {
+        /// Empty data directory
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("usage-dashboard-{}-{}", name, std::process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(&dir).unwrap();
+            Self(dir)
+        }
+
+        /// Data directory holding a single `project/session.jsonl`
+        fn with_session(name: &str, contents: impl AsRef<[u8]>) -> Self {
+            let scratch = Self::new(name);
+            scratch.write("project/session.jsonl", contents);
+            scratch
+        }
+
+        /// Write a file below the directory, creating its parents, and return its path
+        fn write(&self, relative: &str, contents: impl AsRef<[u8]>) -> PathBuf {
+            let path = self.0.join(relative);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, contents).unwrap();
+            path
+        }
+
+        fn path(&self) -> &Path {
+            &self.0
+        }
+
+        fn processor(&self, options: ProcessingOptions) -> UsageProcessor {
+            UsageProcessor::with_data_dir(self.0.clone(), options)
+        }
+
+        /// Every entry in the directory, loaded with `options`
+        fn load(&self, options: ProcessingOptions) -> Vec<UsageEntry> {
+            self.processor(options).process_all_files().unwrap()
+        }
+    }
+
+    impl Drop for ScratchDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    const SAMPLE_LINE: &str = r#"{"timestamp":"2025-07-01T12:00:00Z","requestId":"req_1","cwd":"/Users/dev/app","message":{"id":"msg_1","model":"claude-sonnet-4-20250514","usage":{"input_tokens":100,"output_tokens":50}}}"#;
+
+    #[test]
+    fn test_missing_data_dir_yields_no_entries() {
+        // e.g. a CI box whose fallback home has never run Claude Code
+        let missing = std::env::temp_dir().join(format!("usage-dashboard-no-home-{}", std::process::id())).join("projects");
+        let processor = UsageProcessor::with_data_dir(missing, ProcessingOptions::default());
+
+        assert!(!processor.has_data_dir());
+        assert!(processor.process_all_files().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_data_dir_that_reappears_after_one_retry() {
+        let root = ScratchDir::new("flaky-mount");
+        let mount = root.path().join("projects");
+        let processor = UsageProcessor::with_data_dir(mount.clone(), ProcessingOptions::default());
+
+        // The "mount" comes back while the processor waits out its first backoff
+        let mut waits = Vec::new();
+        let found = processor.wait_for_data_dir_with(|delay| {
+            waits.push(delay);
+            fs::create_dir_all(&mount).unwrap();
+        });
+        assert!(found);
+        assert_eq!(waits, vec![std::time::Duration::from_millis(250)]);
+
+        // Still gone after every retry: give up once the backoff is spent
+        let gone = UsageProcessor::with_data_dir(root.path().join("never-mounted"), ProcessingOptions::default());
+        let mut waits = Vec::new();
+        assert!(!gone.wait_for_data_dir_with(|delay| waits.push(delay.as_millis())));
+        assert_eq!(waits, vec![250, 500, 1000]);
+    }
+
+    #[test]
+    fn test_find_jsonl_files_skips_ignored_and_deep_dirs() {
+        let root = ScratchDir::with_session("walk", SAMPLE_LINE);
+
+        // Decoys: an ignored directory and a directory nested beyond the depth limit
+        root.write("project/node_modules/decoy.jsonl", SAMPLE_LINE);
+        root.write("a/b/c/d/e/f/deep.jsonl", SAMPLE_LINE);
+
+        let files = root.processor(ProcessingOptions::default()).find_jsonl_files().unwrap();
+
+        assert_eq!(files, vec![root.path().join("project").join("session.jsonl")]);
+    }
+
+    #[test]
+    fn test_code_and_desktop_schemas_parse_alike() {
+        const CLAUDE_CODE_LINE: &str = r#"{"type":"assistant","timestamp":"2025-07-01T12:00:00Z","requestId":"req_1","cwd":"/Users/dev/app","message":{"id":"msg_1","model":"claude-sonnet-4-20250514","usage":{"input_tokens":100,"output_tokens":50,"cache_read_input_tokens":400,"cache_creation_input_tokens":20}}}"#;
+        const CLAUDE_DESKTOP_LINE: &str = r#"{"type":"assistant","timestamp":"2025-07-01T12:00:00Z","requestId":"req_1","cwd":"/Users/dev/app","id":"msg_1","model":"claude-sonnet-4-20250514","usage":{"input_tokens":100,"output_tokens":50,"cache_read_tokens":400,"cache_creation_tokens":20}}"#;
+
+        let load = |name: &str, line: &str| {
+            let root = ScratchDir::with_session(name, line);
+            let processor = root.processor(ProcessingOptions::default());
+            let entries = processor.process_all_files().unwrap();
+            (entries, processor.schema_counts())
+        };
+
+        let (code_entries, code_schemas) = load("schema-code", CLAUDE_CODE_LINE);
+        let (desktop_entries, desktop_schemas) = load("schema-desktop", CLAUDE_DESKTOP_LINE);
+
+        assert_eq!(code_entries.len(), 1);
+        assert_eq!((code_entries[0].cache_read_tokens, code_entries[0].cache_creation_tokens), (400, 20));
+        assert_eq!(code_entries, desktop_entries);
+        assert_eq!(code_schemas, HashMap::from([(LogSchema::ClaudeCode, 1)]));
+        assert_eq!(desktop_schemas, HashMap::from([(LogSchema::ClaudeDesktop, 1)]));
+    }
+
+    #[test]
+    fn test_merged_jsonl_round_trip() {
+        use crate::analytics::export::export_merged_jsonl;
+
+        let second_line = SAMPLE_LINE
+            .replace("req_1", "req_2")
+            .replace("msg_1", "msg_2")
+            .replace("12:00:00Z", "13:30:00Z");
+        let root = ScratchDir::with_session("jsonl", format!("{}\n{}\n{}\n", SAMPLE_LINE, second_line, SAMPLE_LINE));
+
+        let mut original = root.load(ProcessingOptions::default());
+        assert_eq!(original.len(), 2);
+
+        // Re-import the merged export from a fresh directory
+        let archive_root = ScratchDir::new("jsonl-archive");
+        archive_root.write("merged.jsonl", export_merged_jsonl(&original));
+        let mut reimported = archive_root.load(ProcessingOptions::default());
+
+        original.sort_by_key(|e| e.timestamp);
+        reimported.sort_by_key(|e| e.timestamp);
+        assert_eq!(original, reimported);
+    }
+
+    #[test]
+    fn test_recompute_costs_ignores_logged_cost() {
+        let marked_up = SAMPLE_LINE.replace(r#""cwd""#, r#""costUSD":1.0,"cwd""#);
+        let unlogged = SAMPLE_LINE.replace("req_1", "req_2").replace("msg_1", "msg_2");
+        let root = ScratchDir::with_session("recompute", format!("{}\n{}\n", marked_up, unlogged));
+        let computed = CostCalculator::new().calculate_cost("claude-sonnet-4-20250514", 100, 50, 0, 0);
+
+        let load = |recompute_costs: bool| root.load(ProcessingOptions { recompute_costs, ..ProcessingOptions::default() });
+        let total = |entries: &[UsageEntry]| entries.iter().map(|e| e.cost).sum::<f64>();
+
+        let trusted = load(false);
+        let recomputed = load(true);
+        assert!((total(&trusted) - (1.0 + computed)).abs() < 1e-9);
+        assert!((total(&recomputed) - 2.0 * computed).abs() < 1e-9);
+
+        // Cached entries switch either way without re-reading the file
+        let switched: Vec<UsageEntry> = trusted.into_iter().map(|e| e.with_cost_source(true)).collect();
+        assert!((total(&switched) - total(&recomputed)).abs() < 1e-9);
+        let restored: Vec<UsageEntry> = recomputed.into_iter().map(|e| e.with_cost_source(false)).collect();
+        assert!((total(&restored) - (1.0 + computed)).abs() < 1e-9);
+        assert_eq!(restored.iter().filter(|e| e.is_cost_divergent()).count(), 1);
+    }
+
+    #[test]
+    fn test_entries_are_costed_at_the_rates_of_their_day() {
+        use crate::analytics::calculator::{HistoricalPricing, ModelPricing};
+        use chrono::NaiveDate;
+
+        let older = SAMPLE_LINE
+            .replace("req_1", "req_2")
+            .replace("msg_1", "msg_2")
+            .replace("2025-07-01", "2025-05-01");
+        let root = ScratchDir::with_session("pricing-history", format!("{}\n{}\n", SAMPLE_LINE, older));
+
+        // Sonnet 4 cost double until June
+        let options = ProcessingOptions {
+            pricing_history: vec![HistoricalPricing {
+                model_pattern: "sonnet-4".to_string(),
+                from: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+                until: NaiveDate::from_ymd_opt(2025, 6, 1).unwrap(),
+                pricing: ModelPricing {
+                    input_price: 6.0,
+                    output_price: 30.0,
+                    cache_read_price: 0.60,
+                    cache_write_price: 7.50,
+                },
+            }],
+            ..ProcessingOptions::default()
+        };
+        let mut entries = root.load(options);
+        entries.sort_by_key(|e| e.timestamp);
+
+        let current = CostCalculator::new().calculate_cost("claude-sonnet-4-20250514", 100, 50, 0, 0);
+        assert!((entries[1].computed_cost - current).abs() < 1e-12);
+        assert!((entries[0].computed_cost - 2.0 * current).abs() < 1e-12);
+        assert_eq!(entries[0].cost, entries[0].computed_cost);
+    }
+
+    #[test]
+    fn test_invalid_logged_costs_are_sanitized() {
+        let negative_line = SAMPLE_LINE.replace(r#""cwd""#, r#""costUSD":-2.5,"cwd""#);
+        let root = ScratchDir::with_session("invalid-cost", negative_line);
+
+        let processor = root.processor(ProcessingOptions::default());
+        let entries = processor.process_all_files().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].cost, 0.0);
+        assert_eq!(processor.invalid_cost_count(), 1);
+
+        // JSON has no NaN or infinity literal, so those are checked directly
+        assert_eq!(processor.sanitize_cost(f64::NAN, 0.3), 0.3);
+        assert_eq!(processor.sanitize_cost(f64::INFINITY, 0.3), 0.3);
+        assert_eq!(processor.sanitize_cost(1.25, 0.3), 1.25);
+        assert_eq!(processor.invalid_cost_count(), 3);
+    }
+
+    #[test]
+    fn test_logged_cost_divergence_is_flagged() {
+        let logged_line = SAMPLE_LINE.replace(r#""cwd""#, r#""costUSD":1.0,"cwd""#);
+        let unlogged_line = SAMPLE_LINE.replace("req_1", "req_2").replace("msg_1", "msg_2");
+        let root = ScratchDir::with_session("divergence", format!("{}\n{}\n", logged_line, unlogged_line));
+
+        let mut entries = root.load(ProcessingOptions::default());
+        entries.sort_by(|a, b| a.request_id.cmp(&b.request_id));
+        let expected = CostCalculator::new().calculate_cost("claude-sonnet-4-20250514", 100, 50, 0, 0);
+
+        // The logged cost is still reported, with the computed cost kept alongside
+        assert_eq!(entries[0].cost, 1.0);
+        assert_eq!(entries[0].computed_cost, expected);
+        assert!(entries[0].is_cost_divergent());
+
+        assert_eq!(entries[1].cost, expected);
+        assert!(!entries[1].is_cost_divergent());
+    }
+
+    #[test]
+    fn test_max_history_days_skips_old_entries() {
+        let timestamp_of = |days_ago: i64| (Utc::now() - Duration::days(days_ago)).to_rfc3339();
+        let old_line = SAMPLE_LINE.replace("2025-07-01T12:00:00Z", &timestamp_of(60));
+        let recent_line = SAMPLE_LINE
+            .replace("2025-07-01T12:00:00Z", &timestamp_of(1))
+            .replace("req_1", "req_2")
+            .replace("msg_1", "msg_2");
+        let root = ScratchDir::with_session("history", format!("{}\n{}\n", old_line, recent_line));
+
+        let entries = root.load(ProcessingOptions {
+            max_history_days: Some(30),
+            ..ProcessingOptions::default()
+        });
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].request_id.as_deref(), Some("req_2"));
+
+        // Without a cutoff both entries load
+        assert_eq!(root.load(ProcessingOptions::default()).len(), 2);
+    }
+
+    #[test]
+    fn test_large_token_counts_parse_without_truncation() {
+        let huge = u32::MAX as u64 + 1_000;
+        let line = SAMPLE_LINE.replace(r#""input_tokens":100"#, &format!(r#""input_tokens":{}"#, huge));
+        let root = ScratchDir::with_session("large-tokens", line);
+
+        let entries = root.load(ProcessingOptions::default());
+        assert_eq!(entries[0].input_tokens, huge);
+    }
+
+    #[test]
+    fn test_array_shaped_message_keeps_usage() {
+        // Usage inside one of the content blocks
+        let block_usage = r#"{"timestamp":"2025-07-01T12:00:00Z","requestId":"req_1","cwd":"/Users/dev/app","message":[{"type":"text","text":"hi"},{"id":"msg_1","model":"claude-sonnet-4-20250514","usage":{"input_tokens":120,"output_tokens":30,"cache_read_input_tokens":400}}]}"#;
+        // Usage and model next to the array at the top level
+        let top_level_usage = r#"{"timestamp":"2025-07-01T12:05:00Z","requestId":"req_2","cwd":"/Users/dev/app","id":"msg_2","model":"claude-opus-4-20250514","message":[{"type":"text","text":"hi"}],"usage":{"input_tokens":7,"output_tokens":9}}"#;
+        // No usage anywhere is skipped rather than failing the file
+        let no_usage = r#"{"timestamp":"2025-07-01T12:10:00Z","requestId":"req_3","message":[{"type":"text","text":"hi"}]}"#;
+        let root = ScratchDir::with_session("array-message", format!("{}\n{}\n{}\n", block_usage, top_level_usage, no_usage));
+
+        let mut entries = root.load(ProcessingOptions::default());
+        entries.sort_by_key(|e| e.timestamp);
+        assert_eq!(entries.len(), 2);
+
+        assert_eq!(entries[0].model, "claude-sonnet-4-20250514");
+        assert_eq!((entries[0].input_tokens, entries[0].output_tokens, entries[0].cache_read_tokens), (120, 30, 400));
+
+        assert_eq!(entries[1].model, "claude-opus-4-20250514");
+        assert_eq!((entries[1].input_tokens, entries[1].output_tokens), (7, 9));
+    }
+
+    #[test]
+    fn test_only_billable_record_types_are_counted() {
+        let record = |entry_type: Option<&str>, id: &str, input: u64| {
+            let type_field = entry_type.map(|t| format!(r#""type":"{}","#, t)).unwrap_or_default();
+            format!(
+                r#"{{{}"timestamp":"2025-07-01T12:00:00Z","requestId":"req_{}","message":{{"id":"msg_{}","model":"claude-sonnet-4-20250514","usage":{{"input_tokens":{},"output_tokens":1}}}}}}"#,
+                type_field, id, id, input
+            )
+        };
+        let lines = [
+            record(Some("assistant"), "a", 100),
+            record(Some("user"), "u", 200),
+            record(Some("system"), "s", 300),
+            record(Some("tool_result"), "t", 400),
+            record(None, "n", 500),
+        ];
+        let root = ScratchDir::with_session("record-types", lines.join("\n"));
+
+        let input_tokens = |options: ProcessingOptions| {
+            let mut tokens: Vec<u64> = root.load(options).iter().map(|e| e.input_tokens).collect();
+            tokens.sort();
+            tokens
+        };
+
+        // Assistant records plus untyped ones (older files) by default
+        assert_eq!(input_tokens(ProcessingOptions::default()), vec![100, 500]);
+
+        let with_types = |types: &[&str]| ProcessingOptions {
+            billable_entry_types: types.iter().map(|t| t.to_string()).collect(),
+            ..ProcessingOptions::default()
+        };
+        assert_eq!(input_tokens(with_types(&["assistant", "tool_result"])), vec![100, 400, 500]);
+        assert_eq!(input_tokens(with_types(&[])), vec![100, 200, 300, 400, 500]);
+    }
+
+    #[test]
+    fn test_future_dated_entries_follow_policy() {
+        let timestamp_in = |minutes: i64| (Utc::now() + Duration::minutes(minutes)).to_rfc3339();
+        let skewed_line = SAMPLE_LINE.replace("2025-07-01T12:00:00Z", &timestamp_in(180));
+        // Within the tolerance, so never treated as skewed
+        let slightly_ahead_line = SAMPLE_LINE
+            .replace("2025-07-01T12:00:00Z", &timestamp_in(10))
+            .replace("req_1", "req_2")
+            .replace("msg_1", "msg_2");
+        let root = ScratchDir::with_session("future", format!("{}\n{}\n", skewed_line, slightly_ahead_line));
+
+        let load = |future_entries| {
+            let mut entries = root.load(ProcessingOptions {
+                future_entries,
+                ..ProcessingOptions::default()
+            });
+            entries.sort_by(|a, b| a.request_id.cmp(&b.request_id));
+            entries
+        };
+
+        let kept = load(FutureEntryPolicy::Keep);
+        assert_eq!(kept.len(), 2);
+        assert!(kept[0].future_dated);
+        assert!(kept[0].timestamp > Utc::now() + Duration::hours(2));
+        assert!(!kept[1].future_dated);
+
+        let clamped = load(FutureEntryPolicy::Clamp);
+        assert!(clamped[0].future_dated);
+        assert!(clamped[0].timestamp <= Utc::now());
+        assert!(!clamped[1].future_dated);
+
+        let dropped = load(FutureEntryPolicy::Drop);
+        assert_eq!(dropped.len(), 1);
+        assert_eq!(dropped[0].request_id.as_deref(), Some("req_2"));
+    }
+
+    #[test]
+    fn test_bom_and_trailing_commas_are_tolerated() {
+        let root = ScratchDir::new("bom");
+        root.write("bom/session.jsonl", format!("\u{feff}{}\n", SAMPLE_LINE));
+        let second = SAMPLE_LINE.replace("req_1", "req_2").replace("msg_1", "msg_2");
+        let third = SAMPLE_LINE.replace("req_1", "req_3").replace("msg_1", "msg_3");
+        root.write("commas/session.jsonl", format!("{},\n{} , \t\n", second, third));
+
+        let processor = root.processor(ProcessingOptions::default());
+        let mut ids: Vec<String> = processor
+            .process_all_files()
+            .unwrap()
+            .into_iter()
+            .filter_map(|e| e.request_id)
+            .collect();
+        ids.sort();
+
+        assert_eq!(ids, vec!["req_1", "req_2", "req_3"]);
+        assert_eq!(processor.repaired_line_count(), 3);
+    }
+
+    #[test]
+    fn test_raw_lines_are_kept_only_when_capture_is_on() {
+        let root = ScratchDir::with_session("raw-lines", format!("{},\n", SAMPLE_LINE));
+
+        let load = |capture_raw_lines| {
+            root.load(ProcessingOptions {
+                capture_raw_lines,
+                ..ProcessingOptions::default()
+            })
+        };
+
+        assert_eq!(load(false)[0].raw_line, None);
+        // The line exactly as written, before any repair
+        assert_eq!(load(true)[0].raw_line.as_deref(), Some(format!("{},", SAMPLE_LINE).as_str()));
+    }
+
+    #[test]
+    fn test_parse_errors_are_collected_up_to_the_limit_and_exported() {
+        let root = ScratchDir::new("parse-errors");
+        let file = root.write(
+            "project/session.jsonl",
+            format!("{}\n{{not json\n\n{{\"timestamp\":\"yesterday\",\"message\":{{}}}}\n[1,2\n", SAMPLE_LINE),
+        );
+
+        let processor = root.processor(ProcessingOptions { max_parse_errors: 2, ..ProcessingOptions::default() });
+        assert_eq!(processor.process_all_files().unwrap().len(), 1);
+
+        let log = processor.parse_errors();
+        assert_eq!(log.errors.iter().map(|e| e.line).collect::<Vec<_>>(), vec![Some(2), Some(4)]);
+        assert!(log.errors.iter().all(|e| e.file == file));
+        // The third bad line is past the cap and only counted
+        assert_eq!((log.dropped, log.total()), (1, 3));
+
+        let exported = crate::analytics::export::parse_errors_log(&log);
+        let lines: Vec<&str> = exported.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with(&format!("{}:2: Failed to parse JSON", file.display())));
+        assert!(lines[1].starts_with(&format!("{}:4: ", file.display())));
+        assert_eq!(lines[2], "... 1 more not recorded (limit reached)");
+    }
+
+    #[test]
+    fn test_clean_lines_are_not_counted_as_repaired() {
+        let root = ScratchDir::with_session("no-repair", format!("{}  \n", SAMPLE_LINE));
+
+        let processor = root.processor(ProcessingOptions::default());
+        assert_eq!(processor.process_all_files().unwrap().len(), 1);
+        assert_eq!(processor.repaired_line_count(), 0);
+    }
+
+    #[test]
+    fn test_non_utf8_file_does_not_block_other_files() {
+        let root = ScratchDir::with_session("non-utf8", SAMPLE_LINE.replace("req_1", "req_2").replace("msg_1", "msg_2"));
+        let mut garbage = SAMPLE_LINE.as_bytes().to_vec();
+        garbage.extend_from_slice(&[0xff, 0xfe, 0x00, 0xc3, 0x28, b'\n']);
+        root.write("broken/session.jsonl", garbage);
+
+        let entries = root.load(ProcessingOptions::default());
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].request_id.as_deref(), Some("req_2"));
+    }
+
+    #[test]
+    fn test_panic_in_one_file_is_recorded_and_skipped() {
+        let root = ScratchDir::with_session("panic", SAMPLE_LINE.replace("req_1", "req_2").replace("msg_1", "msg_2"));
+        let broken = root.write("broken/session.jsonl", SAMPLE_LINE);
+
+        let processor = root.processor(ProcessingOptions::default());
+        let files = processor.find_jsonl_files().unwrap();
+        let entries = processor.process_files_with(&files, |file_path, dedup| {
+            if file_path == broken {
+                panic!("parser bug");
+            }
+            processor.process_file(file_path, dedup)
+        });
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].request_id.as_deref(), Some("req_2"));
+        let log = processor.parse_errors();
+        assert_eq!(log.errors.len(), 1);
+        assert_eq!((&log.errors[0].file, log.errors[0].line), (&broken, None));
+        assert!(log.errors[0].reason.contains("panic"));
+    }
+}
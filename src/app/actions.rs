@@ -3,10 +3,11 @@
 // Used for keybindings and user interactions
 
 use gpui::actions;
+use serde::{Deserialize, Serialize};
 
-actions!(dashboard, [SwitchTab, Refresh, Export, Search]);
+actions!(dashboard, [SwitchTab, Refresh, Export, Search, CopyVisibleData, MarkAllSeen, ShareSummary, ExportParseErrors]);
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum DashboardTab {
     Overview,
     Models,
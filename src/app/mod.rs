@@ -3,4 +3,6 @@
 pub mod models;
 pub mod views;
 pub mod actions;
+pub mod settings;
+pub mod shortcuts;
 
@@ -0,0 +1,610 @@
+// Application settings and persistence
+// Handles user preferences for analytics and display behavior
+
+use chrono::{DateTime, Utc, Weekday};
+use chrono_tz::Tz;
+use log::warn;
+use gpui::{point, px, size, Bounds, Pixels};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use crate::app::actions::DashboardTab;
+use crate::utils::config::ConfigStore;
+use crate::utils::formatting::{CostPrecision, NumberFormat};
+use crate::utils::logging::LogVerbosity;
+use crate::analytics::calculator::{CostCalculator, HistoricalPricing};
+use crate::analytics::export::ExportFormat;
+use crate::analytics::models::{
+    AggregationOptions, CostThreshold, DataDirRetry, FutureEntryPolicy, ProcessingOptions, ProjectSort, QuietHours, SessionSort,
+    SessionSortColumn, TimelineGranularity, TokenTotalPolicy,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    pub token_total_policy: TokenTotalPolicy,
+    pub scan_max_depth: usize,
+    pub scan_ignored_dirs: Vec<String>,
+    /// Skip loading entries older than this many days; None keeps all history
+    pub max_history_days: Option<u32>,
+    /// Handling for entries dated more than an hour ahead of the clock
+    pub future_entry_policy: FutureEntryPolicy,
+    /// Record `type` values counted as usage (e.g. "assistant"); empty counts every type
+    pub billable_entry_types: Vec<String>,
+    /// Bill cache-creation tokens at the input rate for models that lack cache write pricing
+    pub cache_writes_as_input: bool,
+    /// Report every entry at its token-based cost instead of a logged `costUSD`
+    pub recompute_costs: bool,
+    /// Superseded model rates, so older entries are costed at the price of their day
+    pub pricing_history: Vec<HistoricalPricing>,
+    /// Retries, with backoff, while the data directory is missing before giving up on a load
+    pub data_dir_retry: DataDirRetry,
+    /// Debug mode: keep every entry's original JSONL line for the raw inspector
+    pub capture_raw_lines: bool,
+    pub week_start: Weekday,
+    /// IANA timezone name (e.g. "Europe/Berlin"); None uses the system local timezone
+    pub timezone: Option<String>,
+    /// Reload interval in seconds; None disables auto-refresh
+    pub auto_refresh_secs: Option<u64>,
+    pub number_format: NumberFormat,
+    pub cost_precision: CostPrecision,
+    /// Show one row per model family instead of one per dated model version
+    pub group_models_by_family: bool,
+    /// Tab shown on startup (see `startup_tab` for how it combines with `remember_last_tab`)
+    pub default_tab: DashboardTab,
+    /// Reopen on whichever tab was active when the app was last used
+    pub remember_last_tab: bool,
+    /// Tabs shown in the navigation; number keys count only these
+    pub visible_tabs: Vec<DashboardTab>,
+    /// Most recently active tab, tracked while `remember_last_tab` is on
+    pub last_tab: Option<DashboardTab>,
+    /// Where the window was last placed; None opens a centered default window
+    pub window_geometry: Option<WindowGeometry>,
+    /// Spending limit for the current calendar month in USD; None disables budget tracking
+    pub monthly_budget: Option<f64>,
+    /// Show a native notification when the month's spend first exceeds the budget (macOS only)
+    pub budget_notifications: bool,
+    /// Flag single requests costing more than this many USD; None turns the alert off
+    pub request_cost_alert: Option<f64>,
+    /// Project paths left out of all totals
+    pub excluded_projects: Vec<String>,
+    /// Model ids left out of all totals
+    pub excluded_models: Vec<String>,
+    /// Leave cache tokens and their cost out of every total (raw input/output only)
+    pub ignore_cache: bool,
+    /// Group symlinked project directories under their real path
+    pub resolve_project_symlinks: bool,
+    /// Leave usage during `quiet_hours` (e.g. overnight agents) out of all totals
+    pub quiet_hours_enabled: bool,
+    /// Local-time window used when `quiet_hours_enabled` is on; kept while disabled
+    pub quiet_hours: QuietHours,
+    /// Ignore everything logged before this instant (e.g. early test data); None counts all history
+    pub analysis_start_date: Option<DateTime<Utc>>,
+    /// Format written by the Export action (Cmd+E)
+    pub default_export_format: ExportFormat,
+    /// List models without pricing after the first load so their $0 costs are noticed
+    pub audit_models_on_startup: bool,
+    /// Models and projects costing less than this are grouped into an "Other" row; 0 disables
+    pub minor_contributor_threshold: CostThreshold,
+    /// Daily, weekly or monthly grouping the Timeline opens with (updated when it's changed there)
+    pub timeline_granularity: TimelineGranularity,
+    /// How much is written to the console
+    pub log_verbosity: LogVerbosity,
+    /// Free-form notes keyed by session id (e.g. "migration spike")
+    pub session_notes: HashMap<String, String>,
+    /// Project paths kept at the top of the Projects list, in the order they were pinned
+    pub pinned_projects: Vec<String>,
+    /// Sort and filter choices last made on each tab
+    pub tab_view_state: HashMap<DashboardTab, TabViewState>,
+    /// The first-run welcome guide has been dismissed. Defaults to true so settings files
+    /// written before the guide existed don't bring it back; only a missing file sets it false
+    pub onboarding_completed: bool,
+}
+
+/// Sort and filter choices remembered for one tab
+///
+/// Sort columns are stored by label so a renamed or removed column only resets that
+/// tab's sort instead of failing to load the whole settings file.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TabViewState {
+    /// Label of the sorted column (e.g. "Cost"); None or an unknown label uses the tab's default
+    pub sort_column: Option<String>,
+    pub sort_descending: bool,
+    /// Last text typed into the tab's filter
+    pub query: String,
+}
+
+/// Saved window position and size in logical pixels
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WindowGeometry {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl WindowGeometry {
+    /// How much of the window (roughly a title bar's worth) must land on a display to restore it
+    const MIN_VISIBLE: f32 = 50.0;
+
+    pub fn from_bounds(bounds: Bounds<Pixels>) -> Self {
+        Self {
+            x: bounds.origin.x.into(),
+            y: bounds.origin.y.into(),
+            width: bounds.size.width.into(),
+            height: bounds.size.height.into(),
+        }
+    }
+
+    pub fn to_bounds(&self) -> Bounds<Pixels> {
+        Bounds::new(point(px(self.x), px(self.y)), size(px(self.width), px(self.height)))
+    }
+
+    /// Whether enough of the window overlaps one of the given display bounds to be reachable
+    pub fn is_visible_on(&self, displays: &[Bounds<Pixels>]) -> bool {
+        let bounds = self.to_bounds();
+        displays.iter().any(|display| {
+            let overlap = bounds.intersect(display);
+            overlap.size.width >= px(Self::MIN_VISIBLE) && overlap.size.height >= px(Self::MIN_VISIBLE)
+        })
+    }
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        let processing = ProcessingOptions::default();
+        Self {
+            token_total_policy: TokenTotalPolicy::IncludeCache,
+            scan_max_depth: processing.max_depth,
+            scan_ignored_dirs: processing.ignored_dirs,
+            max_history_days: processing.max_history_days,
+            future_entry_policy: processing.future_entries,
+            billable_entry_types: processing.billable_entry_types,
+            cache_writes_as_input: processing.cache_writes_as_input,
+            recompute_costs: processing.recompute_costs,
+            pricing_history: processing.pricing_history,
+            data_dir_retry: processing.data_dir_retry,
+            capture_raw_lines: processing.capture_raw_lines,
+            week_start: Weekday::Mon,
+            timezone: None,
+            auto_refresh_secs: None,
+            number_format: NumberFormat::Abbreviated,
+            cost_precision: CostPrecision::Auto,
+            group_models_by_family: false,
+            default_tab: DashboardTab::Overview,
+            remember_last_tab: false,
+            visible_tabs: DashboardTab::all(),
+            last_tab: None,
+            window_geometry: None,
+            monthly_budget: None,
+            request_cost_alert: Some(1.0),
+            excluded_projects: Vec::new(),
+            excluded_models: Vec::new(),
+            ignore_cache: false,
+            resolve_project_symlinks: false,
+            quiet_hours_enabled: false,
+            quiet_hours: QuietHours::default(),
+            analysis_start_date: None,
+            default_export_format: ExportFormat::Csv,
+            audit_models_on_startup: false,
+            minor_contributor_threshold: CostThreshold::Absolute(0.0),
+            timeline_granularity: TimelineGranularity::Monthly,
+            log_verbosity: LogVerbosity::Quiet,
+            session_notes: HashMap::new(),
+            pinned_projects: Vec::new(),
+            tab_view_state: HashMap::new(),
+            onboarding_completed: true,
+            budget_notifications: false,
+        }
+    }
+}
+
+impl AppSettings {
+    /// Settings file name within the config directory
+    const FILE_NAME: &str = "settings.json";
+    /// Copy of a settings file that failed to load
+    const BACKUP_FILE_NAME: &str = "settings.json.bak";
+
+    /// Load app settings from disk, falling back to defaults
+    pub fn load() -> Self {
+        Self::load_or_default(ConfigStore::global())
+    }
+
+    /// Settings from `store`. Defaults are written only on first launch; a file that can't be
+    /// read or parsed is left in place (and copied to `settings.json.bak`) so one bad field
+    /// doesn't wipe notes, pins and exclusions, and defaults are used in memory instead.
+    fn load_or_default(store: &ConfigStore) -> Self {
+        if !store.exists(Self::FILE_NAME) {
+            // No settings file at all means this is the first launch
+            let default_settings = Self {
+                onboarding_completed: false,
+                ..Self::default()
+            };
+            if let Err(e) = default_settings.save_to(store) {
+                warn!("⚠️ Failed to save settings: {}", e);
+            }
+            return default_settings;
+        }
+
+        match Self::load_from(store) {
+            Ok(settings) => settings,
+            Err(e) => {
+                warn!("⚠️ Could not load {}, using defaults for this session: {}", Self::FILE_NAME, e);
+                if let Ok(content) = store.read(Self::FILE_NAME) {
+                    if let Err(e) = store.write(Self::BACKUP_FILE_NAME, &content) {
+                        warn!("⚠️ Failed to back up settings: {}", e);
+                    }
+                }
+                Self::default()
+            }
+        }
+    }
+
+    fn load_from(store: &ConfigStore) -> anyhow::Result<Self> {
+        let content = store.read(Self::FILE_NAME)?;
+        let settings: AppSettings = serde_json::from_str(&content)?;
+        Ok(settings)
+    }
+
+    /// Save app settings to disk
+    pub fn save(&self) -> anyhow::Result<()> {
+        self.save_to(ConfigStore::global())
+    }
+
+    fn save_to(&self, store: &ConfigStore) -> anyhow::Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        store.write(Self::FILE_NAME, &content)
+    }
+
+    /// Number of projects and models currently excluded from totals
+    pub fn excluded_count(&self) -> usize {
+        self.excluded_projects.len() + self.excluded_models.len()
+    }
+
+    /// Tab to open on startup
+    ///
+    /// When `remember_last_tab` is on and a tab has been recorded, the last-used tab wins;
+    /// otherwise `default_tab` is used.
+    pub fn startup_tab(&self) -> DashboardTab {
+        let tab = match (&self.last_tab, self.remember_last_tab) {
+            (Some(last_tab), true) => last_tab.clone(),
+            _ => self.default_tab.clone(),
+        };
+        // A tab hidden since it was chosen gives way to the first one shown
+        let shown = self.shown_tabs();
+        if shown.contains(&tab) {
+            tab
+        } else {
+            shown[0].clone()
+        }
+    }
+
+    /// Tabs in the navigation, in their usual order; never empty, so a hand-edited
+    /// `visible_tabs` that lists none shows them all
+    pub fn shown_tabs(&self) -> Vec<DashboardTab> {
+        let shown: Vec<DashboardTab> =
+            DashboardTab::all().into_iter().filter(|tab| self.visible_tabs.contains(tab)).collect();
+        if shown.is_empty() {
+            DashboardTab::all()
+        } else {
+            shown
+        }
+    }
+
+    /// Tab reached with number key `number` (from 1), counting only shown tabs
+    pub fn tab_for_number(&self, number: usize) -> Option<DashboardTab> {
+        self.shown_tabs().get(number.checked_sub(1)?).cloned()
+    }
+
+    /// Show or hide a tab; hiding the last shown tab is refused. Returns whether anything changed.
+    pub fn toggle_tab_visibility(&mut self, tab: &DashboardTab) -> bool {
+        let mut shown = self.shown_tabs();
+        if shown.contains(tab) {
+            if shown.len() == 1 {
+                return false;
+            }
+            shown.retain(|t| t != tab);
+        } else {
+            shown.push(tab.clone());
+        }
+        self.visible_tabs = shown;
+        true
+    }
+
+    pub fn is_project_pinned(&self, project_path: &str) -> bool {
+        self.pinned_projects.iter().any(|path| path == project_path)
+    }
+
+    /// Pin a project below any already pinned, or unpin it
+    pub fn toggle_project_pin(&mut self, project_path: &str) {
+        if self.is_project_pinned(project_path) {
+            self.pinned_projects.retain(|path| path != project_path);
+        } else {
+            self.pinned_projects.push(project_path.to_string());
+        }
+    }
+
+    /// Note attached to a session, if any
+    pub fn session_note(&self, session_id: &str) -> Option<&str> {
+        self.session_notes.get(session_id).map(String::as_str)
+    }
+
+    /// Attach a note to a session; a blank note removes it
+    pub fn set_session_note(&mut self, session_id: &str, note: &str) {
+        let note = note.trim();
+        if note.is_empty() {
+            self.session_notes.remove(session_id);
+        } else {
+            self.session_notes.insert(session_id.to_string(), note.to_string());
+        }
+    }
+
+    fn view_state(&self, tab: &DashboardTab) -> Option<&TabViewState> {
+        self.tab_view_state.get(tab)
+    }
+
+    /// Project sort restored from the Projects tab state
+    pub fn project_sort(&self) -> ProjectSort {
+        self.view_state(&DashboardTab::Projects)
+            .and_then(|state| state.sort_column.as_deref())
+            .and_then(|label| ProjectSort::ALL.into_iter().find(|sort| sort.label() == label))
+            .unwrap_or(ProjectSort::TotalCost)
+    }
+
+    pub fn set_project_sort(&mut self, sort: ProjectSort) {
+        let state = self.tab_view_state.entry(DashboardTab::Projects).or_default();
+        state.sort_column = Some(sort.label().to_string());
+        state.sort_descending = true;
+    }
+
+    /// Sessions table sort restored from the Sessions tab state
+    pub fn session_sort(&self) -> SessionSort {
+        self.view_state(&DashboardTab::Sessions)
+            .and_then(|state| {
+                let label = state.sort_column.as_deref()?;
+                let column = SessionSortColumn::ALL.into_iter().find(|column| column.label() == label)?;
+                Some(SessionSort { column, descending: state.sort_descending })
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn set_session_sort(&mut self, sort: SessionSort) {
+        let state = self.tab_view_state.entry(DashboardTab::Sessions).or_default();
+        state.sort_column = Some(sort.column.label().to_string());
+        state.sort_descending = sort.descending;
+    }
+
+    /// Last Sessions table filter
+    pub fn session_filter(&self) -> String {
+        self.view_state(&DashboardTab::Sessions).map(|state| state.query.clone()).unwrap_or_default()
+    }
+
+    pub fn set_session_filter(&mut self, query: &str) {
+        self.tab_view_state.entry(DashboardTab::Sessions).or_default().query = query.to_string();
+    }
+
+    /// Calculator matching the pricing applied while loading entries
+    pub fn cost_calculator(&self) -> CostCalculator {
+        CostCalculator::with_cache_write_fallback(self.cache_writes_as_input).with_pricing_history(self.pricing_history.clone())
+    }
+
+    /// Options passed to the aggregator when computing analytics
+    pub fn aggregation_options(&self) -> AggregationOptions {
+        AggregationOptions {
+            token_total_policy: self.token_total_policy,
+            week_start: self.week_start,
+            timezone: self.parsed_timezone(),
+            group_models_by_family: self.group_models_by_family,
+            request_cost_alert: self.request_cost_alert,
+            excluded_projects: self.excluded_projects.clone(),
+            excluded_models: self.excluded_models.clone(),
+            ignore_cache: self.ignore_cache,
+            cache_writes_as_input: self.cache_writes_as_input,
+            resolve_symlinks: self.resolve_project_symlinks,
+            quiet_hours: self.quiet_hours_enabled.then_some(self.quiet_hours),
+            analysis_start: self.analysis_start_date,
+            pricing_history: self.pricing_history.clone(),
+        }
+    }
+
+    /// Resolve the configured timezone, falling back to system local if the name is unknown
+    pub fn parsed_timezone(&self) -> Option<Tz> {
+        let name = self.timezone.as_deref()?;
+        match name.parse::<Tz>() {
+            Ok(tz) => Some(tz),
+            Err(_) => {
+                warn!("⚠️ Unknown timezone '{}' in settings, using system local time", name);
+                None
+            }
+        }
+    }
+
+    /// Options passed to the processor when scanning and parsing files
+    pub fn processing_options(&self) -> ProcessingOptions {
+        ProcessingOptions {
+            max_depth: self.scan_max_depth,
+            ignored_dirs: self.scan_ignored_dirs.clone(),
+            max_history_days: self.max_history_days,
+            future_entries: self.future_entry_policy,
+            billable_entry_types: self.billable_entry_types.clone(),
+            cache_writes_as_input: self.cache_writes_as_input,
+            recompute_costs: self.recompute_costs,
+            pricing_history: self.pricing_history.clone(),
+            data_dir_retry: self.data_dir_retry,
+            capture_raw_lines: self.capture_raw_lines,
+            ..ProcessingOptions::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::config::CONFIG_DIR_ENV;
+
+    #[test]
+    fn test_startup_tab_precedence() {
+        let mut settings = AppSettings {
+            default_tab: DashboardTab::Models,
+            ..AppSettings::default()
+        };
+        assert_eq!(settings.startup_tab(), DashboardTab::Models);
+
+        // A recorded last tab is ignored unless remembering is enabled
+        settings.last_tab = Some(DashboardTab::Timeline);
+        assert_eq!(settings.startup_tab(), DashboardTab::Models);
+
+        settings.remember_last_tab = true;
+        assert_eq!(settings.startup_tab(), DashboardTab::Timeline);
+
+        // Nothing recorded yet falls back to the default
+        settings.last_tab = None;
+        assert_eq!(settings.startup_tab(), DashboardTab::Models);
+
+        // Unless that tab has been hidden
+        settings.toggle_tab_visibility(&DashboardTab::Models);
+        assert_eq!(settings.startup_tab(), DashboardTab::Overview);
+    }
+
+    #[test]
+    fn test_number_keys_skip_hidden_tabs() {
+        let mut settings = AppSettings::default();
+        assert_eq!(settings.tab_for_number(4), Some(DashboardTab::Sessions));
+
+        assert!(settings.toggle_tab_visibility(&DashboardTab::Sessions));
+        assert_eq!(settings.tab_for_number(3), Some(DashboardTab::Projects));
+        assert_eq!(settings.tab_for_number(4), Some(DashboardTab::Timeline));
+        assert_eq!(settings.tab_for_number(5), None);
+        assert_eq!(settings.tab_for_number(0), None);
+
+        // Shown again in its usual place, not at the end
+        assert!(settings.toggle_tab_visibility(&DashboardTab::Sessions));
+        assert_eq!(settings.tab_for_number(4), Some(DashboardTab::Sessions));
+
+        // The last shown tab can't be hidden
+        for tab in [DashboardTab::Overview, DashboardTab::Models, DashboardTab::Projects, DashboardTab::Sessions] {
+            assert!(settings.toggle_tab_visibility(&tab));
+        }
+        assert!(!settings.toggle_tab_visibility(&DashboardTab::Timeline));
+        assert_eq!(settings.shown_tabs(), vec![DashboardTab::Timeline]);
+        assert_eq!(settings.tab_for_number(1), Some(DashboardTab::Timeline));
+
+        // An empty list from a hand-edited settings file shows everything
+        settings.visible_tabs.clear();
+        assert_eq!(settings.shown_tabs(), DashboardTab::all());
+    }
+
+    #[test]
+    fn test_window_geometry_visibility() {
+        let displays = [
+            Bounds::new(point(px(0.0), px(0.0)), size(px(1440.0), px(900.0))),
+            Bounds::new(point(px(1440.0), px(0.0)), size(px(1920.0), px(1080.0))),
+        ];
+        let at = |x, y| WindowGeometry { x, y, width: 1200.0, height: 800.0 };
+
+        assert!(at(100.0, 50.0).is_visible_on(&displays));
+        // Mostly on the second display
+        assert!(at(2500.0, 200.0).is_visible_on(&displays));
+        // Only a sliver left on screen, or on a display that was disconnected
+        assert!(!at(-1180.0, 50.0).is_visible_on(&displays));
+        assert!(!at(4000.0, 200.0).is_visible_on(&displays));
+        assert!(!at(100.0, 50.0).is_visible_on(&[]));
+
+        let geometry = at(10.0, 20.0);
+        assert_eq!(WindowGeometry::from_bounds(geometry.to_bounds()), geometry);
+    }
+
+    #[test]
+    fn test_settings_saved_in_overridden_config_dir() {
+        let dir = std::env::temp_dir()
+            .join(format!("usage-dashboard-config-env-{}", std::process::id()))
+            .join("nested");
+        let _ = std::fs::remove_dir_all(dir.parent().unwrap());
+        // How ConfigStore::global() resolves its directory, with USAGE_DASHBOARD_CONFIG_DIR set to `dir`
+        let env_dir = ConfigStore::dir_from_env(|name| (name == CONFIG_DIR_ENV).then(|| dir.display().to_string()));
+        assert_eq!(env_dir, dir);
+        assert!(dir.is_dir());
+        let store = ConfigStore::at(Some(env_dir));
+
+        let settings = AppSettings {
+            monthly_budget: Some(42.0),
+            ..AppSettings::default()
+        };
+        settings.save_to(&store).unwrap();
+        assert!(dir.join(AppSettings::FILE_NAME).is_file());
+        assert_eq!(AppSettings::load_from(&store).unwrap().monthly_budget, Some(42.0));
+
+        let _ = std::fs::remove_dir_all(dir.parent().unwrap());
+    }
+
+    #[test]
+    fn test_malformed_settings_file_is_kept_and_backed_up() {
+        let dir = std::env::temp_dir().join(format!("usage-dashboard-config-bad-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let store = ConfigStore::at(Some(dir.clone()));
+
+        // First launch writes defaults and shows onboarding
+        let first = AppSettings::load_or_default(&store);
+        assert!(!first.onboarding_completed);
+        assert!(store.exists(AppSettings::FILE_NAME));
+
+        // One bad field must not overwrite everything else in the file
+        let malformed = r#"{"monthly_budget": "lots", "pinned_projects": ["/Users/dev/app"]}"#;
+        store.write(AppSettings::FILE_NAME, malformed).unwrap();
+        let loaded = AppSettings::load_or_default(&store);
+        assert!(loaded.onboarding_completed);
+        assert!(loaded.pinned_projects.is_empty());
+        assert_eq!(store.read(AppSettings::FILE_NAME).unwrap(), malformed);
+        assert_eq!(store.read(AppSettings::BACKUP_FILE_NAME).unwrap(), malformed);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_tab_view_state_round_trip() {
+        let mut settings = AppSettings::default();
+        assert_eq!(settings.project_sort(), ProjectSort::TotalCost);
+        assert_eq!(settings.session_sort(), SessionSort::default());
+
+        settings.set_project_sort(ProjectSort::CostPerRequest);
+        let sort = SessionSort { column: SessionSortColumn::Tokens, descending: false };
+        settings.set_session_sort(sort);
+        settings.set_session_filter("billing");
+
+        let json = serde_json::to_string(&settings).unwrap();
+        let restored: AppSettings = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.tab_view_state, settings.tab_view_state);
+        assert_eq!(restored.project_sort(), ProjectSort::CostPerRequest);
+        assert_eq!(restored.session_sort(), sort);
+        assert_eq!(restored.session_filter(), "billing");
+
+        // Older files: no map at all, a tab entry missing fields, or a column that no longer exists
+        let legacy: AppSettings = serde_json::from_str(r#"{"ignore_cache": false}"#).unwrap();
+        assert!(legacy.tab_view_state.is_empty());
+        let partial: AppSettings = serde_json::from_str(
+            r#"{"tab_view_state": {"Sessions": {"query": "api"}, "Projects": {"sort_column": "Velocity"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(partial.session_filter(), "api");
+        assert_eq!(partial.session_sort(), SessionSort::default());
+        assert_eq!(partial.project_sort(), ProjectSort::TotalCost);
+    }
+
+    #[test]
+    fn test_session_notes_serde() {
+        let mut settings = AppSettings::default();
+        settings.set_session_note("abc123", "  migration spike ");
+        settings.set_session_note("def456", "bug hunt");
+        settings.set_session_note("def456", "   ");
+
+        let json = serde_json::to_string(&settings).unwrap();
+        let restored: AppSettings = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.session_note("abc123"), Some("migration spike"));
+        assert_eq!(restored.session_note("def456"), None);
+        assert_eq!(restored.session_notes.len(), 1);
+
+        // Settings written before notes existed load with none
+        let legacy: AppSettings = serde_json::from_str(r#"{"ignore_cache": true}"#).unwrap();
+        assert!(legacy.session_notes.is_empty());
+    }
+}
@@ -0,0 +1,216 @@
+// Keyboard Shortcuts
+// Single registry of the dashboard's shortcuts, used both to handle key presses
+// and to list them in the "?" overlay so the two never drift apart
+
+use gpui::{KeyBinding, Keystroke};
+
+use crate::analytics::models::TimeRange;
+use crate::app::actions::{CopyVisibleData, Export, MarkAllSeen, ShareSummary};
+
+/// Outside the text fields, which keep Cmd+C and plain letters for themselves
+const TEXT_FIELD_CONTEXT: &str = "Dashboard && !SessionSearch && !SessionNote && !SessionFilter";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Modifier {
+    None,
+    Alt,
+    Platform,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ShortcutCommand {
+    /// The nth tab of those shown, counting from 1, so hidden tabs leave no gaps
+    SwitchTab(usize),
+    SetTimeRange(TimeRange),
+    Refresh,
+    Export,
+    CopyVisibleData,
+    MarkAllSeen,
+    ShareSummary,
+    FocusSessionSearch,
+    ToggleDebugPanel,
+    ToggleShortcutOverlay,
+    HideApp,
+    CloseWindow,
+    Quit,
+}
+
+impl ShortcutCommand {
+    pub fn description(&self) -> String {
+        match self {
+            Self::SwitchTab(number) => format!("Go to tab {}", number),
+            Self::SetTimeRange(range) => format!("Show {}", range.label()),
+            Self::Refresh => "Reload usage data".to_string(),
+            Self::Export => "Export this view".to_string(),
+            Self::CopyVisibleData => "Copy this view as TSV".to_string(),
+            Self::MarkAllSeen => "Clear every tab's new-spend badge".to_string(),
+            Self::ShareSummary => "Copy a shareable summary".to_string(),
+            Self::FocusSessionSearch => "Go to a session".to_string(),
+            Self::ToggleDebugPanel => "Toggle the debug panel".to_string(),
+            Self::ToggleShortcutOverlay => "Show or hide this list".to_string(),
+            Self::HideApp => "Hide the app".to_string(),
+            Self::CloseWindow => "Close the window".to_string(),
+            Self::Quit => "Quit".to_string(),
+        }
+    }
+
+    /// Heading the overlay lists this command under
+    pub fn group(&self) -> &'static str {
+        match self {
+            Self::SwitchTab(_) => "Tabs",
+            Self::SetTimeRange(_) => "Time range",
+            Self::Refresh | Self::Export | Self::CopyVisibleData | Self::MarkAllSeen | Self::ShareSummary => "Data",
+            Self::FocusSessionSearch | Self::ToggleDebugPanel | Self::ToggleShortcutOverlay => "Navigation",
+            Self::HideApp | Self::CloseWindow | Self::Quit => "Window",
+        }
+    }
+
+    /// Commands dispatched as GPUI actions through key bindings rather than the key-down handler
+    fn is_action(&self) -> bool {
+        matches!(self, Self::Export | Self::CopyVisibleData | Self::MarkAllSeen | Self::ShareSummary)
+    }
+}
+
+pub struct Shortcut {
+    pub modifier: Modifier,
+    /// Key names that trigger it; the first is the one displayed
+    pub keys: &'static [&'static str],
+    pub command: ShortcutCommand,
+}
+
+impl Shortcut {
+    /// Display form matching the rest of the UI, e.g. "Alt+1" or "⌘E"
+    pub fn label(&self) -> String {
+        let key = self.keys[0].to_uppercase();
+        match self.modifier {
+            Modifier::None => key,
+            Modifier::Alt => format!("Alt+{}", key),
+            Modifier::Platform => format!("⌘{}", key),
+        }
+    }
+
+    /// The shortcut in GPUI key binding syntax, e.g. "alt-1" or "cmd-e"
+    fn binding(&self) -> String {
+        match self.modifier {
+            Modifier::None => self.keys[0].to_string(),
+            Modifier::Alt => format!("alt-{}", self.keys[0]),
+            Modifier::Platform => format!("cmd-{}", self.keys[0]),
+        }
+    }
+
+    fn matches(&self, keystroke: &Keystroke) -> bool {
+        let modifiers = &keystroke.modifiers;
+        let modifier_matches = match self.modifier {
+            Modifier::None => !modifiers.platform && !modifiers.alt && !modifiers.control,
+            Modifier::Alt => modifiers.alt && !modifiers.platform,
+            Modifier::Platform => modifiers.platform,
+        };
+        modifier_matches && self.keys.contains(&keystroke.key.as_str())
+    }
+}
+
+/// Every shortcut, in the order the overlay lists them
+pub const SHORTCUTS: &[Shortcut] = &[
+    Shortcut { modifier: Modifier::None, keys: &["1"], command: ShortcutCommand::SwitchTab(1) },
+    Shortcut { modifier: Modifier::None, keys: &["2"], command: ShortcutCommand::SwitchTab(2) },
+    Shortcut { modifier: Modifier::None, keys: &["3"], command: ShortcutCommand::SwitchTab(3) },
+    Shortcut { modifier: Modifier::None, keys: &["4"], command: ShortcutCommand::SwitchTab(4) },
+    Shortcut { modifier: Modifier::None, keys: &["5"], command: ShortcutCommand::SwitchTab(5) },
+    Shortcut { modifier: Modifier::Alt, keys: &["1"], command: ShortcutCommand::SetTimeRange(TimeRange::AllTime) },
+    Shortcut { modifier: Modifier::Alt, keys: &["2"], command: ShortcutCommand::SetTimeRange(TimeRange::Last30Days) },
+    Shortcut { modifier: Modifier::Alt, keys: &["3"], command: ShortcutCommand::SetTimeRange(TimeRange::Last7Days) },
+    Shortcut { modifier: Modifier::Alt, keys: &["4"], command: ShortcutCommand::SetTimeRange(TimeRange::Yesterday) },
+    Shortcut { modifier: Modifier::Alt, keys: &["5"], command: ShortcutCommand::SetTimeRange(TimeRange::Today) },
+    Shortcut { modifier: Modifier::Alt, keys: &["6"], command: ShortcutCommand::SetTimeRange(TimeRange::Last24Hours) },
+    Shortcut { modifier: Modifier::None, keys: &["r"], command: ShortcutCommand::Refresh },
+    Shortcut { modifier: Modifier::Platform, keys: &["e"], command: ShortcutCommand::Export },
+    Shortcut { modifier: Modifier::Platform, keys: &["c"], command: ShortcutCommand::CopyVisibleData },
+    Shortcut { modifier: Modifier::None, keys: &["m"], command: ShortcutCommand::MarkAllSeen },
+    Shortcut { modifier: Modifier::None, keys: &["s"], command: ShortcutCommand::ShareSummary },
+    Shortcut { modifier: Modifier::None, keys: &["g"], command: ShortcutCommand::FocusSessionSearch },
+    Shortcut { modifier: Modifier::None, keys: &["d"], command: ShortcutCommand::ToggleDebugPanel },
+    // Shift+/ arrives as "/" on some layouts
+    Shortcut { modifier: Modifier::None, keys: &["?", "/"], command: ShortcutCommand::ToggleShortcutOverlay },
+    Shortcut { modifier: Modifier::Platform, keys: &["h"], command: ShortcutCommand::HideApp },
+    Shortcut { modifier: Modifier::Platform, keys: &["w"], command: ShortcutCommand::CloseWindow },
+    Shortcut { modifier: Modifier::Platform, keys: &["q"], command: ShortcutCommand::Quit },
+];
+
+/// Shortcut the key-down handler should run for this keystroke, if any
+pub fn find(keystroke: &Keystroke) -> Option<&'static Shortcut> {
+    SHORTCUTS
+        .iter()
+        .find(|shortcut| !shortcut.command.is_action() && shortcut.matches(keystroke))
+}
+
+/// Key bindings for the shortcuts that dispatch GPUI actions
+pub fn action_bindings() -> Vec<KeyBinding> {
+    SHORTCUTS
+        .iter()
+        .filter_map(|shortcut| {
+            let keystroke = shortcut.binding();
+            match shortcut.command {
+                ShortcutCommand::Export => Some(KeyBinding::new(&keystroke, Export, None)),
+                ShortcutCommand::CopyVisibleData => {
+                    Some(KeyBinding::new(&keystroke, CopyVisibleData, Some(TEXT_FIELD_CONTEXT)))
+                }
+                ShortcutCommand::MarkAllSeen => Some(KeyBinding::new(&keystroke, MarkAllSeen, Some(TEXT_FIELD_CONTEXT))),
+                ShortcutCommand::ShareSummary => Some(KeyBinding::new(&keystroke, ShareSummary, Some(TEXT_FIELD_CONTEXT))),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Shortcuts grouped under their overlay headings, keeping registry order
+pub fn grouped() -> Vec<(&'static str, Vec<&'static Shortcut>)> {
+    let mut groups: Vec<(&'static str, Vec<&'static Shortcut>)> = Vec::new();
+    for shortcut in SHORTCUTS {
+        let group = shortcut.command.group();
+        match groups.iter_mut().find(|(name, _)| *name == group) {
+            Some((_, shortcuts)) => shortcuts.push(shortcut),
+            None => groups.push((group, vec![shortcut])),
+        }
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::actions::DashboardTab;
+
+    fn key(source: &str) -> Keystroke {
+        Keystroke::parse(source).unwrap()
+    }
+
+    #[test]
+    fn test_shortcut_lookup() {
+        assert_eq!(find(&key("3")).unwrap().command, ShortcutCommand::SwitchTab(3));
+        assert_eq!(find(&key("alt-3")).unwrap().command, ShortcutCommand::SetTimeRange(TimeRange::Last7Days));
+        assert_eq!(find(&key("/")).unwrap().command, ShortcutCommand::ToggleShortcutOverlay);
+        assert_eq!(find(&key("cmd-q")).unwrap().command, ShortcutCommand::Quit);
+        assert_eq!(find(&key("ctrl-3")).map(|s| &s.command), None);
+        // Handled by key bindings instead
+        assert_eq!(find(&key("cmd-e")).map(|s| &s.command), None);
+        assert_eq!(find(&key("m")).map(|s| &s.command), None);
+        assert_eq!(find(&key("s")).map(|s| &s.command), None);
+        assert_eq!(action_bindings().len(), 4);
+    }
+
+    #[test]
+    fn test_registry_has_no_conflicts_and_covers_every_tab() {
+        for (i, a) in SHORTCUTS.iter().enumerate() {
+            for b in &SHORTCUTS[i + 1..] {
+                let overlap = a.keys.iter().any(|k| b.keys.contains(k));
+                assert!(!(a.modifier == b.modifier && overlap), "{} conflicts with {}", a.label(), b.label());
+            }
+        }
+        for number in 1..=DashboardTab::all().len() {
+            assert!(SHORTCUTS.iter().any(|s| s.command == ShortcutCommand::SwitchTab(number)));
+        }
+        let label = |command: ShortcutCommand| SHORTCUTS.iter().find(|s| s.command == command).unwrap().label();
+        assert_eq!(label(ShortcutCommand::Export), "⌘E");
+        assert_eq!(label(ShortcutCommand::SetTimeRange(TimeRange::AllTime)), "Alt+1");
+    }
+}
@@ -1,5 +1,6 @@
 // Reusable UI Components
 // These are building blocks for the dashboard views
 
-// All component files removed - functionality integrated directly into root view
+pub mod tooltip;
 
+// Most component files removed - functionality integrated directly into root view
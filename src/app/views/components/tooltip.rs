@@ -0,0 +1,36 @@
+/// Plain text tooltip shown over hoverable cells
+use gpui::*;
+
+pub struct TextTooltip {
+    text: SharedString,
+    background: Hsla,
+    border: Hsla,
+    text_color: Hsla,
+}
+
+impl TextTooltip {
+    pub fn new(text: impl Into<SharedString>, background: Hsla, border: Hsla, text_color: Hsla) -> Self {
+        Self {
+            text: text.into(),
+            background,
+            border,
+            text_color,
+        }
+    }
+}
+
+impl Render for TextTooltip {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .px_2()
+            .py_1()
+            .bg(self.background)
+            .border_1()
+            .border_color(self.border)
+            .rounded_md()
+            .shadow_md()
+            .text_xs()
+            .text_color(self.text_color)
+            .child(self.text.clone())
+    }
+}
@@ -2,8 +2,10 @@
 // This is the top-level view that contains the entire application
 
 use gpui::*;
-// Unused FluentBuilder import removed
-use crate::app::actions::DashboardTab;
+use gpui::prelude::FluentBuilder;
+use crate::app::actions::{CopyVisibleData, DashboardTab, Export, ExportParseErrors, MarkAllSeen, ShareSummary};
+use crate::app::settings::{AppSettings, WindowGeometry};
+use crate::app::shortcuts::{self, ShortcutCommand};
 // Simple loading state enum for root view
 #[derive(Debug, Clone)]
 pub enum LoadingState {
@@ -12,12 +14,27 @@ pub enum LoadingState {
     Error(String),
 }
 use crate::analytics::aggregator::UsageAggregator;
-use crate::analytics::models::TimeRange;
+use crate::analytics::calculator::CostCalculator;
+use crate::analytics::export;
+use crate::analytics::models::{
+    CostThreshold, EntityDelta, ParseErrorLog, Period, PeriodDiff, ProjectSort, SessionSort, SessionSortColumn, TimeRange, TimelineGranularity, TokenCategory, TokenTotalPolicy, UsageEntry,
+    WeeklyUsage,
+};
 use crate::analytics::processor::UsageProcessor;
 use crate::analytics::{DailyUsage, ModelStats, ProjectStats, SessionStats, UsageStats};
+use crate::theme::settings::ThemeSettings;
 use crate::theme::ThemeRegistry;
-use std::collections::HashMap;
+use crate::utils::config::ConfigStore;
+use crate::utils::formatting::{self, NumberFormat};
+use crate::utils::logging;
+use crate::utils::system;
+use log::{debug, info, warn};
+use crate::app::views::components::tooltip::TextTooltip;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::rc::Rc;
 use std::sync::Arc;
+use std::time::Duration;
 use chrono::{DateTime, Local};
 
 #[derive(Debug, Clone, Copy)]
@@ -28,6 +45,13 @@ enum MetricType {
     Quaternary,
 }
 
+/// Card a right-click menu was opened on
+#[derive(Debug, Clone, PartialEq)]
+enum CardMenuTarget {
+    Project { path: String, name: String },
+    Model(String),
+}
+
 #[derive(Debug, Clone)]
 struct MonthlyUsage {
     month: String,
@@ -39,8 +63,57 @@ struct MonthlyUsage {
 
 // GPUI scrolling implementation using built-in overflow_scroll method
 
+/// Time range buttons in header order, with their short labels
+const TIME_RANGE_OPTIONS: [(&str, TimeRange); 6] = [
+    ("All Time", TimeRange::AllTime),
+    ("30D", TimeRange::Last30Days),
+    ("7D", TimeRange::Last7Days),
+    ("Yesterday", TimeRange::Yesterday),
+    ("Today", TimeRange::Today),
+    ("24H", TimeRange::Last24Hours),
+];
+
+/// Below this window width metric card rows wrap into a 2x2 grid
+const NARROW_LAYOUT_WIDTH: f32 = 900.0;
+/// Days in the trailing average drawn over the daily timeline
+const ROLLING_AVERAGE_DAYS: usize = 7;
+
+/// Width taken by everything in a timeline row besides its bar: label, statistics,
+/// gaps and the card and page padding
+const CHART_ROW_CHROME_WIDTH: f32 = 480.0;
+/// Bounds of the pixel span a full timeline bar covers
+const CHART_BAR_SPAN_RANGE: (f32, f32) = (160.0, 900.0);
+/// Bounds of a timeline bar's height, scaled from the window height
+const CHART_ROW_HEIGHT_RANGE: (f32, f32) = (16.0, 40.0);
+/// Bounds of the weekday chart's plot height, scaled from the window height
+const WEEKDAY_CHART_HEIGHT_RANGE: (f32, f32) = (80.0, 240.0);
+
+/// Smallest fill fraction drawn for a non-zero share so tiny rows stay visible
+const MIN_SHARE_BAR_FRACTION: f32 = 0.02;
+
+/// Trailing days averaged for the Overview's annualized run-rate
+const RUN_RATE_WINDOW_DAYS: i64 = 30;
+
+/// Rows in each Overview leaderboard
+const LEADERBOARD_SIZE: usize = 5;
+
+/// Period lengths (days) offered by the Timeline's period comparison
+const COMPARISON_PERIOD_OPTIONS: [i64; 2] = [7, 30];
+/// Rows shown per list in the period comparison
+const COMPARISON_ROWS: usize = 8;
+
+/// Row height of the Sessions table, fixed so the list can be virtualized
+const SESSION_TABLE_ROW_HEIGHT: f32 = 36.0;
+/// Rows shown before the Sessions table scrolls
+const SESSION_TABLE_VISIBLE_ROWS: usize = 12;
+
+/// Session notes longer than this are cut short on the session cards
+const SESSION_NOTE_PREVIEW_CHARS: usize = 80;
+
 pub struct RootView {
     focus_handle: FocusHandle,
+    /// Focus handles for keyboard-reachable controls, keyed by `control_focus_key`-style names
+    control_focus: HashMap<String, FocusHandle>,
     active_tab: DashboardTab,
     loading_message: String,
     analytics_data: Option<Arc<UsageStats>>,
@@ -48,36 +121,176 @@ pub struct RootView {
     loading_state: LoadingState,
     is_loading: bool,
     theme_registry: ThemeRegistry,
+    app_settings: AppSettings,
+    /// Window width from the latest render, used for responsive layout
+    viewport_width: Pixels,
+    /// Window height from the latest render, used to size charts
+    viewport_height: Pixels,
     current_time_range: TimeRange,
+    project_sort: ProjectSort,
+    /// Projects tab shows the side-by-side comparison instead of the list
+    compare_projects: bool,
+    /// Project paths picked for the left and right comparison columns
+    compared_projects: [Option<String>; 2],
+    /// Open right-click menu and where it was opened
+    card_menu: Option<(CardMenuTarget, Point<Pixels>)>,
+    /// Startup pricing audit: every model id and whether it is priced
+    model_audit: Option<Vec<(String, bool)>>,
+    /// Filtered total cost (and the range it was for) when each tab was last left, this session only
+    tab_cost_snapshots: HashMap<DashboardTab, (TimeRange, f64)>,
+    /// The "Other" rows of the model breakdown and project list are expanded
+    show_other_models: bool,
+    show_other_projects: bool,
+    /// First-run welcome guide is open
+    show_onboarding: bool,
+    /// Keyboard shortcut list opened with "?"
+    show_shortcut_overlay: bool,
+    group_sessions_by_project: bool,
+    /// Sessions tab shows the sortable table instead of the timeline cards
+    show_session_table: bool,
+    session_sort: SessionSort,
+    /// Text typed into the Sessions table filter
+    session_filter: String,
+    session_filter_focus: FocusHandle,
+    /// Leave models without requests in the current range off the Models tab
+    hide_inactive_models: bool,
+    /// Models tab shows each model's blended cost per 1K tokens instead of its total cost
+    show_cost_per_thousand: bool,
+    /// Session the Sessions tab is narrowed to, set by "Go to session", as (project key, session id)
+    /// since the same id can recur across projects
+    selected_session: Option<(String, String)>,
+    /// Text typed into the "Go to session" header field
+    session_query: String,
+    session_query_focus: FocusHandle,
+    /// Note being typed for the selected session; None while the note isn't being edited
+    session_note_draft: Option<String>,
+    /// Request of the selected session whose raw JSON is shown in the inspector
+    inspected_entry: Option<UsageEntry>,
+    session_note_focus: FocusHandle,
+    sessions_scroll: ScrollHandle,
+    models_scroll: ScrollHandle,
+    projects_scroll: ScrollHandle,
+    collapsed_session_groups: HashSet<String>,
+    hidden_token_categories: HashSet<TokenCategory>,
+    timeline_granularity: TimelineGranularity,
+    /// Length in days of each side of the Timeline's period comparison
+    comparison_days: i64,
     refresh_task: Option<Task<()>>,
+    /// Window bounds not yet written to settings, and the debounced task that writes them
+    pending_window_geometry: Option<WindowGeometry>,
+    window_geometry_task: Option<Task<()>>,
     last_refresh: Option<DateTime<Local>>,
     is_refreshing: bool,
+    toast_message: Option<String>,
+    toast_task: Option<Task<()>>,
+    show_export_menu: bool,
+    show_auto_refresh_menu: bool,
+    show_debug_panel: bool,
+    show_settings_panel: bool,
+    show_default_tab_menu: bool,
+    confirm_settings_reset: bool,
+    /// Set once the over-budget notification has fired; cleared when spend drops back under budget
+    budget_alert_sent: bool,
 }
 
 impl Drop for RootView {
     fn drop(&mut self) {
-        if let Some(task) = self.refresh_task.take() {
-            task.detach();
-        }
+        // Dropping the task cancels the auto-refresh timer when the window goes away
+        self.refresh_task.take();
+        // Closing mid-debounce still records where the window was
+        self.window_geometry_task.take();
+        self.save_pending_window_geometry();
     }
 }
 
 impl RootView {
     pub fn set_active_tab(&mut self, tab: DashboardTab, cx: &mut Context<Self>) {
         if self.active_tab != tab {
-            println!("🔄 Switching to tab: {:?}", tab);
+            debug!("🔄 Switching to tab: {:?}", tab);
+            if let Some(ref data) = self.analytics_data {
+                self.tab_cost_snapshots
+                    .insert(self.active_tab.clone(), (self.current_time_range, data.total_cost));
+            }
             self.active_tab = tab;
+            if self.app_settings.remember_last_tab {
+                self.app_settings.last_tab = Some(self.active_tab.clone());
+                self.save_settings(cx);
+            }
             cx.notify();
         }
     }
 
+    /// Spend added since `tab` was last left, if it grew; the active tab never has a delta
+    fn tab_spend_delta(&self, tab: &DashboardTab) -> Option<f64> {
+        if *tab == self.active_tab {
+            return None;
+        }
+        let (range, snapshot) = self.tab_cost_snapshots.get(tab)?;
+        // A different time range changes the total without any new spend
+        if *range != self.current_time_range {
+            return None;
+        }
+        let delta = self.analytics_data.as_ref()?.total_cost - snapshot;
+        (delta >= 0.005).then_some(delta)
+    }
+
+    fn has_unseen_spend(&self) -> bool {
+        self.app_settings.shown_tabs().iter().any(|tab| self.tab_spend_delta(tab).is_some())
+    }
+
+    /// Take the current total as every tab's baseline, clearing all spend badges at once
+    pub fn mark_all_tabs_seen(&mut self, cx: &mut Context<Self>) {
+        let Some(ref data) = self.analytics_data else {
+            return;
+        };
+        let snapshot = (self.current_time_range, data.total_cost);
+        for tab in DashboardTab::all() {
+            self.tab_cost_snapshots.insert(tab, snapshot);
+        }
+        debug!("👀 Marked all tabs as seen at {}", self.format_cost(snapshot.1));
+        cx.notify();
+    }
+
+    pub fn set_default_tab(&mut self, tab: DashboardTab, cx: &mut Context<Self>) {
+        self.app_settings.default_tab = tab;
+        self.show_default_tab_menu = false;
+        self.save_settings(cx);
+        cx.notify();
+    }
+
+    /// Show or hide a tab in the navigation, moving off it when it is hidden
+    pub fn toggle_tab_visibility(&mut self, tab: DashboardTab, cx: &mut Context<Self>) {
+        if !self.app_settings.toggle_tab_visibility(&tab) {
+            self.show_toast("At least one tab has to stay visible", cx);
+            return;
+        }
+        info!("🗂️ Visible tabs: {:?}", self.app_settings.visible_tabs);
+        self.save_settings(cx);
+        let shown = self.app_settings.shown_tabs();
+        if !shown.contains(&self.active_tab) {
+            self.set_active_tab(shown[0].clone(), cx);
+        }
+        cx.notify();
+    }
+
+    pub fn toggle_remember_last_tab(&mut self, cx: &mut Context<Self>) {
+        self.app_settings.remember_last_tab = !self.app_settings.remember_last_tab;
+        self.app_settings.last_tab = self
+            .app_settings
+            .remember_last_tab
+            .then(|| self.active_tab.clone());
+        self.save_settings(cx);
+        cx.notify();
+    }
+
     pub fn toggle_theme(&mut self, cx: &mut Context<Self>) {
         if let Err(e) = self.theme_registry.toggle_mode() {
-            println!("⚠️ Failed to toggle theme: {}", e);
+            warn!("⚠️ Failed to toggle theme: {}", e);
         } else {
-            println!("🎨 Theme toggled to: {:?}", self.theme_registry.mode());
+            info!("🎨 Theme toggled to: {:?}", self.theme_registry.mode());
             cx.notify();
         }
+        self.show_config_notice(cx);
     }
 
     // Fast filtering method that works on cached data
@@ -86,21 +299,80 @@ impl RootView {
             let start = std::time::Instant::now();
 
             // Filter entries based on time range
-            let aggregator = UsageAggregator::new();
-            let filtered_entries =
-                aggregator.filter_by_time_range(&full_data.entries, self.current_time_range);
-
+            let aggregator = UsageAggregator::with_options(self.app_settings.aggregation_options());
             // For now, recalculate stats from filtered entries
             // TODO: In future, we could pre-calculate stats for each time range
-            let filtered_stats = aggregator.calculate_usage_stats(&filtered_entries);
+            let filtered_stats =
+                aggregator.stats_for_range(&full_data.entries, self.current_time_range);
 
             self.analytics_data = Some(Arc::new(filtered_stats));
 
             let elapsed = start.elapsed();
-            println!("⚡ Time filter applied in {:?}", elapsed);
+            debug!("⚡ Time filter applied in {:?}", elapsed);
         }
     }
 
+    fn tab_focus_key(tab: &DashboardTab) -> String {
+        format!("tab:{:?}", tab)
+    }
+
+    fn time_range_focus_key(range: TimeRange) -> String {
+        format!("range:{:?}", range)
+    }
+
+    const THEME_TOGGLE_FOCUS_KEY: &str = "theme-toggle";
+
+    /// Create focus handles for tabs, time ranges and the theme toggle, in tab order
+    fn create_control_focus_handles(cx: &mut Context<Self>) -> HashMap<String, FocusHandle> {
+        let keys: Vec<String> = DashboardTab::all()
+            .iter()
+            .map(Self::tab_focus_key)
+            .chain(TIME_RANGE_OPTIONS.iter().map(|(_, range)| Self::time_range_focus_key(*range)))
+            .chain(std::iter::once(Self::THEME_TOGGLE_FOCUS_KEY.to_string()))
+            .collect();
+
+        keys.into_iter()
+            .enumerate()
+            .map(|(index, key)| {
+                let handle = cx.focus_handle().tab_index(index as isize + 1).tab_stop(true);
+                (key, handle)
+            })
+            .collect()
+    }
+
+    /// Make a control keyboard-focusable with an accent focus ring; Space/Enter run `on_activate`
+    fn focusable_control<E: InteractiveElement + Styled>(
+        &self,
+        element: E,
+        key: &str,
+        cx: &mut Context<Self>,
+        on_activate: impl Fn(&mut RootView, &mut Context<RootView>) + 'static,
+    ) -> E {
+        let Some(handle) = self.control_focus.get(key) else {
+            return element;
+        };
+        let ring_color = self.theme_registry.colors().text_accent;
+
+        element
+            .track_focus(handle)
+            .focus(move |style| {
+                style.shadow(vec![BoxShadow {
+                    color: ring_color,
+                    offset: point(px(0.0), px(0.0)),
+                    blur_radius: px(0.0),
+                    spread_radius: px(2.0),
+                }])
+            })
+            .on_key_down(cx.listener(
+                move |view: &mut RootView, event: &KeyDownEvent, _window, cx| {
+                    if matches!(event.keystroke.key.as_str(), "space" | "enter") {
+                        on_activate(view, cx);
+                        cx.stop_propagation();
+                    }
+                },
+            ))
+    }
+
     fn render_time_range_filter(&self, cx: &mut Context<Self>) -> impl IntoElement {
         let theme = self.theme_registry.colors();
 
@@ -113,9 +385,11 @@ impl RootView {
             .border_1()
             .border_color(theme.border)
             .rounded_md()
-            .child(self.render_time_range_button("All Time", TimeRange::AllTime, cx))
-            .child(self.render_time_range_button("30D", TimeRange::Last30Days, cx))
-            .child(self.render_time_range_button("7D", TimeRange::Last7Days, cx))
+            .children(
+                TIME_RANGE_OPTIONS
+                    .iter()
+                    .map(|(label, range)| self.render_time_range_button(label, *range, cx)),
+            )
     }
 
     fn render_time_range_button(
@@ -129,7 +403,7 @@ impl RootView {
         let label_string = label.to_string();
         let elevated_surface = theme.elevated_surface;
 
-        div()
+        let button = div()
             .px_3()
             .py_1()
             .text_xs()
@@ -169,21 +443,25 @@ impl RootView {
                     view.set_time_range(range, cx);
                 }),
             )
-            .child(label_string)
+            .child(label_string);
+
+        self.focusable_control(button, &Self::time_range_focus_key(range), cx, move |view, cx| {
+            view.set_time_range(range, cx);
+        })
     }
 
     pub fn set_time_range(&mut self, range: TimeRange, cx: &mut Context<Self>) {
-        println!(
+        debug!(
             "🎯 set_time_range called: current={:?}, new={:?}",
             self.current_time_range, range
         );
         if self.current_time_range != range {
-            println!("🔄 Switching to time range: {:?}", range);
+            debug!("🔄 Switching to time range: {:?}", range);
             self.current_time_range = range;
             self.apply_time_filter(); // Use fast filtering instead of full reload
             cx.notify();
         } else {
-            println!("⚠️ Time range is already set to {:?}, skipping", range);
+            debug!("⚠️ Time range is already set to {:?}, skipping", range);
         }
     }
 
@@ -245,7 +523,7 @@ impl RootView {
         let elevated_surface = colors.elevated_surface;
         let border_color = colors.border;
 
-        div()
+        let toggle = div()
             .id("theme-toggle")
             .flex()
             .items_center()
@@ -270,583 +548,3702 @@ impl RootView {
                     .text_size(px(14.0))
                     .text_color(colors.text)
                     .child(if is_dark { "🌙" } else { "☀️" }),
-            )
+            );
+
+        self.focusable_control(toggle, Self::THEME_TOGGLE_FOCUS_KEY, cx, |view, cx| {
+            view.toggle_theme(cx);
+        })
     }
 
-    pub fn new(cx: &mut Context<Self>) -> Self {
-        let mut view = Self {
-            focus_handle: cx.focus_handle(),
-            active_tab: DashboardTab::Overview,
-            loading_message: "Loading analytics data...".to_string(),
-            analytics_data: None,
-            full_analytics_data: None,
-            loading_state: LoadingState::LoadingInitial,
-            is_loading: true,
-            theme_registry: ThemeRegistry::new(),
-            current_time_range: TimeRange::Last30Days,
-            refresh_task: None,
-            last_refresh: None,
-            is_refreshing: false,
+    /// Save window bounds once they stop changing, so dragging doesn't rewrite settings on every frame
+    fn schedule_window_geometry_save(&mut self, bounds: Bounds<Pixels>, cx: &mut Context<Self>) {
+        self.pending_window_geometry = Some(WindowGeometry::from_bounds(bounds));
+        // Replacing the task cancels the save scheduled by the previous bounds change
+        self.window_geometry_task = Some(cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(Duration::from_millis(500)).await;
+            let _ = this.update(cx, |view, cx| {
+                view.save_pending_window_geometry();
+                view.show_config_notice(cx);
+            });
+        }));
+    }
+
+    fn save_pending_window_geometry(&mut self) {
+        if let Some(geometry) = self.pending_window_geometry.take() {
+            self.app_settings.window_geometry = Some(geometry);
+            if let Err(e) = self.app_settings.save() {
+                warn!("⚠️ Failed to save window bounds: {}", e);
+            }
+        }
+    }
+
+    /// Write settings to disk, telling the user if they could only be kept in memory
+    fn save_settings(&mut self, cx: &mut Context<Self>) {
+        if let Err(e) = self.app_settings.save() {
+            warn!("⚠️ Failed to save settings: {}", e);
+        }
+        self.show_config_notice(cx);
+    }
+
+    /// Tell the user once if the config directory turned out to be read-only
+    fn show_config_notice(&mut self, cx: &mut Context<Self>) {
+        if let Some(notice) = ConfigStore::global().take_notice() {
+            self.show_toast(notice, cx);
+        }
+    }
+
+    /// Show a transient confirmation message that clears itself after a short delay
+    fn show_toast(&mut self, message: impl Into<String>, cx: &mut Context<Self>) {
+        self.toast_message = Some(message.into());
+        // Replacing the task cancels any pending dismissal from a previous toast
+        self.toast_task = Some(cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(Duration::from_secs(2)).await;
+            let _ = this.update(cx, |view, cx| {
+                view.toast_message = None;
+                cx.notify();
+            });
+        }));
+        cx.notify();
+    }
+
+    fn copy_project_path(&mut self, project_path: String, cx: &mut Context<Self>) {
+        cx.write_to_clipboard(ClipboardItem::new_string(project_path.clone()));
+        info!("📋 Copied project path: {}", project_path);
+        self.show_toast(format!("Copied {}", project_path), cx);
+    }
+
+    /// Export the raw entries of a single project or session to CSV
+    fn export_card_entries(
+        &mut self,
+        project_path: String,
+        session_id: Option<String>,
+        name: String,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(ref data) = self.analytics_data else {
+            self.show_toast("No data loaded to export", cx);
+            return;
+        };
+        let entries = match session_id {
+            Some(ref session_id) => export::entries_for_session(&data.entries, &project_path, session_id),
+            None => export::entries_for_project(&data.entries, &project_path),
         };
+        if entries.is_empty() {
+            self.show_toast("No entries to export", cx);
+            return;
+        }
 
-        // Focus will be handled by the window system when the view is rendered
+        let file_name = export::suggested_file_name(&name, "csv");
+        match export::write_export(&file_name, &export::export_entries_csv(&entries, self.app_settings.parsed_timezone())) {
+            Ok(path) => {
+                info!("💾 Exported {} entries to {:?}", entries.len(), path);
+                self.show_toast(format!("Exported {} entries to {}", entries.len(), path.display()), cx);
+            }
+            Err(e) => {
+                warn!("⚠️ Export failed: {}", e);
+                self.show_toast(format!("Export failed: {}", e), cx);
+            }
+        }
+    }
 
-        // Load data synchronously on initialization
-        view.load_data_synchronously();
-        
-        // Start periodic refresh
-        view.start_periodic_refresh(cx);
-        
-        view
+    /// Write every loaded (deduplicated) entry to a single JSON Lines archive
+    fn export_merged_jsonl(&mut self, cx: &mut Context<Self>) {
+        let Some(ref data) = self.full_analytics_data else {
+            self.show_toast("No data loaded to export", cx);
+            return;
+        };
+        let entry_count = data.entries.len();
+        let contents = export::export_merged_jsonl(&data.entries);
+
+        match export::write_export(&export::suggested_file_name("merged", "jsonl"), &contents) {
+            Ok(path) => {
+                info!("💾 Exported {} merged entries to {:?}", entry_count, path);
+                self.show_toast(format!("Exported {} entries to {}", entry_count, path.display()), cx);
+            }
+            Err(e) => {
+                warn!("⚠️ Export failed: {}", e);
+                self.show_toast(format!("Export failed: {}", e), cx);
+            }
+        }
     }
 
-    fn load_data_synchronously(&mut self) {
-        println!("🔄 Starting synchronous analytics data loading...");
+    /// Write every line the last load skipped, with its file, line number and reason, to parse-errors.log
+    pub fn export_parse_errors(&mut self, cx: &mut Context<Self>) {
+        let Some(ref data) = self.full_analytics_data else {
+            self.show_toast("No data loaded yet", cx);
+            return;
+        };
+        if data.parse_errors.is_empty() {
+            self.show_toast("No parse errors in the last load", cx);
+            return;
+        }
+        let error_count = data.parse_errors.total();
+        let contents = export::parse_errors_log(&data.parse_errors);
 
-        // Load full data once
-        match Self::load_analytics_data_sync() {
-            Ok(stats) => {
-                println!(
-                    "✅ Real analytics data loaded successfully with {} entries",
-                    stats.entries.len()
-                );
-                self.full_analytics_data = Some(Arc::new(stats));
-                // Apply initial filter
-                self.apply_time_filter();
-                self.loading_state = LoadingState::LoadedFull;
-                self.loading_message = "Dashboard ready - real data loaded".to_string();
-                self.is_loading = false;
+        match export::write_export(export::PARSE_ERRORS_FILE_NAME, &contents) {
+            Ok(path) => {
+                info!("💾 Exported {} parse errors to {:?}", error_count, path);
+                self.show_toast(format!("Exported {} parse errors to {}", error_count, path.display()), cx);
             }
             Err(e) => {
-                println!("⚠️ Failed to load real data: {}, using sample data", e);
-                self.loading_state = LoadingState::LoadedFull;
-                self.loading_message = "Dashboard ready - using sample data".to_string();
-                self.is_loading = false;
-                // analytics_data remains None, will use sample data
+                warn!("⚠️ Export failed: {}", e);
+                self.show_toast(format!("Export failed: {}", e), cx);
             }
         }
-        
-        self.last_refresh = Some(Local::now());
     }
 
-    fn start_periodic_refresh(&mut self, _cx: &mut Context<Self>) {
-        // For now, let's implement a simpler version without the async refresh
-        // We'll need to find the right GPUI pattern for this
-        println!("📊 Auto-refresh enabled - data will refresh every 30 seconds");
-        
-        // TODO: Implement proper async refresh once we understand the GPUI API better
-        // The refresh_data method is ready to be called manually via the button for now
+    /// Settings that change which usage counts, as shown alongside shared totals
+    fn active_filter_labels(&self) -> Vec<String> {
+        let settings = &self.app_settings;
+        let mut filters = Vec::new();
+        if let Some(label) = self.analysis_start_label() {
+            filters.push(format!("Since {}", label));
+        }
+        if settings.quiet_hours_enabled {
+            filters.push(format!("Quiet hours {} excluded", settings.quiet_hours.label()));
+        }
+        if !settings.excluded_projects.is_empty() {
+            filters.push(format!("{} projects excluded", settings.excluded_projects.len()));
+        }
+        if !settings.excluded_models.is_empty() {
+            filters.push(format!("{} models excluded", settings.excluded_models.len()));
+        }
+        if settings.ignore_cache {
+            filters.push("Cache tokens ignored".to_string());
+        }
+        filters
     }
-    
-    fn refresh_data(&mut self, cx: &mut Context<Self>) {
-        if self.is_refreshing {
-            return; // Already refreshing
+
+    /// Copy the Overview's totals, with the range and filters behind them, as shareable text.
+    /// GPUI has no offscreen capture to turn the view into a PNG, so text is what gets shared.
+    pub fn share_summary(&mut self, cx: &mut Context<Self>) {
+        if self.is_loading {
+            return;
         }
-        
-        println!("🔄 Refreshing analytics data...");
-        self.is_refreshing = true;
-        
-        // Load new data
-        match Self::load_analytics_data_sync() {
-            Ok(stats) => {
-                println!("✅ Data refreshed successfully with {} entries", stats.entries.len());
-                self.full_analytics_data = Some(Arc::new(stats));
-                self.apply_time_filter();
-                self.last_refresh = Some(Local::now());
+        let Some(analytics) = self.analytics_data.clone() else {
+            self.show_toast("No data loaded to share", cx);
+            return;
+        };
+
+        let summary = export::overview_summary(&analytics, self.current_time_range.label(), &self.active_filter_labels());
+        cx.write_to_clipboard(ClipboardItem::new_string(summary));
+        info!("📋 Copied the {} summary", self.current_time_range.label());
+        self.show_toast("Summary copied as text (image export isn't supported)", cx);
+    }
+
+    /// Write the current range's per-day totals, including empty days, to CSV
+    fn export_daily_csv(&mut self, cx: &mut Context<Self>) {
+        let analytics = self.get_analytics_data();
+        let daily = analytics.daily_usage.values().cloned().collect::<Vec<_>>();
+        if daily.is_empty() {
+            self.show_toast("No daily usage to export", cx);
+            return;
+        }
+
+        match export::write_export(&export::suggested_file_name("daily", "csv"), &export::export_daily_csv(&daily)) {
+            Ok(path) => {
+                info!("💾 Exported daily usage to {:?}", path);
+                self.show_toast(format!("Exported daily usage to {}", path.display()), cx);
             }
             Err(e) => {
-                println!("⚠️ Failed to refresh data: {}", e);
+                warn!("⚠️ Export failed: {}", e);
+                self.show_toast(format!("Export failed: {}", e), cx);
             }
         }
-        
-        self.is_refreshing = false;
-        cx.notify();
     }
 
-    fn load_analytics_data_sync() -> anyhow::Result<UsageStats> {
-        // Use the existing analytics processor
-        let processor = UsageProcessor::new()?;
-        let entries = processor.process_all_files()?;
+    /// Write the active tab's data in the default export format (bound to Cmd+E)
+    pub fn export_current_view(&mut self, cx: &mut Context<Self>) {
+        if self.is_loading {
+            return;
+        }
+        let Some(analytics) = self.analytics_data.clone() else {
+            self.show_toast("No data loaded to export", cx);
+            return;
+        };
 
-        println!("📊 Processing {} usage entries...", entries.len());
+        let format = self.app_settings.default_export_format;
+        let contents = match (&self.active_tab, format) {
+            // The CSV timeline export already fills in days without usage
+            (DashboardTab::Timeline, export::ExportFormat::Csv) => {
+                export::export_daily_csv(&analytics.daily_usage.values().cloned().collect::<Vec<_>>())
+            }
+            _ => self.visible_table(&analytics).render(format),
+        };
 
-        let aggregator = UsageAggregator::new();
-        let stats = aggregator.aggregate_entries(entries);
+        let name = self.active_tab.title().to_lowercase();
+        match export::write_export(&export::suggested_file_name(&name, format.extension()), &contents) {
+            Ok(path) => {
+                info!("💾 Exported {} view as {} to {:?}", name, format.label(), path);
+                self.show_toast(format!("Exported {} to {}", self.active_tab.title(), path.display()), cx);
+            }
+            Err(e) => {
+                warn!("⚠️ Export failed: {}", e);
+                self.show_toast(format!("Export failed: {}", e), cx);
+            }
+        }
+    }
 
-        println!("✅ Analytics computation complete");
-        Ok(stats)
+    /// Rows of the active tab in the order (and with the filters) they're shown in
+    fn visible_table(&self, analytics: &UsageStats) -> export::ExportTable {
+        match self.active_tab {
+            DashboardTab::Overview | DashboardTab::Models => export::models_table(analytics),
+            DashboardTab::Projects => {
+                let mut projects = analytics.project_stats.values().cloned().collect::<Vec<_>>();
+                UsageAggregator::new().sort_projects(&mut projects, self.project_sort);
+                export::project_rows(&projects)
+            }
+            DashboardTab::Sessions => {
+                export::session_rows(&self.displayed_sessions(), self.app_settings.parsed_timezone())
+            }
+            DashboardTab::Timeline => {
+                export::daily_table(&analytics.daily_usage.values().cloned().collect::<Vec<_>>())
+            }
+        }
     }
 
-    /// Get analytics data - real data if loaded, sample data as fallback
-    fn get_analytics_data(&self) -> UsageStats {
-        if let Some(ref real_data) = self.analytics_data {
-            println!(
-                "🔍 Using real analytics data: ${:.2} total, {} tokens, {} models, {} projects",
-                real_data.total_cost,
-                real_data.total_tokens,
-                real_data.model_stats.len(),
-                real_data.project_stats.len()
-            );
-            (**real_data).clone()
-        } else {
-            println!("⚠️ Falling back to sample data - real data not loaded");
-            self.get_sample_analytics()
+    /// Copy the active tab's rows as tab-separated values for pasting into a spreadsheet
+    pub fn copy_visible_data(&mut self, cx: &mut Context<Self>) {
+        if self.is_loading {
+            return;
         }
+        let Some(analytics) = self.analytics_data.clone() else {
+            self.show_toast("No data loaded to copy", cx);
+            return;
+        };
+
+        let table = self.visible_table(&analytics);
+        let row_count = table.rows.len();
+        cx.write_to_clipboard(ClipboardItem::new_string(table.to_tsv()));
+        info!("📋 Copied {} {} rows as TSV", row_count, self.active_tab.title());
+        self.show_toast(format!("Copied {} rows from {}", row_count, self.active_tab.title()), cx);
     }
 
-    /// Generate sample analytics data for demonstration (fallback when real data not loaded)
-    fn get_sample_analytics(&self) -> UsageStats {
-        let mut model_stats = HashMap::new();
+    pub fn cycle_log_verbosity(&mut self, cx: &mut Context<Self>) {
+        self.app_settings.log_verbosity = self.app_settings.log_verbosity.next();
+        logging::set_verbosity(self.app_settings.log_verbosity);
+        self.save_settings(cx);
+        cx.notify();
+    }
 
-        // Claude 3.5 Sonnet
-        model_stats.insert(
-            "claude-3-5-sonnet-20241022".to_string(),
-            ModelStats {
-                model: "claude-3-5-sonnet-20241022".to_string(),
-                display_name: "Claude 3.5 Sonnet".to_string(),
-                total_cost: 12.45,
-                total_tokens: 125000,
-                input_tokens: 75000,
-                output_tokens: 35000,
-                cache_read_tokens: 10000,
-                cache_creation_tokens: 5000,
-                request_count: 156,
-            },
-        );
+    pub fn cycle_default_export_format(&mut self, cx: &mut Context<Self>) {
+        self.app_settings.default_export_format = self.app_settings.default_export_format.next();
+        self.save_settings(cx);
+        cx.notify();
+    }
 
-        // Claude 3 Opus
-        model_stats.insert(
-            "claude-3-opus-20240229".to_string(),
-            ModelStats {
-                model: "claude-3-opus-20240229".to_string(),
-                display_name: "Claude 3 Opus".to_string(),
-                total_cost: 8.72,
-                total_tokens: 42000,
-                input_tokens: 28000,
-                output_tokens: 12000,
-                cache_read_tokens: 1500,
-                cache_creation_tokens: 500,
-                request_count: 47,
-            },
-        );
+    /// Copy the breakdown for the active tab (projects or models) as a Markdown table
+    fn copy_markdown_table(&mut self, cx: &mut Context<Self>) {
+        let analytics = self.get_analytics_data();
+        let (table, kind) = match self.active_tab {
+            DashboardTab::Projects | DashboardTab::Sessions => {
+                (export::export_projects_markdown(&analytics), "Project")
+            }
+            _ => (export::export_models_markdown(&analytics), "Model"),
+        };
+        cx.write_to_clipboard(ClipboardItem::new_string(table));
+        self.show_toast(format!("{} table copied as Markdown", kind), cx);
+    }
 
-        // Claude 3 Haiku
-        model_stats.insert(
-            "claude-3-haiku-20240307".to_string(),
-            ModelStats {
-                model: "claude-3-haiku-20240307".to_string(),
-                display_name: "Claude 3 Haiku".to_string(),
-                total_cost: 2.31,
-                total_tokens: 89000,
-                input_tokens: 65000,
-                output_tokens: 20000,
-                cache_read_tokens: 3000,
-                cache_creation_tokens: 1000,
-                request_count: 203,
-            },
-        );
+    pub fn toggle_export_menu(&mut self, cx: &mut Context<Self>) {
+        self.show_export_menu = !self.show_export_menu;
+        self.show_auto_refresh_menu = false;
+        cx.notify();
+    }
 
-        let mut project_stats = HashMap::new();
-        project_stats.insert(
-            "/Users/dev/rust-project".to_string(),
-            ProjectStats {
-                project_name: "rust-project".to_string(),
-                project_path: "/Users/dev/rust-project".to_string(),
-                total_cost: 15.23,
-                total_tokens: 145000,
-                input_tokens: 95000,
-                output_tokens: 35000,
-                cache_read_tokens: 10000,
-                cache_creation_tokens: 5000,
-                request_count: 198,
-                session_count: 12,
-                last_used: chrono::Utc::now(),
-            },
-        );
+    pub fn toggle_auto_refresh_menu(&mut self, cx: &mut Context<Self>) {
+        self.show_auto_refresh_menu = !self.show_auto_refresh_menu;
+        self.show_export_menu = false;
+        cx.notify();
+    }
 
-        UsageStats {
-            total_cost: 23.48,
-            total_input_tokens: 168000,
-            total_output_tokens: 67000,
-            total_cache_read_tokens: 14500,
-            total_cache_creation_tokens: 6500,
-            total_tokens: 256000,
-            session_count: 15,
-            entries: vec![], // Empty for demo
-            model_stats,
-            project_stats,
-            session_stats: HashMap::new(),
-            daily_usage: HashMap::new(),
+    fn auto_refresh_label(secs: Option<u64>) -> String {
+        match secs {
+            None | Some(0) => "Off".to_string(),
+            Some(secs) if secs % 60 == 0 => format!("{}m", secs / 60),
+            Some(secs) => format!("{}s", secs),
         }
     }
 
-    /// Get sessions data - real data if loaded, sample data as fallback
-    fn get_sessions_data(&self) -> Vec<SessionStats> {
-        if let Some(ref real_data) = self.analytics_data {
-            // Extract sessions from real analytics data
-            real_data
-                .session_stats
-                .values()
-                .cloned()
-                .collect::<Vec<_>>()
+    fn render_auto_refresh_button(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let colors = self.theme_registry.colors();
+        let elevated_surface = colors.elevated_surface;
+        let border_color = colors.border;
+
+        div()
+            .id("auto-refresh-button")
+            .flex()
+            .items_center()
+            .justify_center()
+            .px_2()
+            .h(px(32.0))
+            .bg(if self.show_auto_refresh_menu {
+                colors.elevated_surface
+            } else {
+                colors.surface
+            })
+            .border_1()
+            .border_color(colors.border)
+            .rounded(px(6.0))
+            .cursor_pointer()
+            .hover(move |style| style.bg(elevated_surface))
+            .active(move |style| style.bg(border_color))
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(|view: &mut RootView, _event, _window, cx| {
+                    view.toggle_auto_refresh_menu(cx);
+                }),
+            )
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(colors.text_muted)
+                    .child(format!(
+                        "Auto: {}",
+                        Self::auto_refresh_label(self.app_settings.auto_refresh_secs)
+                    )),
+            )
+    }
+
+    /// Auto-refresh interval dropdown, rendered at the root so it layers above the content
+    fn render_auto_refresh_menu(&self, cx: &mut Context<Self>) -> Div {
+        const INTERVALS: [Option<u64>; 5] = [None, Some(30), Some(60), Some(300), Some(900)];
+
+        let theme = self.theme_registry.colors();
+        if !self.show_auto_refresh_menu {
+            return div();
+        }
+
+        div()
+            .absolute()
+            .top(px(64.0))
+            .right(px(248.0))
+            .w(px(120.0))
+            .p_1()
+            .flex()
+            .flex_col()
+            .bg(theme.elevated_surface)
+            .border_1()
+            .border_color(theme.border)
+            .rounded_md()
+            .shadow_md()
+            .children(INTERVALS.into_iter().map(|secs| {
+                self.render_menu_item(Self::auto_refresh_label(secs), cx, move |view, cx| {
+                    view.set_auto_refresh(secs, cx);
+                })
+            }))
+    }
+
+    fn render_export_button(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let colors = self.theme_registry.colors();
+        let elevated_surface = colors.elevated_surface;
+        let border_color = colors.border;
+
+        div()
+            .id("export-button")
+            .flex()
+            .items_center()
+            .justify_center()
+            .w(px(40.0))
+            .h(px(32.0))
+            .bg(if self.show_export_menu {
+                colors.elevated_surface
+            } else {
+                colors.surface
+            })
+            .border_1()
+            .border_color(colors.border)
+            .rounded(px(6.0))
+            .cursor_pointer()
+            .hover(move |style| style.bg(elevated_surface))
+            .active(move |style| style.bg(border_color))
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(|view: &mut RootView, _event, _window, cx| {
+                    view.toggle_export_menu(cx);
+                }),
+            )
+            .child(
+                div()
+                    .text_size(px(14.0))
+                    .text_color(colors.text)
+                    .child("⬇"),
+            )
+    }
+
+    fn render_menu_item(
+        &self,
+        label: impl Into<SharedString>,
+        cx: &mut Context<Self>,
+        on_select: impl Fn(&mut RootView, &mut Context<RootView>) + 'static,
+    ) -> Div {
+        let theme = self.theme_registry.colors();
+        let surface = theme.surface;
+
+        div()
+            .px_3()
+            .py_2()
+            .text_sm()
+            .text_color(theme.text)
+            .rounded_sm()
+            .cursor_pointer()
+            .hover(move |style| style.bg(surface))
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(move |view: &mut RootView, _event, _window, cx| {
+                    view.show_export_menu = false;
+                    view.show_auto_refresh_menu = false;
+                    view.card_menu = None;
+                    on_select(view, cx);
+                    cx.notify();
+                }),
+            )
+            .child(label.into())
+    }
+
+    /// Export options dropdown, rendered at the root so it layers above the content
+    fn render_export_menu(&self, cx: &mut Context<Self>) -> Div {
+        let theme = self.theme_registry.colors();
+        if !self.show_export_menu {
+            return div();
+        }
+
+        div()
+            .absolute()
+            .top(px(64.0))
+            .right(px(136.0))
+            .w(px(240.0))
+            .p_1()
+            .flex()
+            .flex_col()
+            .bg(theme.elevated_surface)
+            .border_1()
+            .border_color(theme.border)
+            .rounded_md()
+            .shadow_md()
+            .child(self.render_menu_item(
+                format!("This view as {}  ⌘E", self.app_settings.default_export_format.label()),
+                cx,
+                |view, cx| view.export_current_view(cx),
+            ))
+            .child(self.render_menu_item("Share summary (copies text)  S", cx, |view, cx| {
+                view.share_summary(cx);
+            }))
+            .child(self.render_menu_item("Merged JSONL (all entries)", cx, |view, cx| {
+                view.export_merged_jsonl(cx);
+            }))
+            .when_some(
+                self.full_analytics_data.as_ref().map(|data| data.parse_errors.total()).filter(|&count| count > 0),
+                |menu, count| {
+                    menu.child(self.render_menu_item(format!("Parse errors log ({})", count), cx, |view, cx| {
+                        view.export_parse_errors(cx);
+                    }))
+                },
+            )
+            .when(self.active_tab == DashboardTab::Timeline, |menu| {
+                menu.child(self.render_menu_item("Daily CSV (timeline)", cx, |view, cx| {
+                    view.export_daily_csv(cx);
+                }))
+            })
+            .child(self.render_menu_item(
+                match self.active_tab {
+                    DashboardTab::Projects | DashboardTab::Sessions => {
+                        "Copy projects as Markdown"
+                    }
+                    _ => "Copy models as Markdown",
+                },
+                cx,
+                |view, cx| view.copy_markdown_table(cx),
+            ))
+    }
+
+    fn open_card_menu(&mut self, target: CardMenuTarget, position: Point<Pixels>, cx: &mut Context<Self>) {
+        self.card_menu = Some((target, position));
+        cx.notify();
+    }
+
+    /// Right-click menu for project and model cards, drawn where it was opened
+    fn render_card_menu(&self, cx: &mut Context<Self>) -> Div {
+        let theme = self.theme_registry.colors();
+        let Some((ref target, position)) = self.card_menu else {
+            return div();
+        };
+
+        let menu = div()
+            .absolute()
+            .left(position.x)
+            .top(position.y)
+            .w(px(220.0))
+            .p_1()
+            .flex()
+            .flex_col()
+            .bg(theme.elevated_surface)
+            .border_1()
+            .border_color(theme.border)
+            .rounded_md()
+            .shadow_md()
+            .on_mouse_down_out(cx.listener(|view: &mut RootView, _event, _window, cx| {
+                view.card_menu = None;
+                cx.notify();
+            }));
+
+        match target.clone() {
+            CardMenuTarget::Project { path, name } => {
+                let export_path = path.clone();
+                menu.child(self.render_menu_item("Export entries", cx, move |view, cx| {
+                    view.export_card_entries(export_path.clone(), None, name.clone(), cx);
+                }))
+                .child(self.render_menu_item("Exclude from totals", cx, move |view, cx| {
+                    view.toggle_project_exclusion(path.clone(), cx);
+                }))
+            }
+            CardMenuTarget::Model(model) => {
+                menu.child(self.render_menu_item("Exclude from totals", cx, move |view, cx| {
+                    view.toggle_model_exclusion(model.clone(), cx);
+                }))
+            }
+        }
+    }
+
+    pub fn toggle_project_exclusion(&mut self, project_path: String, cx: &mut Context<Self>) {
+        let excluded = Self::toggle_listed(&mut self.app_settings.excluded_projects, project_path.clone());
+        self.exclusions_changed(&project_path, excluded, cx);
+    }
+
+    pub fn toggle_model_exclusion(&mut self, model: String, cx: &mut Context<Self>) {
+        let excluded = Self::toggle_listed(&mut self.app_settings.excluded_models, model.clone());
+        self.exclusions_changed(&model, excluded, cx);
+    }
+
+    /// Add `value` to the list or remove it if present, returning whether it is now listed
+    fn toggle_listed(list: &mut Vec<String>, value: String) -> bool {
+        if let Some(index) = list.iter().position(|item| *item == value) {
+            list.remove(index);
+            false
         } else {
-            self.get_sample_sessions_analytics()
+            list.push(value);
+            true
         }
     }
 
-    /// Generate sample session analytics data for demonstration
-    fn get_sample_sessions_analytics(&self) -> Vec<SessionStats> {
-        use chrono::{Duration, Utc};
+    fn exclusions_changed(&mut self, name: &str, excluded: bool, cx: &mut Context<Self>) {
+        self.save_settings(cx);
+        self.apply_time_filter();
+        if excluded {
+            info!("🚫 Excluded {} from totals", name);
+            self.show_toast(format!("Excluded {} from totals", name), cx);
+        } else {
+            info!("✅ Included {} in totals", name);
+            self.show_toast(format!("Included {} in totals", name), cx);
+        }
+        cx.notify();
+    }
 
-        vec![
-            SessionStats {
-                session_id: "session_2024071201".to_string(),
-                project_path: "/Users/dev/rust-project".to_string(),
-                total_cost: 8.45,
-                total_tokens: 89000,
-                input_tokens: 65000,
-                output_tokens: 18000,
-                cache_read_tokens: 4000,
-                cache_creation_tokens: 2000,
-                request_count: 87,
-                timestamp: Utc::now() - Duration::hours(2),
-            },
-            SessionStats {
-                session_id: "session_2024071901".to_string(),
-                project_path: "/Users/dev/web-app".to_string(),
-                total_cost: 6.72,
-                total_tokens: 76000,
-                input_tokens: 52000,
-                output_tokens: 16000,
-                cache_read_tokens: 5000,
-                cache_creation_tokens: 3000,
-                request_count: 65,
-                timestamp: Utc::now() - Duration::days(1) - Duration::hours(4),
-            },
-            SessionStats {
-                session_id: "session_2024071902".to_string(),
-                project_path: "/Users/dev/rust-project".to_string(),
-                total_cost: 4.23,
-                total_tokens: 45000,
-                input_tokens: 32000,
-                output_tokens: 9000,
-                cache_read_tokens: 3000,
-                cache_creation_tokens: 1000,
-                request_count: 34,
-                timestamp: Utc::now() - Duration::days(1) - Duration::hours(8),
-            },
-            SessionStats {
-                session_id: "session_2024071801".to_string(),
-                project_path: "/Users/dev/python-tool".to_string(),
-                total_cost: 3.08,
-                total_tokens: 38000,
-                input_tokens: 28000,
-                output_tokens: 7000,
-                cache_read_tokens: 2000,
-                cache_creation_tokens: 1000,
-                request_count: 23,
-                timestamp: Utc::now() - Duration::days(2) - Duration::hours(6),
-            },
-            SessionStats {
-                session_id: "session_2024071701".to_string(),
-                project_path: "/Users/dev/web-app".to_string(),
-                total_cost: 1.25,
-                total_tokens: 15000,
-                input_tokens: 11000,
-                output_tokens: 3000,
-                cache_read_tokens: 800,
-                cache_creation_tokens: 200,
-                request_count: 12,
-                timestamp: Utc::now() - Duration::days(3) - Duration::hours(10),
-            },
-        ]
+    fn render_copy_path_button(&self, project_path: &str, cx: &mut Context<Self>) -> Div {
+        let colors = self.theme_registry.colors();
+        let elevated_surface = colors.elevated_surface;
+        // Sessions and projects without a cwd are aggregated under "Unknown Project"
+        let is_enabled = !project_path.is_empty() && project_path != "Unknown Project";
+        let path = project_path.to_string();
+
+        let button = div()
+            .flex()
+            .items_center()
+            .justify_center()
+            .w(px(28.0))
+            .h(px(24.0))
+            .bg(colors.surface)
+            .border_1()
+            .border_color(colors.border)
+            .rounded(px(4.0))
+            .text_size(px(12.0))
+            .child("📋");
+
+        if is_enabled {
+            button
+                .cursor_pointer()
+                .hover(move |style| style.bg(elevated_surface))
+                .on_mouse_down(
+                    MouseButton::Left,
+                    cx.listener(move |view: &mut RootView, _event, _window, cx| {
+                        view.copy_project_path(path.clone(), cx);
+                    }),
+                )
+        } else {
+            button.opacity(0.4)
+        }
+    }
+
+    fn render_toast(&self) -> Div {
+        let theme = self.theme_registry.colors();
+        let Some(ref message) = self.toast_message else {
+            return div();
+        };
+
+        div()
+            .absolute()
+            .bottom_6()
+            .right_6()
+            .px_4()
+            .py_2()
+            .bg(theme.elevated_surface)
+            .border_1()
+            .border_color(theme.border)
+            .rounded_md()
+            .shadow_md()
+            .text_sm()
+            .text_color(theme.text)
+            .child(message.clone())
+    }
+
+    /// Corner badge shown while a background refresh replaces the data on screen
+    fn render_refresh_indicator(&self) -> AnyElement {
+        let theme = self.theme_registry.colors();
+        if !self.is_background_refresh() {
+            return div().into_any_element();
+        }
+
+        div()
+            .absolute()
+            .bottom_6()
+            .left_6()
+            .flex()
+            .items_center()
+            .gap_2()
+            .px_3()
+            .py_1()
+            .bg(theme.elevated_surface)
+            .border_1()
+            .border_color(theme.border)
+            .rounded_md()
+            .shadow_md()
+            .text_xs()
+            .text_color(theme.text_muted)
+            .child(
+                div()
+                    .size(px(8.0))
+                    .rounded_full()
+                    .bg(theme.metric_quaternary)
+                    .with_animation(
+                        "refresh-indicator-pulse",
+                        Animation::new(Duration::from_millis(1200))
+                            .repeat()
+                            .with_easing(pulsating_between(0.3, 1.0)),
+                        |dot, delta| dot.opacity(delta),
+                    ),
+            )
+            .child("Refreshing data…")
+            .into_any_element()
+    }
+
+    pub fn toggle_settings_panel(&mut self, cx: &mut Context<Self>) {
+        self.show_settings_panel = !self.show_settings_panel;
+        self.confirm_settings_reset = false;
+        self.show_default_tab_menu = false;
+        self.show_export_menu = false;
+        self.show_auto_refresh_menu = false;
+        cx.notify();
+    }
+
+    /// Restore theme and app settings to their defaults, rewriting both config files
+    pub fn show_onboarding(&mut self, cx: &mut Context<Self>) {
+        self.show_onboarding = true;
+        self.show_settings_panel = false;
+        cx.notify();
+    }
+
+    /// Close the welcome guide and record that it has been seen
+    pub fn complete_onboarding(&mut self, cx: &mut Context<Self>) {
+        self.show_onboarding = false;
+        if !self.app_settings.onboarding_completed {
+            self.app_settings.onboarding_completed = true;
+            self.save_settings(cx);
+        }
+        cx.notify();
+    }
+
+    pub fn toggle_shortcut_overlay(&mut self, cx: &mut Context<Self>) {
+        self.show_shortcut_overlay = !self.show_shortcut_overlay;
+        cx.notify();
+    }
+
+    /// Run a shortcut from the registry; Export and Copy arrive as actions instead
+    /// Overlay text for a shortcut: number keys name the tab they reach now, and keys past
+    /// the last shown tab are left out
+    fn shortcut_description(&self, command: &ShortcutCommand) -> Option<String> {
+        match command {
+            ShortcutCommand::SwitchTab(number) => {
+                self.app_settings.tab_for_number(*number).map(|tab| format!("Go to {}", tab.title()))
+            }
+            _ => Some(command.description()),
+        }
+    }
+
+    fn run_shortcut(&mut self, command: &ShortcutCommand, window: &mut Window, cx: &mut Context<Self>) {
+        match command {
+            ShortcutCommand::SwitchTab(number) => {
+                if let Some(tab) = self.app_settings.tab_for_number(*number) {
+                    self.set_active_tab(tab, cx);
+                }
+            }
+            ShortcutCommand::SetTimeRange(range) => self.set_time_range(*range, cx),
+            ShortcutCommand::Refresh => self.refresh_data(cx),
+            ShortcutCommand::Export => self.export_current_view(cx),
+            ShortcutCommand::CopyVisibleData => self.copy_visible_data(cx),
+            ShortcutCommand::MarkAllSeen => self.mark_all_tabs_seen(cx),
+            ShortcutCommand::ShareSummary => self.share_summary(cx),
+            ShortcutCommand::FocusSessionSearch => {
+                window.focus(&self.session_query_focus);
+                cx.notify();
+            }
+            ShortcutCommand::ToggleDebugPanel => self.toggle_debug_panel(cx),
+            ShortcutCommand::ToggleShortcutOverlay => self.toggle_shortcut_overlay(cx),
+            ShortcutCommand::HideApp => {
+                // Standard macOS behavior
+                debug!("🙈 CMD+H pressed - hiding application");
+                cx.hide();
+            }
+            ShortcutCommand::CloseWindow => {
+                debug!("🪟 CMD+W pressed - closing window");
+                cx.hide(); // Hide the window instead of quitting
+            }
+            ShortcutCommand::Quit => {
+                debug!("🚪 CMD+Q pressed - quitting application");
+                cx.quit();
+            }
+        }
+    }
+
+    /// Every registered shortcut, grouped; dismissed with "?" or Esc
+    fn render_shortcut_overlay(&self, cx: &mut Context<Self>) -> Div {
+        let theme = self.theme_registry.colors();
+        if !self.show_shortcut_overlay {
+            return div();
+        }
+
+        div()
+            .absolute()
+            .inset_0()
+            .flex()
+            .items_center()
+            .justify_center()
+            .bg(hsla(0.0, 0.0, 0.0, 0.4))
+            .child(
+                div()
+                    .w(px(520.0))
+                    .p_6()
+                    .flex()
+                    .flex_col()
+                    .gap_4()
+                    .bg(theme.elevated_surface)
+                    .border_1()
+                    .border_color(theme.border)
+                    .rounded_lg()
+                    .shadow_md()
+                    .child(
+                        div()
+                            .text_xl()
+                            .font_weight(FontWeight::BOLD)
+                            .text_color(theme.text_accent)
+                            .child("Keyboard Shortcuts"),
+                    )
+                    .child(
+                        div().flex().flex_wrap().gap_6().children(shortcuts::grouped().into_iter().map(
+                            |(group, entries)| {
+                                div()
+                                    .w(px(220.0))
+                                    .flex()
+                                    .flex_col()
+                                    .gap_1()
+                                    .child(
+                                        div()
+                                            .text_sm()
+                                            .font_weight(FontWeight::SEMIBOLD)
+                                            .text_color(theme.text)
+                                            .child(group),
+                                    )
+                                    .children(entries.into_iter().filter_map(|shortcut| {
+                                        let description = self.shortcut_description(&shortcut.command)?;
+                                        Some(div()
+                                            .flex()
+                                            .items_center()
+                                            .gap_3()
+                                            .text_sm()
+                                            .child(
+                                                div()
+                                                    .w(px(56.0))
+                                                    .px_1()
+                                                    .rounded_sm()
+                                                    .bg(theme.surface)
+                                                    .border_1()
+                                                    .border_color(theme.border)
+                                                    .text_xs()
+                                                    .text_color(theme.text)
+                                                    .child(shortcut.label()),
+                                            )
+                                            .child(
+                                                div()
+                                                    .text_color(theme.text_muted)
+                                                    .child(description),
+                                            ))
+                                    }))
+                            },
+                        )),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .justify_end()
+                            .child(self.render_chip_button("Close", true, cx, |view, cx| {
+                                view.toggle_shortcut_overlay(cx)
+                            })),
+                    ),
+            )
+    }
+
+    /// Welcome guide covering the data source, shortcuts and time filter
+    fn render_onboarding(&self, cx: &mut Context<Self>) -> Div {
+        let theme = self.theme_registry.colors();
+        if !self.show_onboarding {
+            return div();
+        }
+
+        let section = |title: &'static str, lines: &[&'static str]| {
+            div()
+                .flex()
+                .flex_col()
+                .gap_1()
+                .child(
+                    div()
+                        .text_sm()
+                        .font_weight(FontWeight::SEMIBOLD)
+                        .text_color(theme.text)
+                        .child(title),
+                )
+                .children(
+                    lines
+                        .iter()
+                        .map(|line| div().text_sm().text_color(theme.text_muted).child(*line))
+                        .collect::<Vec<_>>(),
+                )
+        };
+
+        div()
+            .absolute()
+            .inset_0()
+            .flex()
+            .items_center()
+            .justify_center()
+            .bg(hsla(0.0, 0.0, 0.0, 0.4))
+            .child(
+                div()
+                    .w(px(460.0))
+                    .p_6()
+                    .flex()
+                    .flex_col()
+                    .gap_4()
+                    .bg(theme.elevated_surface)
+                    .border_1()
+                    .border_color(theme.border)
+                    .rounded_lg()
+                    .shadow_md()
+                    .child(
+                        div()
+                            .text_xl()
+                            .font_weight(FontWeight::BOLD)
+                            .text_color(theme.text_accent)
+                            .child("Welcome to the Usage Dashboard"),
+                    )
+                    .child(section(
+                        "Where the data comes from",
+                        &["Claude Code's logs in ~/.claude/projects, read locally. Nothing is uploaded."],
+                    ))
+                    .child(section(
+                        "Tabs",
+                        &[
+                            "Overview for totals, Models and Projects for breakdowns,",
+                            "Sessions for individual conversations, Timeline for spend over time.",
+                        ],
+                    ))
+                    .child(section(
+                        "Time filter",
+                        &["The range buttons in the header narrow every tab to that period."],
+                    ))
+                    .child(section(
+                        "Shortcuts",
+                        &[
+                            "1-5 switch tabs · Alt+1-6 pick a time range",
+                            "G go to a session · ⌘E export this view · ⌘C copy as TSV · ? list all shortcuts",
+                        ],
+                    ))
+                    .child(
+                        div()
+                            .flex()
+                            .justify_end()
+                            .child(self.render_chip_button("Got it", true, cx, |view, cx| view.complete_onboarding(cx))),
+                    ),
+            )
+    }
+
+    fn reset_settings_to_defaults(&mut self, cx: &mut Context<Self>) {
+        info!("♻️ Resetting settings to defaults");
+        if let Err(e) = self.theme_registry.reset_to_defaults() {
+            warn!("⚠️ Failed to save theme settings: {}", e);
+        }
+        self.app_settings = AppSettings::default();
+        if let Err(e) = self.app_settings.save() {
+            warn!("⚠️ Failed to save settings: {}", e);
+        }
+        self.confirm_settings_reset = false;
+
+        // Scan and aggregation options may have changed, so reload from disk
+        self.start_periodic_refresh(cx);
+        self.refresh_data(cx);
+        self.show_toast("Settings reset to defaults", cx);
+        // A read-only config directory matters more than the confirmation, so it replaces it
+        self.show_config_notice(cx);
+    }
+
+    fn render_settings_button(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let colors = self.theme_registry.colors();
+        let elevated_surface = colors.elevated_surface;
+        let border_color = colors.border;
+
+        div()
+            .id("settings-button")
+            .flex()
+            .items_center()
+            .justify_center()
+            .w(px(40.0))
+            .h(px(32.0))
+            .bg(if self.show_settings_panel {
+                colors.elevated_surface
+            } else {
+                colors.surface
+            })
+            .border_1()
+            .border_color(colors.border)
+            .rounded(px(6.0))
+            .cursor_pointer()
+            .hover(move |style| style.bg(elevated_surface))
+            .active(move |style| style.bg(border_color))
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(|view: &mut RootView, _event, _window, cx| {
+                    view.toggle_settings_panel(cx);
+                }),
+            )
+            .child(
+                div()
+                    .text_size(px(14.0))
+                    .text_color(colors.text)
+                    .child("⚙"),
+            )
+    }
+
+    /// Header field for "Go to session"; typed keys are collected while it has focus
+    fn render_session_search(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let colors = self.theme_registry.colors();
+        let accent = colors.text_accent;
+        let is_empty = self.session_query.is_empty();
+
+        div()
+            .id("session-search")
+            .key_context("SessionSearch")
+            .track_focus(&self.session_query_focus)
+            .flex()
+            .items_center()
+            .gap_1()
+            .w(px(170.0))
+            .h(px(32.0))
+            .px_2()
+            .bg(colors.surface)
+            .border_1()
+            .border_color(colors.border)
+            .rounded(px(6.0))
+            .cursor_text()
+            .text_xs()
+            .focus(move |style| style.border_color(accent))
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(|view: &mut RootView, _event, window, cx| {
+                    window.focus(&view.session_query_focus);
+                    cx.notify();
+                }),
+            )
+            .on_key_down(cx.listener(
+                |view: &mut RootView, event: &KeyDownEvent, window: &mut Window, cx: &mut Context<RootView>| {
+                    let modifiers = &event.keystroke.modifiers;
+                    // Leave shortcuts and focus movement to the root handler
+                    if modifiers.platform || modifiers.control || event.keystroke.key == "tab" {
+                        return;
+                    }
+
+                    match event.keystroke.key.as_str() {
+                        "enter" => {
+                            let query = view.session_query.clone();
+                            if view.go_to_session(&query, cx) {
+                                window.focus(&view.focus_handle);
+                            }
+                        }
+                        "escape" => {
+                            view.session_query.clear();
+                            window.focus(&view.focus_handle);
+                        }
+                        "backspace" => {
+                            view.session_query.pop();
+                        }
+                        _ => {
+                            if let Some(text) = &event.keystroke.key_char {
+                                view.session_query.push_str(text);
+                            }
+                        }
+                    }
+                    cx.stop_propagation();
+                    cx.notify();
+                },
+            ))
+            .child(div().text_color(colors.text_muted).child("🔎"))
+            .child(
+                div()
+                    .overflow_hidden()
+                    .text_color(if is_empty { colors.text_muted } else { colors.text })
+                    .child(if is_empty {
+                        "Go to session (g)".to_string()
+                    } else {
+                        self.session_query.clone()
+                    }),
+            )
+    }
+
+    /// Most recent session whose id starts with `query`
+    fn find_session_by_prefix<'a>(sessions: &'a [SessionStats], query: &str) -> Option<&'a SessionStats> {
+        let query = query.trim();
+        if query.is_empty() {
+            return None;
+        }
+        sessions.iter().find(|session| session.session_id.starts_with(query))
+    }
+
+    /// Narrow the Sessions tab to the session matching `query`, returning whether one was found
+    pub fn go_to_session(&mut self, query: &str, cx: &mut Context<Self>) -> bool {
+        let sessions = self.get_sessions_data();
+        match Self::find_session_by_prefix(&sessions, query) {
+            Some(session) => {
+                info!("🔎 Going to session {}", session.session_id);
+                self.session_query.clear();
+                self.show_session(session.project_path.clone(), session.session_id.clone(), cx);
+                true
+            }
+            None => {
+                self.show_toast(format!("Session not found: {}", query.trim()), cx);
+                false
+            }
+        }
+    }
+
+    /// Switch to the Sessions tab narrowed to one session
+    pub fn show_session(&mut self, project: String, session_id: String, cx: &mut Context<Self>) {
+        self.selected_session = Some((project, session_id));
+        self.session_note_draft = None;
+        self.inspected_entry = None;
+        self.active_tab = DashboardTab::Sessions;
+        self.sessions_scroll.set_offset(point(px(0.0), px(0.0)));
+        cx.notify();
+    }
+
+    /// Open the session holding the costliest request in the current view
+    pub fn show_most_expensive_request(&mut self, cx: &mut Context<Self>) {
+        let aggregator = UsageAggregator::with_options(self.app_settings.aggregation_options());
+        let session = self
+            .analytics_data
+            .as_ref()
+            .and_then(|data| data.most_expensive_request.as_ref())
+            .and_then(|entry| entry.session_id.clone().map(|id| (aggregator.project_key(entry), id)));
+        match session {
+            Some((project, session_id)) => self.show_session(project, session_id, cx),
+            None => self.show_toast("That request has no session to open", cx),
+        }
+    }
+
+    pub fn clear_selected_session(&mut self, cx: &mut Context<Self>) {
+        self.selected_session = None;
+        self.session_note_draft = None;
+        self.inspected_entry = None;
+        cx.notify();
+    }
+
+    /// Store the note typed for a session and persist it
+    fn save_session_note(&mut self, session_id: &str, note: &str, cx: &mut Context<Self>) {
+        self.app_settings.set_session_note(session_id, note);
+        self.save_settings(cx);
+        self.session_note_draft = None;
+        cx.notify();
+    }
+
+    /// Overview of every persisted setting with a guarded reset action
+    fn render_settings_panel(&self, cx: &mut Context<Self>) -> Div {
+        let theme = self.theme_registry.colors();
+        if !self.show_settings_panel {
+            return div();
+        }
+
+        let settings = &self.app_settings;
+        let row = |label: &'static str, value: String| {
+            div()
+                .flex()
+                .justify_between()
+                .gap_4()
+                .child(div().text_color(theme.text_muted).child(label))
+                .child(div().text_color(theme.text).child(value))
+        };
+
+        let reset_controls = if self.confirm_settings_reset {
+            div()
+                .flex()
+                .items_center()
+                .justify_between()
+                .child(
+                    div()
+                        .text_xs()
+                        .text_color(theme.text_muted)
+                        .child("Reset all settings?"),
+                )
+                .child(
+                    div()
+                        .flex()
+                        .gap_2()
+                        .child(self.render_chip_button("Cancel", false, cx, |view, cx| {
+                            view.confirm_settings_reset = false;
+                            cx.notify();
+                        }))
+                        .child(self.render_chip_button("Reset", true, cx, |view, cx| {
+                            view.reset_settings_to_defaults(cx);
+                        })),
+                )
+        } else {
+            div()
+                .flex()
+                .justify_end()
+                .gap_2()
+                .child(self.render_chip_button("Welcome guide", false, cx, |view, cx| {
+                    view.show_onboarding(cx);
+                }))
+                .child(self.render_chip_button("Reset to defaults", false, cx, |view, cx| {
+                    view.confirm_settings_reset = true;
+                    cx.notify();
+                }))
+        };
+
+        div()
+            .absolute()
+            .top(px(64.0))
+            .right(px(24.0))
+            .w(px(320.0))
+            .p_3()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .bg(theme.elevated_surface)
+            .border_1()
+            .border_color(theme.border)
+            .rounded_md()
+            .shadow_md()
+            .text_xs()
+            .child(
+                div()
+                    .text_sm()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(theme.text)
+                    .child("Settings"),
+            )
+            .child(row("Theme", format!("{:?}", self.theme_registry.settings().mode)))
+            .child(row("Token totals", settings.token_total_policy.label().to_string()))
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_4()
+                    .child(div().text_color(theme.text_muted).child("Numbers"))
+                    .child(self.render_chip_button(
+                        settings.number_format.label(),
+                        false,
+                        cx,
+                        |view, cx| view.toggle_number_format(cx),
+                    )),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_4()
+                    .child(div().text_color(theme.text_muted).child("Models"))
+                    .child(self.render_chip_button(
+                        if settings.group_models_by_family { "By family" } else { "By version" },
+                        false,
+                        cx,
+                        |view, cx| view.toggle_group_models_by_family(cx),
+                    )),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_4()
+                    .child(div().text_color(theme.text_muted).child("Ignore cache (raw input/output only)"))
+                    .child(self.render_chip_button(
+                        if settings.ignore_cache { "On" } else { "Off" },
+                        settings.ignore_cache,
+                        cx,
+                        |view, cx| view.toggle_ignore_cache(cx),
+                    )),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_4()
+                    .child(div().text_color(theme.text_muted).child("Merge symlinked project folders"))
+                    .child(self.render_chip_button(
+                        if settings.resolve_project_symlinks { "On" } else { "Off" },
+                        settings.resolve_project_symlinks,
+                        cx,
+                        |view, cx| view.toggle_resolve_project_symlinks(cx),
+                    )),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_4()
+                    .child(div().text_color(theme.text_muted).child("Exclude quiet hours"))
+                    .child(self.render_chip_button(
+                        if settings.quiet_hours_enabled { "On" } else { "Off" },
+                        settings.quiet_hours_enabled,
+                        cx,
+                        |view, cx| view.toggle_quiet_hours(cx),
+                    )),
+            )
+            .when(settings.quiet_hours_enabled, |panel| {
+                panel.child(
+                    div()
+                        .flex()
+                        .items_center()
+                        .justify_between()
+                        .gap_2()
+                        .child(div().text_color(theme.text_muted).child(settings.quiet_hours.label()))
+                        .child(
+                            div()
+                                .flex()
+                                .items_center()
+                                .gap_1()
+                                .child(div().text_color(theme.text_muted).child("From"))
+                                .child(self.render_chip_button("−", false, cx, |view, cx| view.shift_quiet_hours(-1, 0, cx)))
+                                .child(self.render_chip_button("+", false, cx, |view, cx| view.shift_quiet_hours(1, 0, cx)))
+                                .child(div().ml_1().text_color(theme.text_muted).child("To"))
+                                .child(self.render_chip_button("−", false, cx, |view, cx| view.shift_quiet_hours(0, -1, cx)))
+                                .child(self.render_chip_button("+", false, cx, |view, cx| view.shift_quiet_hours(0, 1, cx))),
+                        ),
+                )
+            })
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_4()
+                    .child(div().text_color(theme.text_muted).child("Ignore data before a start date"))
+                    .child(self.render_chip_button(
+                        if settings.analysis_start_date.is_some() { "On" } else { "Off" },
+                        settings.analysis_start_date.is_some(),
+                        cx,
+                        |view, cx| view.toggle_analysis_start(cx),
+                    )),
+            )
+            .when_some(self.analysis_start_label(), |panel, label| {
+                panel.child(
+                    div()
+                        .flex()
+                        .items_center()
+                        .justify_between()
+                        .gap_2()
+                        .child(div().text_color(theme.text_muted).child(format!("Counting from {}", label)))
+                        .child(
+                            div()
+                                .flex()
+                                .items_center()
+                                .gap_1()
+                                .child(self.render_chip_button("−1M", false, cx, |view, cx| view.shift_analysis_start(0, -1, cx)))
+                                .child(self.render_chip_button("−1D", false, cx, |view, cx| view.shift_analysis_start(-1, 0, cx)))
+                                .child(self.render_chip_button("+1D", false, cx, |view, cx| view.shift_analysis_start(1, 0, cx)))
+                                .child(self.render_chip_button("+1M", false, cx, |view, cx| view.shift_analysis_start(0, 1, cx))),
+                        ),
+                )
+            })
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_4()
+                    .child(div().text_color(theme.text_muted).child("Bill unpriced cache writes as input"))
+                    .child(self.render_chip_button(
+                        if settings.cache_writes_as_input { "On" } else { "Off" },
+                        settings.cache_writes_as_input,
+                        cx,
+                        |view, cx| view.toggle_cache_writes_as_input(cx),
+                    )),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_4()
+                    .child(div().text_color(theme.text_muted).child("Recompute costs from tokens (ignore logged costUSD)"))
+                    .child(self.render_chip_button(
+                        if settings.recompute_costs { "On" } else { "Off" },
+                        settings.recompute_costs,
+                        cx,
+                        |view, cx| view.toggle_recompute_costs(cx),
+                    )),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_4()
+                    .child(div().text_color(theme.text_muted).child("Debug: keep raw log lines for the inspector"))
+                    .child(self.render_chip_button(
+                        if settings.capture_raw_lines { "On" } else { "Off" },
+                        settings.capture_raw_lines,
+                        cx,
+                        |view, cx| view.toggle_capture_raw_lines(cx),
+                    )),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_4()
+                    .child(div().text_color(theme.text_muted).child("Console logging"))
+                    .child(self.render_chip_button(
+                        settings.log_verbosity.label(),
+                        false,
+                        cx,
+                        |view, cx| view.cycle_log_verbosity(cx),
+                    )),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_4()
+                    .child(div().text_color(theme.text_muted).child("Pricing audit at startup"))
+                    .child(self.render_chip_button(
+                        if settings.audit_models_on_startup { "On" } else { "Off" },
+                        settings.audit_models_on_startup,
+                        cx,
+                        |view, cx| view.toggle_audit_models_on_startup(cx),
+                    )),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_4()
+                    .child(div().text_color(theme.text_muted).child("Export format"))
+                    .child(self.render_chip_button(
+                        settings.default_export_format.label(),
+                        false,
+                        cx,
+                        |view, cx| view.cycle_default_export_format(cx),
+                    )),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_4()
+                    .child(div().text_color(theme.text_muted).child("Cost precision"))
+                    .child(self.render_chip_button(
+                        settings.cost_precision.label(),
+                        false,
+                        cx,
+                        |view, cx| view.cycle_cost_precision(cx),
+                    )),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_4()
+                    .child(div().text_color(theme.text_muted).child("Excluded"))
+                    .child(
+                        div()
+                            .flex()
+                            .flex_wrap()
+                            .justify_end()
+                            .gap_1()
+                            .when(settings.excluded_count() == 0, |list| {
+                                list.child(div().text_color(theme.text).child("Nothing"))
+                            })
+                            // Clicking an excluded item counts it again
+                            .children(settings.excluded_projects.iter().map(|path| {
+                                let path = path.clone();
+                                let name = UsageAggregator::new().extract_project_name(&path);
+                                self.render_chip_button(format!("{} ✕", name), false, cx, move |view, cx| {
+                                    view.toggle_project_exclusion(path.clone(), cx)
+                                })
+                            }))
+                            .children(settings.excluded_models.iter().map(|model| {
+                                let model = model.clone();
+                                self.render_chip_button(format!("{} ✕", model), false, cx, move |view, cx| {
+                                    view.toggle_model_exclusion(model.clone(), cx)
+                                })
+                            })),
+                    ),
+            )
+            .child(row(
+                "Group small contributors",
+                match settings.minor_contributor_threshold {
+                    threshold if threshold.is_disabled() => "Off".to_string(),
+                    CostThreshold::Absolute(amount) => format!("Under {}", self.format_cost(amount)),
+                    CostThreshold::PercentOfTotal(percent) => format!("Under {}% of total", percent),
+                },
+            ))
+            .child(row(
+                "Request cost alert",
+                settings
+                    .request_cost_alert
+                    .map(|threshold| format!("Over {}", self.format_cost(threshold)))
+                    .unwrap_or_else(|| "Off".to_string()),
+            ))
+            .child(row(
+                "Monthly budget",
+                settings
+                    .monthly_budget
+                    .map(|budget| self.format_cost(budget))
+                    .unwrap_or_else(|| "Not set".to_string()),
+            ))
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_4()
+                    .child(div().text_color(theme.text_muted).child("Budget alerts"))
+                    .child(self.render_chip_button(
+                        if settings.budget_notifications { "On" } else { "Off" },
+                        settings.budget_notifications,
+                        cx,
+                        |view, cx| view.toggle_budget_notifications(cx),
+                    )),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_4()
+                    .child(div().text_color(theme.text_muted).child("Tabs shown"))
+                    .child(div().flex().flex_wrap().gap_1().children(DashboardTab::all().into_iter().map(|tab| {
+                        let shown = settings.shown_tabs().contains(&tab);
+                        self.render_chip_button(tab.title(), shown, cx, move |view, cx| {
+                            view.toggle_tab_visibility(tab.clone(), cx)
+                        })
+                    }))),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_4()
+                    .child(div().text_color(theme.text_muted).child("Default tab"))
+                    .child(self.render_chip_button(
+                        format!("{} ▾", settings.default_tab.title()),
+                        self.show_default_tab_menu,
+                        cx,
+                        |view, cx| {
+                            view.show_default_tab_menu = !view.show_default_tab_menu;
+                            cx.notify();
+                        },
+                    )),
+            )
+            .when(self.show_default_tab_menu, |panel| {
+                panel.child(
+                    div()
+                        .flex()
+                        .flex_col()
+                        .p_1()
+                        .bg(theme.surface)
+                        .border_1()
+                        .border_color(theme.border)
+                        .rounded_md()
+                        .children(DashboardTab::all().into_iter().map(|tab| {
+                            let label = tab.title();
+                            self.render_menu_item(label, cx, move |view, cx| {
+                                view.set_default_tab(tab.clone(), cx)
+                            })
+                        })),
+                )
+            })
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_4()
+                    // A remembered tab takes precedence over the default tab
+                    .child(div().text_color(theme.text_muted).child("Reopen last tab"))
+                    .child(self.render_chip_button(
+                        if settings.remember_last_tab { "On" } else { "Off" },
+                        settings.remember_last_tab,
+                        cx,
+                        |view, cx| view.toggle_remember_last_tab(cx),
+                    )),
+            )
+            .child(row("Week starts on", settings.week_start.to_string()))
+            .child(row(
+                "Timezone",
+                settings
+                    .timezone
+                    .clone()
+                    .unwrap_or_else(|| "System local".to_string()),
+            ))
+            .child(row(
+                "Auto-refresh",
+                Self::auto_refresh_label(settings.auto_refresh_secs),
+            ))
+            .child(row("Scan depth", settings.scan_max_depth.to_string()))
+            .child(row(
+                "History kept",
+                settings
+                    .max_history_days
+                    .map(|days| format!("{} days", days))
+                    .unwrap_or_else(|| "All".to_string()),
+            ))
+            .child(row("Future-dated entries", settings.future_entry_policy.label().to_string()))
+            .child(row("Ignored directories", settings.scan_ignored_dirs.join(", ")))
+            .child(row(
+                "Counted record types",
+                if settings.billable_entry_types.is_empty() {
+                    "All".to_string()
+                } else {
+                    settings.billable_entry_types.join(", ")
+                },
+            ))
+            .child(div().h(px(1.0)).bg(theme.border))
+            .child(reset_controls)
+    }
+
+    pub fn toggle_debug_panel(&mut self, cx: &mut Context<Self>) {
+        self.show_debug_panel = !self.show_debug_panel;
+        cx.notify();
+    }
+
+    /// Diagnostics about the loaded data, toggled with the "d" key
+    fn render_debug_panel(&self) -> Div {
+        let theme = self.theme_registry.colors();
+        if !self.show_debug_panel {
+            return div();
+        }
+
+        let entries: &[UsageEntry] = self
+            .full_analytics_data
+            .as_ref()
+            .map(|data| data.entries.as_slice())
+            .unwrap_or(&[]);
+        let divergent_count = entries.iter().filter(|e| e.is_cost_divergent()).count();
+        let logged_total: f64 = entries.iter().map(|e| e.logged_cost.unwrap_or(e.computed_cost)).sum();
+        let computed_total: f64 = entries.iter().map(|e| e.computed_cost).sum();
+
+        let row = |label: &'static str, value: String| {
+            div()
+                .flex()
+                .justify_between()
+                .gap_4()
+                .child(div().text_color(theme.text_muted).child(label))
+                .child(div().text_color(theme.text).child(value))
+        };
+
+        div()
+            .absolute()
+            .bottom_6()
+            .left_6()
+            .w(px(300.0))
+            .p_3()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .bg(theme.elevated_surface)
+            .border_1()
+            .border_color(theme.border)
+            .rounded_md()
+            .shadow_md()
+            .text_xs()
+            .child(
+                div()
+                    .text_sm()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(theme.text)
+                    .child("Debug"),
+            )
+            .child(row("Entries loaded", entries.len().to_string()))
+            .child(row(
+                "Cost divergent (>10%)",
+                divergent_count.to_string(),
+            ))
+            .child(row(
+                "Future-dated",
+                entries.iter().filter(|e| e.future_dated).count().to_string(),
+            ))
+            .child(row("Logged cost", self.format_cost(logged_total)))
+            .child(row("Computed cost", self.format_cost(computed_total)))
+    }
+
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let mut view = Self::with_settings(AppSettings::load(), ThemeRegistry::new(), cx);
+        // Loading writes defaults on first run, which may already have hit a read-only directory
+        view.show_config_notice(cx);
+
+        // Focus will be handled by the window system when the view is rendered
+
+        // Remember where the window is so the next launch can restore it
+        cx.observe_window_bounds(window, |view: &mut RootView, window, cx| {
+            view.schedule_window_geometry_save(window.window_bounds().get_bounds(), cx);
+        })
+        .detach();
+
+        // Load data synchronously on initialization
+        view.load_data_synchronously();
+        
+        // Start periodic refresh
+        view.start_periodic_refresh(cx);
+        
+        view
+    }
+
+    /// Test support: build a view over pre-built entries without reading settings,
+    /// theme files or `~/.claude/projects`, and without starting auto-refresh
+    #[allow(dead_code)] // Used by tests
+    pub fn with_entries(entries: Vec<UsageEntry>, cx: &mut Context<Self>) -> Self {
+        let app_settings = AppSettings::default();
+        let theme_registry = ThemeRegistry::with_settings(ThemeSettings::default());
+        let mut view = Self::with_settings(app_settings, theme_registry, cx);
+
+        let aggregator = UsageAggregator::with_options(view.app_settings.aggregation_options());
+        view.full_analytics_data = Some(Arc::new(aggregator.aggregate_entries(entries)));
+        view.apply_time_filter();
+        view.loading_state = LoadingState::LoadedFull;
+        view.loading_message = "Dashboard ready".to_string();
+        view.is_loading = false;
+        view.last_refresh = Some(Local::now());
+        view
+    }
+
+    fn with_settings(
+        app_settings: AppSettings,
+        theme_registry: ThemeRegistry,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        Self {
+            focus_handle: cx.focus_handle(),
+            control_focus: Self::create_control_focus_handles(cx),
+            active_tab: app_settings.startup_tab(),
+            show_onboarding: !app_settings.onboarding_completed,
+            show_shortcut_overlay: false,
+            timeline_granularity: app_settings.timeline_granularity,
+            comparison_days: COMPARISON_PERIOD_OPTIONS[0],
+            project_sort: app_settings.project_sort(),
+            session_sort: app_settings.session_sort(),
+            session_filter: app_settings.session_filter(),
+            loading_message: "Loading analytics data...".to_string(),
+            analytics_data: None,
+            full_analytics_data: None,
+            loading_state: LoadingState::LoadingInitial,
+            is_loading: true,
+            theme_registry,
+            app_settings,
+            viewport_width: px(f32::MAX),
+            viewport_height: px(f32::MAX),
+            current_time_range: TimeRange::Last30Days,
+            compare_projects: false,
+            compared_projects: [None, None],
+            group_sessions_by_project: false,
+            show_session_table: false,
+            session_filter_focus: cx.focus_handle(),
+            hide_inactive_models: true,
+            show_cost_per_thousand: false,
+            selected_session: None,
+            session_query: String::new(),
+            session_query_focus: cx.focus_handle(),
+            session_note_draft: None,
+            inspected_entry: None,
+            session_note_focus: cx.focus_handle(),
+            sessions_scroll: ScrollHandle::new(),
+            models_scroll: ScrollHandle::new(),
+            projects_scroll: ScrollHandle::new(),
+            collapsed_session_groups: HashSet::new(),
+            hidden_token_categories: HashSet::new(),
+            refresh_task: None,
+            pending_window_geometry: None,
+            window_geometry_task: None,
+            last_refresh: None,
+            is_refreshing: false,
+            toast_message: None,
+            toast_task: None,
+            show_export_menu: false,
+            show_auto_refresh_menu: false,
+            show_debug_panel: false,
+            show_settings_panel: false,
+            show_default_tab_menu: false,
+            card_menu: None,
+            model_audit: None,
+            tab_cost_snapshots: HashMap::new(),
+            show_other_models: false,
+            show_other_projects: false,
+            confirm_settings_reset: false,
+            budget_alert_sent: false,
+        }
+    }
+
+    fn load_data_synchronously(&mut self) {
+        info!("🔄 Starting synchronous analytics data loading...");
+
+        // Load full data once
+        match Self::load_analytics_data_sync(&self.app_settings) {
+            Ok(stats) => {
+                info!(
+                    "✅ Real analytics data loaded successfully with {} entries",
+                    stats.entries.len()
+                );
+                self.full_analytics_data = Some(Arc::new(stats));
+                // Apply initial filter
+                self.apply_time_filter();
+                if self.app_settings.audit_models_on_startup {
+                    self.run_model_audit();
+                }
+                self.loading_state = LoadingState::LoadedFull;
+                self.loading_message = "Dashboard ready - real data loaded".to_string();
+                self.is_loading = false;
+            }
+            Err(e) => {
+                warn!("⚠️ Failed to load real data: {}, using sample data", e);
+                // Kept so the empty-state banner can explain the failure and offer a retry
+                self.loading_state = LoadingState::Error(e.to_string());
+                self.loading_message = "Dashboard ready - using sample data".to_string();
+                self.is_loading = false;
+                // analytics_data remains None, will use sample data
+            }
+        }
+        
+        self.last_refresh = Some(Local::now());
+    }
+
+    /// Check every loaded model id against the pricing table
+    fn run_model_audit(&mut self) {
+        let Some(ref data) = self.full_analytics_data else {
+            return;
+        };
+        let models: HashSet<String> = data.entries.iter().map(|e| e.model.clone()).collect();
+        let audit = CostCalculator::new().classify_models(&models);
+
+        info!("🔍 Pricing audit of {} models:", audit.len());
+        for (model, known) in &audit {
+            info!("  {} {}", if *known { "✅" } else { "❌" }, model);
+        }
+        self.model_audit = Some(audit);
+    }
+
+    pub fn dismiss_model_audit(&mut self, cx: &mut Context<Self>) {
+        self.model_audit = None;
+        cx.notify();
+    }
+
+    /// Costs are computed while loading, so changing the fallback reloads the data
+    pub fn toggle_cache_writes_as_input(&mut self, cx: &mut Context<Self>) {
+        self.app_settings.cache_writes_as_input = !self.app_settings.cache_writes_as_input;
+        self.save_settings(cx);
+        self.refresh_data(cx);
+    }
+
+    /// Raw lines are captured while loading, so turning capture on or off reloads the data
+    pub fn toggle_capture_raw_lines(&mut self, cx: &mut Context<Self>) {
+        self.app_settings.capture_raw_lines = !self.app_settings.capture_raw_lines;
+        info!("🐛 Capture raw log lines: {}", self.app_settings.capture_raw_lines);
+        self.save_settings(cx);
+        self.inspected_entry = None;
+        self.refresh_data(cx);
+    }
+
+    pub fn toggle_recompute_costs(&mut self, cx: &mut Context<Self>) {
+        self.app_settings.recompute_costs = !self.app_settings.recompute_costs;
+        info!("🧾 Recompute costs from tokens: {}", self.app_settings.recompute_costs);
+        self.save_settings(cx);
+        // Entries carry both their logged and computed cost, so no files need re-reading
+        self.reaggregate_full_data();
+        self.apply_time_filter();
+        cx.notify();
+    }
+
+    pub fn toggle_audit_models_on_startup(&mut self, cx: &mut Context<Self>) {
+        self.app_settings.audit_models_on_startup = !self.app_settings.audit_models_on_startup;
+        self.save_settings(cx);
+        cx.notify();
+    }
+
+    /// (Re)start the auto-refresh timer from the current settings.
+    /// Replacing `refresh_task` drops (and so cancels) any previously scheduled timer.
+    fn start_periodic_refresh(&mut self, cx: &mut Context<Self>) {
+        self.refresh_task = None;
+
+        let Some(secs) = self.app_settings.auto_refresh_secs.filter(|secs| *secs > 0) else {
+            info!("📊 Auto-refresh disabled");
+            return;
+        };
+        info!("📊 Auto-refresh enabled - data will refresh every {} seconds", secs);
+
+        self.refresh_task = Some(cx.spawn(async move |this, cx| loop {
+            cx.background_executor()
+                .timer(Duration::from_secs(secs))
+                .await;
+
+            // Stop once the view is gone; skip this tick if a refresh is already running
+            let settings = match this.update(cx, |view, cx| view.begin_refresh(cx)) {
+                Ok(settings) => settings,
+                Err(_) => break,
+            };
+            let Some(settings) = settings else {
+                continue;
+            };
+
+            let result = cx
+                .background_executor()
+                .spawn(async move { RootView::load_analytics_data_sync(&settings) })
+                .await;
+            if this
+                .update(cx, |view, cx| view.finish_refresh(result, cx))
+                .is_err()
+            {
+                break;
+            }
+        }));
+    }
+
+    pub fn set_auto_refresh(&mut self, secs: Option<u64>, cx: &mut Context<Self>) {
+        self.app_settings.auto_refresh_secs = secs;
+        self.save_settings(cx);
+        self.start_periodic_refresh(cx);
+        cx.notify();
+    }
+
+    /// Mark a refresh as started, returning the settings to load with (None if one is in flight)
+    fn begin_refresh(&mut self, cx: &mut Context<Self>) -> Option<AppSettings> {
+        if self.is_refreshing {
+            return None;
+        }
+        info!("🔄 Refreshing analytics data...");
+        self.is_refreshing = true;
+        // With nothing real to show yet, display the tab skeleton instead of sample data
+        if self.full_analytics_data.is_none() {
+            self.is_loading = true;
+        }
+        cx.notify();
+        Some(self.app_settings.clone())
+    }
+
+    fn finish_refresh(&mut self, result: anyhow::Result<UsageStats>, cx: &mut Context<Self>) {
+        match result {
+            Ok(stats) => {
+                info!("✅ Data refreshed successfully with {} entries", stats.entries.len());
+                self.full_analytics_data = Some(Arc::new(stats));
+                self.loading_state = LoadingState::LoadedFull;
+                self.apply_time_filter();
+                self.last_refresh = Some(Local::now());
+                self.check_budget_alert();
+            }
+            Err(e) => {
+                warn!("⚠️ Failed to refresh data: {}", e);
+                // A failed reload keeps whatever real data is already on screen
+                if self.full_analytics_data.is_none() {
+                    self.loading_state = LoadingState::Error(e.to_string());
+                }
+            }
+        }
+
+        self.is_refreshing = false;
+        self.is_loading = false;
+        cx.notify();
+    }
+
+    /// Fire the opt-in over-budget notification the first time this month's spend exceeds the budget
+    fn check_budget_alert(&mut self) {
+        let (Some(budget), Some(full_data)) = (self.app_settings.monthly_budget, self.full_analytics_data.as_ref()) else {
+            return;
+        };
+        if !self.app_settings.budget_notifications {
+            return;
+        }
+
+        let aggregator = UsageAggregator::with_options(self.app_settings.aggregation_options());
+        let spend = aggregator.month_to_date_cost(&full_data.entries);
+        if spend <= budget {
+            // Re-arm so crossing the budget again (e.g. next month) notifies again
+            self.budget_alert_sent = false;
+            return;
+        }
+        if self.budget_alert_sent {
+            return;
+        }
+
+        self.budget_alert_sent = true;
+        let body = format!("{} over your {} monthly budget", self.format_cost(spend - budget), self.format_cost(budget));
+        info!("💸 Monthly budget exceeded: {}", body);
+        if let Err(e) = system::send_notification("Claude usage over budget", &body) {
+            warn!("⚠️ Failed to send budget notification: {}", e);
+        }
+    }
+
+    pub fn toggle_budget_notifications(&mut self, cx: &mut Context<Self>) {
+        self.app_settings.budget_notifications = !self.app_settings.budget_notifications;
+        self.budget_alert_sent = false;
+        self.save_settings(cx);
+        cx.notify();
+    }
+
+    fn refresh_data(&mut self, cx: &mut Context<Self>) {
+        let Some(settings) = self.begin_refresh(cx) else {
+            return; // Already refreshing
+        };
+
+        // Load off the UI thread; the current data stays on screen and usable until
+        // `finish_refresh` swaps in the new stats in one update
+        cx.spawn(async move |this, cx| {
+            let result = cx
+                .background_executor()
+                .spawn(async move { RootView::load_analytics_data_sync(&settings) })
+                .await;
+            let _ = this.update(cx, |view, cx| view.finish_refresh(result, cx));
+        })
+        .detach();
+    }
+
+    /// A reload is running behind data that is already on screen
+    fn is_background_refresh(&self) -> bool {
+        self.is_refreshing && !self.is_loading
+    }
+
+    fn load_analytics_data_sync(settings: &AppSettings) -> anyhow::Result<UsageStats> {
+        // Use the existing analytics processor
+        let processor = UsageProcessor::with_options(settings.processing_options());
+        // No data directory yet (fresh install, headless box, mount still reconnecting):
+        // after a few retries, fall through to the empty state
+        if !processor.wait_for_data_dir() {
+            anyhow::bail!("Data directory not found at {}", processor.data_dir().display());
+        }
+        let entries = processor.process_all_files()?;
+
+        info!("📊 Processing {} usage entries...", entries.len());
+
+        let aggregator = UsageAggregator::with_options(settings.aggregation_options());
+        let mut stats = aggregator.aggregate_entries(entries);
+        stats.parse_errors = processor.parse_errors();
+
+        info!("✅ Analytics computation complete");
+        Ok(stats)
+    }
+
+    /// Get analytics data - real data if loaded, sample data as fallback
+    fn get_analytics_data(&self) -> UsageStats {
+        if let Some(ref real_data) = self.analytics_data {
+            debug!(
+                "🔍 Using real analytics data: ${:.2} total, {} tokens, {} models, {} projects",
+                real_data.total_cost,
+                real_data.total_tokens,
+                real_data.model_stats.len(),
+                real_data.project_stats.len()
+            );
+            (**real_data).clone()
+        } else {
+            debug!("⚠️ Falling back to sample data - real data not loaded");
+            self.get_sample_analytics()
+        }
+    }
+
+    /// Generate sample analytics data for demonstration (fallback when real data not loaded)
+    fn get_sample_analytics(&self) -> UsageStats {
+        let mut model_stats = HashMap::new();
+
+        // Claude 3.5 Sonnet
+        model_stats.insert(
+            "claude-3-5-sonnet-20241022".to_string(),
+            ModelStats {
+                model: "claude-3-5-sonnet-20241022".to_string(),
+                display_name: "Claude 3.5 Sonnet".to_string(),
+                total_cost: 12.45,
+                total_tokens: 125000,
+                input_tokens: 75000,
+                output_tokens: 35000,
+                cache_read_tokens: 10000,
+                cache_creation_tokens: 5000,
+                request_count: 156,
+            },
+        );
+
+        // Claude 3 Opus
+        model_stats.insert(
+            "claude-3-opus-20240229".to_string(),
+            ModelStats {
+                model: "claude-3-opus-20240229".to_string(),
+                display_name: "Claude 3 Opus".to_string(),
+                total_cost: 8.72,
+                total_tokens: 42000,
+                input_tokens: 28000,
+                output_tokens: 12000,
+                cache_read_tokens: 1500,
+                cache_creation_tokens: 500,
+                request_count: 47,
+            },
+        );
+
+        // Claude 3 Haiku
+        model_stats.insert(
+            "claude-3-haiku-20240307".to_string(),
+            ModelStats {
+                model: "claude-3-haiku-20240307".to_string(),
+                display_name: "Claude 3 Haiku".to_string(),
+                total_cost: 2.31,
+                total_tokens: 89000,
+                input_tokens: 65000,
+                output_tokens: 20000,
+                cache_read_tokens: 3000,
+                cache_creation_tokens: 1000,
+                request_count: 203,
+            },
+        );
+
+        let mut project_stats = HashMap::new();
+        project_stats.insert(
+            "/Users/dev/rust-project".to_string(),
+            ProjectStats {
+                project_name: "rust-project".to_string(),
+                project_path: "/Users/dev/rust-project".to_string(),
+                total_cost: 15.23,
+                total_tokens: 145000,
+                input_tokens: 95000,
+                output_tokens: 35000,
+                cache_read_tokens: 10000,
+                cache_creation_tokens: 5000,
+                request_count: 198,
+                session_count: 12,
+                cost_per_request: 15.23 / 198.0,
+                avg_session_cost: 15.23 / 12.0,
+                active_days: 8,
+                sessions_per_active_day: 12.0 / 8.0,
+                last_used: chrono::Utc::now(),
+            },
+        );
+
+        UsageStats {
+            total_cost: 23.48,
+            total_input_tokens: 168000,
+            total_output_tokens: 67000,
+            total_cache_read_tokens: 14500,
+            total_cache_creation_tokens: 6500,
+            total_tokens: 256000,
+            session_count: 15,
+            entries: vec![], // Empty for demo
+            model_stats,
+            project_stats,
+            session_stats: HashMap::new(),
+            daily_usage: HashMap::new(),
+            requests_over_cost_alert: 0,
+            most_expensive_request: None,
+            parse_errors: ParseErrorLog::default(),
+        }
+    }
+
+    /// Get sessions data - real data if loaded, sample data as fallback
+    fn get_sessions_data(&self) -> Vec<SessionStats> {
+        if let Some(ref real_data) = self.analytics_data {
+            // Extract sessions from real analytics data, newest first
+            let mut sessions = real_data
+                .session_stats
+                .values()
+                .cloned()
+                .collect::<Vec<_>>();
+            sessions.sort_by(|a, b| b.end_time.cmp(&a.end_time).then_with(|| a.project_path.cmp(&b.project_path)));
+            sessions
+        } else {
+            self.get_sample_sessions_analytics()
+        }
+    }
+
+    /// Generate sample session analytics data for demonstration
+    fn get_sample_sessions_analytics(&self) -> Vec<SessionStats> {
+        use chrono::{Duration, Utc};
+
+        vec![
+            SessionStats {
+                session_id: "session_2024071201".to_string(),
+                project_path: "/Users/dev/rust-project".to_string(),
+                total_cost: 8.45,
+                total_tokens: 89000,
+                input_tokens: 65000,
+                output_tokens: 18000,
+                cache_read_tokens: 4000,
+                cache_creation_tokens: 2000,
+                request_count: 87,
+                start_time: Utc::now() - Duration::hours(2) - Duration::minutes(45),
+                end_time: Utc::now() - Duration::hours(2),
+            },
+            SessionStats {
+                session_id: "session_2024071901".to_string(),
+                project_path: "/Users/dev/web-app".to_string(),
+                total_cost: 6.72,
+                total_tokens: 76000,
+                input_tokens: 52000,
+                output_tokens: 16000,
+                cache_read_tokens: 5000,
+                cache_creation_tokens: 3000,
+                request_count: 65,
+                start_time: Utc::now() - Duration::days(1) - Duration::hours(4) - Duration::minutes(45),
+                end_time: Utc::now() - Duration::days(1) - Duration::hours(4),
+            },
+            SessionStats {
+                session_id: "session_2024071902".to_string(),
+                project_path: "/Users/dev/rust-project".to_string(),
+                total_cost: 4.23,
+                total_tokens: 45000,
+                input_tokens: 32000,
+                output_tokens: 9000,
+                cache_read_tokens: 3000,
+                cache_creation_tokens: 1000,
+                request_count: 34,
+                start_time: Utc::now() - Duration::days(1) - Duration::hours(8) - Duration::minutes(45),
+                end_time: Utc::now() - Duration::days(1) - Duration::hours(8),
+            },
+            SessionStats {
+                session_id: "session_2024071801".to_string(),
+                project_path: "/Users/dev/python-tool".to_string(),
+                total_cost: 3.08,
+                total_tokens: 38000,
+                input_tokens: 28000,
+                output_tokens: 7000,
+                cache_read_tokens: 2000,
+                cache_creation_tokens: 1000,
+                request_count: 23,
+                start_time: Utc::now() - Duration::days(2) - Duration::hours(6) - Duration::minutes(45),
+                end_time: Utc::now() - Duration::days(2) - Duration::hours(6),
+            },
+            SessionStats {
+                session_id: "session_2024071701".to_string(),
+                project_path: "/Users/dev/web-app".to_string(),
+                total_cost: 1.25,
+                total_tokens: 15000,
+                input_tokens: 11000,
+                output_tokens: 3000,
+                cache_read_tokens: 800,
+                cache_creation_tokens: 200,
+                request_count: 12,
+                start_time: Utc::now() - Duration::days(3) - Duration::hours(10) - Duration::minutes(45),
+                end_time: Utc::now() - Duration::days(3) - Duration::hours(10),
+            },
+        ]
+    }
+
+    /// Compact bar series of cost over the most recent active days
+    fn render_sparkline(&self, daily: &[DailyUsage]) -> Div {
+        const SPARKLINE_DAYS: usize = 14;
+        const SPARKLINE_HEIGHT: f32 = 24.0;
+
+        let theme = self.theme_registry.colors();
+        let mut active_days: Vec<&DailyUsage> =
+            daily.iter().filter(|d| d.request_count > 0).collect();
+        active_days.sort_by(|a, b| a.date.cmp(&b.date));
+        let recent = &active_days[active_days.len().saturating_sub(SPARKLINE_DAYS)..];
+
+        // A single bar is not a trend; skip the sparkline until there is more data
+        if recent.len() < 2 {
+            return div();
+        }
+
+        let max_cost = recent.iter().map(|d| d.total_cost).fold(0.0, f64::max);
+
+        div()
+            .flex()
+            .items_end()
+            .gap(px(1.0))
+            .h(px(SPARKLINE_HEIGHT))
+            .children(recent.iter().map(|day| {
+                let ratio = if max_cost > 0.0 {
+                    (day.total_cost / max_cost) as f32
+                } else {
+                    0.0
+                };
+                div()
+                    .w(px(4.0))
+                    .h(px((ratio * SPARKLINE_HEIGHT).max(2.0)))
+                    .bg(theme.text_accent)
+                    .rounded_sm()
+            }))
+    }
+
+    /// Header status dot: loading, refreshing behind current data, loaded, or no data
+    fn status_dot_color(&self) -> Hsla {
+        let theme = self.theme_registry.colors();
+        if self.is_loading {
+            theme.text_accent // Blue while there is nothing to show yet
+        } else if self.is_refreshing {
+            theme.metric_quaternary // Orange when refreshing
+        } else if self.analytics_data.is_some() {
+            theme.success // Green when data is loaded
+        } else {
+            theme.text_muted // Gray when no data
+        }
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = self.theme_registry.colors();
+        let sparkline_data = self
+            .analytics_data
+            .as_ref()
+            .map(|data| data.daily_usage.values().cloned().collect::<Vec<_>>())
+            .unwrap_or_default();
+
+        div()
+            .flex()
+            .justify_between()
+            .items_center()
+            .bg(theme.elevated_surface)
+            .border_b_1()
+            .border_color(theme.border)
+            .px_6()
+            .py_4()
+            .child(
+                // Title
+                div()
+                    .text_2xl()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(theme.text_accent)
+                    .child("Claude Code Usage Dashboard"),
+            )
+            .child(
+                // Right side: Status indicator and theme toggle
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_4()
+                    .child(
+                        // Filtered grand totals, visible on every tab
+                        self.render_header_rollup(),
+                    )
+                    .child(
+                        // Recent daily cost trend
+                        self.render_sparkline(&sparkline_data),
+                    )
+                    .child(
+                        // Status indicator
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_2()
+                            .child(
+                                // Show last refresh time only
+                                if let Some(ref last_refresh) = self.last_refresh {
+                                    let now = Local::now();
+                                    let duration = now.signed_duration_since(*last_refresh);
+                                    let time_str = if duration.num_seconds() < 60 {
+                                        format!("{}s ago", duration.num_seconds())
+                                    } else if duration.num_minutes() < 60 {
+                                        format!("{}m ago", duration.num_minutes())
+                                    } else {
+                                        format!("{}h ago", duration.num_hours())
+                                    };
+                                    div()
+                                        .text_sm()
+                                        .text_color(theme.text_muted)
+                                        .child(if self.is_refreshing {
+                                            "Refreshing...".to_string()
+                                        } else {
+                                            format!("Updated {}", time_str)
+                                        })
+                                } else {
+                                    div()
+                                        .text_sm()
+                                        .text_color(theme.text_muted)
+                                        .child("Loading...")
+                                },
+                            )
+                            .child(
+                                // Status dot
+                                div()
+                                    .w_3() // Make it slightly bigger
+                                    .h_3() // Make it slightly bigger
+                                    .rounded_full()
+                                    .bg(self.status_dot_color()),
+                            ),
+                    )
+                    .child(
+                        // Jump to a session by id prefix
+                        self.render_session_search(cx),
+                    )
+                    .child(
+                        // Time range filter buttons
+                        self.render_time_range_filter(cx),
+                    )
+                    .when_some(self.analysis_start_label(), |header, label| {
+                        // Every range is clipped to the analysis floor; open settings to change it
+                        header.child(self.render_chip_button(format!("Since {}", label), true, cx, |view, cx| {
+                            view.toggle_settings_panel(cx)
+                        }))
+                    })
+                    .when(self.has_unseen_spend(), |header| {
+                        // Clear every tab's new-spend badge
+                        header.child(self.render_chip_button("Mark all seen", false, cx, |view, cx| {
+                            view.mark_all_tabs_seen(cx)
+                        }))
+                    })
+                    .child(
+                        // Manual refresh button
+                        self.render_refresh_button(cx),
+                    )
+                    .child(
+                        // Auto-refresh interval selector
+                        self.render_auto_refresh_button(cx),
+                    )
+                    .child(
+                        // Open the usage data folder
+                        self.render_open_data_dir_button(cx),
+                    )
+                    .child(
+                        // Export menu button
+                        self.render_export_button(cx),
+                    )
+                    .child(
+                        // Theme toggle button
+                        self.render_theme_toggle(cx),
+                    )
+                    .child(
+                        // Settings panel button
+                        self.render_settings_button(cx),
+                    ),
+            )
+    }
+
+    /// Cost, tokens and requests for the current filters, or dashes while nothing is loaded
+    fn header_rollup(&self) -> [(&'static str, String); 3] {
+        let totals = self.analytics_data.as_ref().filter(|_| !self.is_loading).map(|data| {
+            let requests = data.model_stats.values().map(|m| m.request_count as u64).sum::<u64>();
+            (
+                self.format_cost(data.total_cost),
+                formatting::format_count(data.total_tokens, NumberFormat::Abbreviated),
+                formatting::format_count(requests, NumberFormat::Abbreviated),
+            )
+        });
+        let (cost, tokens, requests) =
+            totals.unwrap_or_else(|| ("—".to_string(), "—".to_string(), "—".to_string()));
+        [("cost", cost), ("tokens", tokens), ("requests", requests)]
+    }
+
+    fn render_header_rollup(&self) -> impl IntoElement {
+        let theme = self.theme_registry.colors();
+        let excluded = self.app_settings.excluded_count();
+
+        div()
+            .flex()
+            .items_center()
+            .gap_3()
+            .px_3()
+            .py_1()
+            .rounded_md()
+            .bg(theme.surface)
+            .text_sm()
+            .children(self.header_rollup().into_iter().map(|(label, value)| {
+                div()
+                    .flex()
+                    .items_baseline()
+                    .gap_1()
+                    .child(div().font_weight(FontWeight::SEMIBOLD).text_color(theme.text).child(value))
+                    .child(div().text_xs().text_color(theme.text_muted).child(label))
+            }))
+            .when(excluded > 0, |rollup| {
+                rollup.child(
+                    div()
+                        .text_xs()
+                        .text_color(theme.warning)
+                        .child(format!("{} excluded", excluded)),
+                )
+            })
+    }
+
+    fn render_tab_navigation(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = self.theme_registry.colors();
+
+        div()
+            .flex()
+            .justify_between()
+            .bg(theme.surface)
+            .border_b_1()
+            .border_color(theme.border)
+            .px_6()
+            .child(
+                div()
+                    .flex()
+                    .children(
+                        self.app_settings
+                            .shown_tabs()
+                            .into_iter()
+                            .enumerate()
+                            .map(|(index, tab)| {
+                                let is_active = self.active_tab == tab;
+                                let key_number = index + 1;
+                                let tab_clone = tab.clone();
+                                let activate_tab = tab.clone();
+                                let text_accent = theme.text_accent;
+
+                                let tab_element = div()
+                                    .px_4()
+                                    .py_3()
+                                    .cursor_pointer()
+                                    .on_mouse_down(
+                                        gpui::MouseButton::Left,
+                                        cx.listener(move |view, _event, _window, cx| {
+                                            view.set_active_tab(tab_clone.clone(), cx);
+                                        }),
+                                    )
+                                    .border_b_2()
+                                    .border_color(if is_active {
+                                        theme.text_accent
+                                    } else {
+                                        hsla(0.0, 0.0, 0.0, 0.0) // Transparent
+                                    })
+                                    .text_color(if is_active {
+                                        theme.text_accent
+                                    } else {
+                                        theme.text_muted
+                                    })
+                                    .font_weight(if is_active {
+                                        FontWeight::SEMIBOLD
+                                    } else {
+                                        FontWeight::NORMAL
+                                    })
+                                    .hover(|style| style.text_color(text_accent))
+                                    .child(
+                                        div()
+                                            .flex()
+                                            .items_center()
+                                            .gap_2()
+                                            .child(format!("{}", key_number))
+                                            .child(tab.title())
+                                            .children(self.tab_spend_delta(&tab).map(|delta| {
+                                                div()
+                                                    .px_1()
+                                                    .rounded_sm()
+                                                    .bg(theme.elevated_surface)
+                                                    .text_xs()
+                                                    .font_weight(FontWeight::SEMIBOLD)
+                                                    .text_color(theme.success)
+                                                    .child(format!("+{}", self.format_cost(delta)))
+                                            })),
+                                    );
+
+                                self.focusable_control(
+                                    tab_element,
+                                    &Self::tab_focus_key(&tab),
+                                    cx,
+                                    move |view, cx| view.set_active_tab(activate_tab.clone(), cx),
+                                )
+                            }),
+                    ),
+            )
+            .child(
+                div()
+                    .py_3()
+                    .text_xs()
+                    .text_color(theme.text_muted)
+                    .child(format!(
+                        "Press 1-{} to switch tabs • Alt+1-6 for time ranges • G to go to a session • ? for all shortcuts",
+                        self.app_settings.shown_tabs().len()
+                    )),
+            )
+    }
+
+    /// Reveal the usage data directory (or its closest existing parent) in the file manager
+    pub fn open_data_directory(&mut self, cx: &mut Context<Self>) {
+        let result = system::open_in_file_manager(&UsageProcessor::default_data_dir());
+        match result {
+            Ok(opened) => info!("📂 Opened {:?}", opened),
+            Err(e) => {
+                warn!("⚠️ Failed to open data directory: {}", e);
+                self.show_toast(format!("Could not open data folder: {}", e), cx);
+            }
+        }
+    }
+
+    fn render_open_data_dir_button(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let colors = self.theme_registry.colors();
+        let elevated_surface = colors.elevated_surface;
+        let border_color = colors.border;
+
+        div()
+            .id("open-data-dir-button")
+            .flex()
+            .items_center()
+            .justify_center()
+            .w(px(40.0))
+            .h(px(32.0))
+            .bg(colors.surface)
+            .border_1()
+            .border_color(colors.border)
+            .rounded(px(6.0))
+            .cursor_pointer()
+            .hover(move |style| style.bg(elevated_surface))
+            .active(move |style| style.bg(border_color))
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(|view: &mut RootView, _event, _window, cx| {
+                    view.open_data_directory(cx);
+                }),
+            )
+            .child(
+                div()
+                    .text_size(px(14.0))
+                    .text_color(colors.text)
+                    .child("📂"),
+            )
+    }
+
+    /// Banner shown when no real data could be loaded and sample data is displayed instead
+    fn render_empty_state_banner(&self, cx: &mut Context<Self>) -> Div {
+        let theme = self.theme_registry.colors();
+        if self.is_loading || self.full_analytics_data.is_some() {
+            return div();
+        }
+        let detail = match &self.loading_state {
+            LoadingState::Error(e) => format!("Could not load usage data: {}", e),
+            _ => "Claude Code logs are read from ~/.claude/projects".to_string(),
+        };
+
+        div()
+            .flex()
+            .items_center()
+            .justify_between()
+            .gap_4()
+            .mb_6()
+            .p_4()
+            .bg(theme.surface)
+            .border_1()
+            .border_color(theme.border)
+            .rounded_lg()
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_1()
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(theme.text)
+                            .child("No usage data found - showing sample data"),
+                    )
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(theme.text_muted)
+                            .child(detail),
+                    ),
+            )
+            .child(
+                div()
+                    .flex()
+                    .gap_2()
+                    .child(self.render_chip_button("Retry", false, cx, |view, cx| view.refresh_data(cx)))
+                    .child(self.render_chip_button("Open data folder", false, cx, |view, cx| {
+                        view.open_data_directory(cx);
+                    })),
+            )
+    }
+
+    /// Warning listing models the startup audit found without pricing
+    fn render_model_audit_banner(&self, cx: &mut Context<Self>) -> Div {
+        let theme = self.theme_registry.colors();
+        let unknown: Vec<&str> = self
+            .model_audit
+            .iter()
+            .flatten()
+            .filter(|(_, known)| !known)
+            .map(|(model, _)| model.as_str())
+            .collect();
+        if unknown.is_empty() {
+            return div();
+        }
+
+        div()
+            .flex()
+            .items_start()
+            .justify_between()
+            .gap_4()
+            .mb_6()
+            .p_4()
+            .bg(theme.surface)
+            .border_1()
+            .border_color(theme.warning)
+            .rounded_lg()
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_1()
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(theme.warning)
+                            .child(format!(
+                                "⚠ {} of {} models have no pricing - their usage counts as $0",
+                                unknown.len(),
+                                self.model_audit.as_ref().map(Vec::len).unwrap_or(0)
+                            )),
+                    )
+                    .children(unknown.into_iter().map(|model| {
+                        div().text_xs().text_color(theme.text).child(model.to_string())
+                    })),
+            )
+            .child(self.render_chip_button("Dismiss", false, cx, |view, cx| view.dismiss_model_audit(cx)))
+    }
+
+    fn render_main_content(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .id("main-content")
+            .flex_1()
+            .h_full()
+            .overflow_scroll()
+            .p_6()
+            .child(self.render_empty_state_banner(cx))
+            .child(self.render_model_audit_banner(cx))
+            .child(if self.is_loading {
+                // Only the very first load gets the global spinner; reloads keep the tab's shape
+                if matches!(self.loading_state, LoadingState::LoadingInitial) {
+                    self.render_loading_content()
+                } else {
+                    self.render_skeleton_for_tab(&self.active_tab)
+                }
+            } else {
+                // Stale data is dimmed, not hidden, while a background refresh runs
+                self.render_active_tab_content(cx)
+                    .when(self.is_background_refresh(), |content| content.opacity(0.6))
+            })
+    }
+
+    fn render_loading_content(&self) -> Div {
+        let theme = self.theme_registry.colors();
+        div()
+            .flex()
+            .flex_col()
+            .items_center()
+            .justify_center()
+            .h_96()
+            .gap_6()
+            .child(
+                // Loading spinner placeholder (would be an actual spinner in real UI)
+                div()
+                    .w_8()
+                    .h_8()
+                    .border_2()
+                    .border_color(theme.text_accent)
+                    .rounded_full(), // Note: GPUI doesn't have built-in animations, but this represents a spinner
+            )
+            .child(
+                div()
+                    .text_lg()
+                    .font_weight(FontWeight::MEDIUM)
+                    .text_color(theme.text)
+                    .child(self.loading_message.clone()),
+            )
+            .child(
+                div()
+                    .text_sm()
+                    .text_color(theme.text_muted)
+                    .child(match &self.loading_state {
+                        LoadingState::LoadingInitial => {
+                            "Scanning Claude usage files...".to_string()
+                        }
+                        LoadingState::LoadedFull => "Processing usage data...".to_string(),
+                        LoadingState::Error(e) => format!("Error: {}", e),
+                    }),
+            )
+    }
+
+    /// Gray placeholder block used by the loading skeletons
+    fn render_skeleton_block(&self, height: Pixels) -> Div {
+        let theme = self.theme_registry.colors();
+        div()
+            .w_full()
+            .h(height)
+            .bg(theme.surface)
+            .border_1()
+            .border_color(theme.border)
+            .rounded_lg()
+    }
+
+    /// Row of placeholders shaped like `render_metric_card`
+    fn render_skeleton_metric_row(&self, count: usize) -> Div {
+        div()
+            .flex()
+            .gap_4()
+            .children((0..count).map(|_| div().flex_1().child(self.render_skeleton_block(px(104.0)))))
+    }
+
+    /// Vertical list of placeholders shaped like project/session/model cards
+    fn render_skeleton_list(&self, rows: usize, row_height: Pixels) -> Div {
+        div()
+            .flex()
+            .flex_col()
+            .gap_4()
+            .children((0..rows).map(|_| self.render_skeleton_block(row_height)))
+    }
+
+    /// Content-shaped placeholders for a tab while its data reloads
+    fn render_skeleton_for_tab(&self, tab: &DashboardTab) -> Div {
+        let theme = self.theme_registry.colors();
+        let title = div()
+            .w(px(280.0))
+            .h(px(36.0))
+            .bg(theme.surface)
+            .rounded_md();
+
+        let body = match tab {
+            DashboardTab::Overview => div()
+                .flex()
+                .flex_col()
+                .gap_6()
+                .child(self.render_skeleton_metric_row(4))
+                .child(self.render_skeleton_block(px(104.0)))
+                .child(self.render_skeleton_block(px(240.0))),
+            DashboardTab::Models => self.render_skeleton_list(3, px(220.0)),
+            DashboardTab::Projects => div()
+                .flex()
+                .flex_col()
+                .gap_6()
+                .child(self.render_skeleton_metric_row(4))
+                .child(self.render_skeleton_list(4, px(160.0))),
+            DashboardTab::Sessions => div()
+                .flex()
+                .flex_col()
+                .gap_6()
+                .child(self.render_skeleton_metric_row(4))
+                .child(self.render_skeleton_list(5, px(140.0))),
+            DashboardTab::Timeline => div()
+                .flex()
+                .flex_col()
+                .gap_6()
+                .child(self.render_skeleton_metric_row(4))
+                .child(self.render_skeleton_list(8, px(28.0))),
+        };
+
+        div().flex().flex_col().gap_6().child(title).child(body)
+    }
+
+    fn render_active_tab_content(&self, cx: &mut Context<Self>) -> Div {
+        match &self.active_tab {
+            DashboardTab::Overview => self.render_overview_content(cx),
+            DashboardTab::Models => self.render_models_content(cx),
+            DashboardTab::Projects => self.render_projects_content(cx),
+            DashboardTab::Sessions => self.render_sessions_content(cx),
+            DashboardTab::Timeline => self.render_timeline_content(cx),
+        }
+    }
+
+    fn render_overview_content(&self, cx: &mut Context<Self>) -> Div {
+        let theme = self.theme_registry.colors();
+        let analytics = self.get_analytics_data();
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_6()
+            .child(
+                div()
+                    .text_3xl()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(theme.text)
+                    .child("Usage Overview"),
+            )
+            .child(
+                self.metric_card_row()
+                    .child(self.render_metric_card(
+                        "Total Cost",
+                        self.format_cost(analytics.total_cost),
+                        MetricType::Primary,
+                    ))
+                    .child(self.render_metric_card(
+                        "Total Tokens",
+                        self.format_number(analytics.total_tokens),
+                        MetricType::Secondary,
+                    ))
+                    .child(self.render_metric_card(
+                        "Sessions",
+                        self.format_number(analytics.session_count as u64),
+                        MetricType::Tertiary,
+                    ))
+                    .child(self.render_metric_card(
+                        "Models Used",
+                        analytics.model_stats.len().to_string(),
+                        MetricType::Quaternary,
+                    )),
+            )
+            .child(self.render_busiest_project_card())
+            .children(self.render_run_rate_card())
+            .child(self.render_leaderboards(&analytics))
+            .children(self.render_expensive_request_card(&analytics, cx))
+            .when(!self.app_settings.ignore_cache, |content| {
+                content.child(self.render_cache_efficiency_card(&analytics))
+            })
+            .child(self.render_breakdown_section(&analytics, cx))
+    }
+
+    /// Top 5 projects and models by cost, side by side
+    fn render_leaderboards(&self, analytics: &UsageStats) -> Div {
+        let theme = self.theme_registry.colors();
+        let projects = UsageAggregator::top_by_cost(
+            analytics.project_stats.values().collect(),
+            LEADERBOARD_SIZE,
+            |p| p.total_cost,
+            |p| p.project_name.as_str(),
+        )
+        .into_iter()
+        .map(|p| (p.project_name.clone(), p.total_cost, theme.text_accent))
+        .collect();
+        let models = UsageAggregator::top_by_cost(
+            analytics.model_stats.values().collect(),
+            LEADERBOARD_SIZE,
+            |m| m.total_cost,
+            |m| m.display_name.as_str(),
+        )
+        .into_iter()
+        .map(|m| (m.display_name.clone(), m.total_cost, self.model_color(&m.model)))
+        .collect();
+
+        div()
+            .flex()
+            .gap_6()
+            .child(self.render_leaderboard("Top Projects by Cost", projects, analytics.total_cost))
+            .child(self.render_leaderboard("Top Models by Cost", models, analytics.total_cost))
     }
 
-    fn render_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+    /// Ranked rows of (name, cost, bar color) with each row's share of `total_cost`
+    fn render_leaderboard(&self, title: &str, rows: Vec<(String, f64, Hsla)>, total_cost: f64) -> Div {
         let theme = self.theme_registry.colors();
 
         div()
+            .flex_1()
+            .p_6()
+            .bg(theme.surface)
+            .rounded_lg()
+            .border_1()
+            .border_color(theme.border)
+            .shadow_sm()
             .flex()
-            .justify_between()
-            .items_center()
-            .bg(theme.elevated_surface)
-            .border_b_1()
+            .flex_col()
+            .gap_3()
+            .child(
+                div()
+                    .text_xl()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(theme.text)
+                    .child(title.to_string()),
+            )
+            .when(rows.is_empty(), |board| {
+                board.child(
+                    div()
+                        .text_sm()
+                        .text_color(theme.text_muted)
+                        .child("No usage in this time range"),
+                )
+            })
+            .children(rows.into_iter().enumerate().map(|(index, (name, cost, color))| {
+                let share = if total_cost > 0.0 { (cost / total_cost) as f32 } else { 0.0 };
+
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_3()
+                    .text_sm()
+                    .child(
+                        div()
+                            .w(px(20.0))
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(theme.text_muted)
+                            .child(format!("{}", index + 1)),
+                    )
+                    .child(div().flex_1().overflow_hidden().text_color(theme.text).child(name))
+                    .child(
+                        div()
+                            .w(px(80.0))
+                            .h(px(6.0))
+                            .rounded_full()
+                            .bg(theme.border)
+                            .child(
+                                div()
+                                    .h_full()
+                                    .w(relative(share.clamp(MIN_SHARE_BAR_FRACTION, 1.0)))
+                                    .rounded_full()
+                                    .bg(color),
+                            ),
+                    )
+                    .child(
+                        div()
+                            .w(px(70.0))
+                            .text_right()
+                            .text_color(theme.success)
+                            .child(self.format_cost(cost)),
+                    )
+            }))
+    }
+
+    fn render_breakdown_section(&self, analytics: &UsageStats, cx: &mut Context<Self>) -> Div {
+        div()
+            .mt_8()
+            .flex()
+            .gap_6()
+            .child(self.render_model_breakdown(analytics, cx))
+            .child(self.render_cost_breakdown(analytics, cx))
+    }
+
+    fn render_model_breakdown(&self, analytics: &UsageStats, cx: &mut Context<Self>) -> Div {
+        let theme = self.theme_registry.colors();
+        let mut models = analytics.model_stats.values().cloned().collect::<Vec<_>>();
+        models.sort_by(|a, b| b.total_cost.partial_cmp(&a.total_cost).unwrap_or(std::cmp::Ordering::Equal));
+        let (models, minor) = UsageAggregator::split_minor_contributors(
+            models,
+            |model| model.total_cost,
+            self.app_settings.minor_contributor_threshold,
+        );
+
+        // The grouped models summed into one row
+        let other = (!minor.is_empty()).then(|| ModelStats {
+            model: "other".to_string(),
+            display_name: format!("{} Other ({} models)", if self.show_other_models { "▾" } else { "▸" }, minor.len()),
+            total_cost: minor.iter().map(|m| m.total_cost).sum(),
+            total_tokens: minor.iter().map(|m| m.total_tokens).sum(),
+            input_tokens: minor.iter().map(|m| m.input_tokens).sum(),
+            output_tokens: minor.iter().map(|m| m.output_tokens).sum(),
+            cache_read_tokens: minor.iter().map(|m| m.cache_read_tokens).sum(),
+            cache_creation_tokens: minor.iter().map(|m| m.cache_creation_tokens).sum(),
+            request_count: minor.iter().map(|m| m.request_count).sum(),
+        });
+
+        div()
+            .flex_1()
+            .p_6()
+            .bg(theme.surface)
+            .rounded_lg()
+            .border_1()
             .border_color(theme.border)
-            .px_6()
-            .py_4()
+            .shadow_sm()
             .child(
-                // Title
                 div()
-                    .text_2xl()
-                    .font_weight(FontWeight::BOLD)
-                    .text_color(theme.text_accent)
-                    .child("Claude Code Usage Dashboard"),
+                    .text_xl()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(theme.text)
+                    .mb_4()
+                    .child("Usage by Model"),
             )
             .child(
-                // Right side: Status indicator and theme toggle
                 div()
                     .flex()
+                    .flex_col()
+                    .gap_3()
+                    .children(
+                        models
+                            .iter()
+                            .map(|model| self.render_model_item(model, analytics.total_cost))
+                            .collect::<Vec<_>>(),
+                    )
+                    .children(other.map(|other| {
+                        div()
+                            .id("other-models")
+                            .cursor_pointer()
+                            .on_click(cx.listener(|view, _event, _window, cx| {
+                                view.show_other_models = !view.show_other_models;
+                                cx.notify();
+                            }))
+                            .child(self.render_model_item(&other, analytics.total_cost))
+                    }))
+                    .when(self.show_other_models, |list| {
+                        list.children(minor.iter().map(|model| {
+                            div().pl_6().child(self.render_model_item(model, analytics.total_cost))
+                        }))
+                    }),
+            )
+    }
+
+    fn render_model_item(&self, model: &ModelStats, total_cost: f64) -> Div {
+        let theme = self.theme_registry.colors();
+        let percentage = if total_cost > 0.0 {
+            (model.total_cost / total_cost * 100.0) as u32
+        } else {
+            0
+        };
+
+        let share = if total_cost > 0.0 { model.total_cost / total_cost } else { 0.0 };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .p_3()
+            .bg(theme.elevated_surface)
+            .rounded_md()
+            .child(
+                div()
+                    .flex()
+                    .justify_between()
                     .items_center()
-                    .gap_4()
                     .child(
-                        // Status indicator
                         div()
                             .flex()
-                            .items_center()
-                            .gap_2()
+                            .flex_col()
                             .child(
-                                // Show last refresh time only
-                                if let Some(ref last_refresh) = self.last_refresh {
-                                    let now = Local::now();
-                                    let duration = now.signed_duration_since(*last_refresh);
-                                    let time_str = if duration.num_seconds() < 60 {
-                                        format!("{}s ago", duration.num_seconds())
-                                    } else if duration.num_minutes() < 60 {
-                                        format!("{}m ago", duration.num_minutes())
-                                    } else {
-                                        format!("{}h ago", duration.num_hours())
-                                    };
-                                    div()
-                                        .text_sm()
-                                        .text_color(theme.text_muted)
-                                        .child(if self.is_refreshing {
-                                            "Refreshing...".to_string()
-                                        } else {
-                                            format!("Updated {}", time_str)
-                                        })
-                                } else {
-                                    div()
-                                        .text_sm()
-                                        .text_color(theme.text_muted)
-                                        .child("Loading...")
-                                },
+                                div()
+                                    .text_sm()
+                                    .font_weight(FontWeight::MEDIUM)
+                                    .text_color(theme.text)
+                                    .child(model.display_name.clone()),
                             )
                             .child(
-                                // Status dot
                                 div()
-                                    .w_3() // Make it slightly bigger
-                                    .h_3() // Make it slightly bigger
-                                    .rounded_full()
-                                    .bg({
-                                        let color = if self.is_refreshing {
-                                            theme.metric_quaternary // Orange when refreshing
-                                        } else if !self.is_loading
-                                            && self.analytics_data.is_some()
-                                        {
-                                            theme.success // Green when data is loaded
-                                        } else if self.is_loading {
-                                            theme.text_accent // Blue during loading
-                                        } else {
-                                            theme.text_muted // Gray when no data
-                                        };
-                                        color
-                                    }),
+                                    .text_xs()
+                                    .text_color(theme.text_muted)
+                                    .child(format!("{} requests", self.format_number(model.request_count as u64))),
                             ),
                     )
                     .child(
-                        // Time range filter buttons
-                        self.render_time_range_filter(cx),
-                    )
+                        div()
+                            .text_right()
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .font_weight(FontWeight::SEMIBOLD)
+                                    .text_color(theme.success)
+                                    .child(self.format_cost(model.total_cost)),
+                            )
+                            .child(
+                                div()
+                                    .text_xs()
+                                    .text_color(theme.text_muted)
+                                    .child(format!("{}%", percentage)),
+                            ),
+                    ),
+            )
+            .child(self.render_share_bar(share, self.model_color(&model.model)))
+    }
+
+    /// Thin inline bar showing a row's share of total cost
+    fn render_share_bar(&self, share: f64, color: Hsla) -> Div {
+        let theme = self.theme_registry.colors();
+        let fraction = if share > 0.0 {
+            (share as f32).clamp(MIN_SHARE_BAR_FRACTION, 1.0)
+        } else {
+            0.0
+        };
+
+        div()
+            .w_full()
+            .h(px(4.0))
+            .rounded_sm()
+            .bg(theme.border)
+            .child(div().h_full().w(relative(fraction)).rounded_sm().bg(color))
+    }
+
+    pub fn toggle_token_total_policy(&mut self, cx: &mut Context<Self>) {
+        self.app_settings.token_total_policy = match self.app_settings.token_total_policy {
+            TokenTotalPolicy::IncludeCache => TokenTotalPolicy::ExcludeCache,
+            TokenTotalPolicy::ExcludeCache => TokenTotalPolicy::IncludeCache,
+        };
+        info!("🔢 Token total policy: {:?}", self.app_settings.token_total_policy);
+        self.save_settings(cx);
+        // Totals are derived during aggregation, so recompute from the cached entries
+        self.reaggregate_full_data();
+        self.apply_time_filter();
+        cx.notify();
+    }
+
+    pub fn toggle_ignore_cache(&mut self, cx: &mut Context<Self>) {
+        self.app_settings.ignore_cache = !self.app_settings.ignore_cache;
+        info!("🧮 Ignore cache: {}", self.app_settings.ignore_cache);
+        self.save_settings(cx);
+        self.apply_time_filter();
+        cx.notify();
+    }
+
+    pub fn toggle_quiet_hours(&mut self, cx: &mut Context<Self>) {
+        self.app_settings.quiet_hours_enabled = !self.app_settings.quiet_hours_enabled;
+        info!(
+            "🌙 Quiet hours {}: {}",
+            self.app_settings.quiet_hours.label(),
+            self.app_settings.quiet_hours_enabled
+        );
+        self.filter_settings_changed(cx);
+    }
+
+    /// Move the quiet hours window's start or end by whole hours
+    pub fn shift_quiet_hours(&mut self, start_delta: i32, end_delta: i32, cx: &mut Context<Self>) {
+        self.app_settings.quiet_hours = self.app_settings.quiet_hours.shifted(start_delta, end_delta);
+        self.filter_settings_changed(cx);
+    }
+
+    /// Turn the analysis floor on at the first day with data, or off
+    pub fn toggle_analysis_start(&mut self, cx: &mut Context<Self>) {
+        let aggregator = UsageAggregator::with_options(self.app_settings.aggregation_options());
+        self.app_settings.analysis_start_date = match self.app_settings.analysis_start_date {
+            Some(_) => None,
+            None => {
+                let earliest = self
+                    .full_analytics_data
+                    .as_ref()
+                    .and_then(|data| data.entries.iter().map(|e| e.timestamp).min())
+                    .unwrap_or_else(chrono::Utc::now);
+                Some(aggregator.start_of_day(aggregator.local_date(&earliest)))
+            }
+        };
+        info!("📅 Analysis start date: {:?}", self.app_settings.analysis_start_date);
+        self.filter_settings_changed(cx);
+    }
+
+    /// Move the analysis floor by whole days and months, keeping it at local midnight
+    pub fn shift_analysis_start(&mut self, days: i64, months: i32, cx: &mut Context<Self>) {
+        let Some(start) = self.app_settings.analysis_start_date else {
+            return;
+        };
+        let aggregator = UsageAggregator::with_options(self.app_settings.aggregation_options());
+        let date = aggregator.local_date(&start) + chrono::Duration::days(days);
+        let months_moved = chrono::Months::new(months.unsigned_abs());
+        let date = if months < 0 {
+            date.checked_sub_months(months_moved)
+        } else {
+            date.checked_add_months(months_moved)
+        }
+        .unwrap_or(date);
+        self.app_settings.analysis_start_date = Some(aggregator.start_of_day(date));
+        self.filter_settings_changed(cx);
+    }
+
+    /// Local date of the analysis floor, e.g. "2025-07-01"
+    fn analysis_start_label(&self) -> Option<String> {
+        let start = self.app_settings.analysis_start_date?;
+        let aggregator = UsageAggregator::with_options(self.app_settings.aggregation_options());
+        Some(aggregator.local_date(&start).format("%Y-%m-%d").to_string())
+    }
+
+    /// Persist a change to which entries count and re-filter the cached data
+    fn filter_settings_changed(&mut self, cx: &mut Context<Self>) {
+        self.save_settings(cx);
+        self.apply_time_filter();
+        cx.notify();
+    }
+
+    /// Re-run aggregation over the cached entries after an aggregation setting changes
+    fn reaggregate_full_data(&mut self) {
+        if let Some(ref full_data) = self.full_analytics_data {
+            let aggregator = UsageAggregator::with_options(self.app_settings.aggregation_options());
+            let recompute = self.app_settings.recompute_costs;
+            let entries: Vec<UsageEntry> =
+                full_data.entries.iter().cloned().map(|entry| entry.with_cost_source(recompute)).collect();
+            let stats = aggregator.calculate_usage_stats(&entries);
+            self.full_analytics_data = Some(Arc::new(stats));
+        }
+    }
+
+    fn render_token_policy_toggle(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = self.theme_registry.colors();
+        let elevated_surface = theme.elevated_surface;
+
+        div()
+            .px_2()
+            .py_1()
+            .text_xs()
+            .text_color(theme.text_muted)
+            .bg(theme.elevated_surface)
+            .border_1()
+            .border_color(theme.border)
+            .rounded_sm()
+            .cursor_pointer()
+            .hover(move |style| style.bg(elevated_surface))
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(|view: &mut RootView, _event, _window, cx| {
+                    view.toggle_token_total_policy(cx);
+                }),
+            )
+            .child(format!("Total: {}", self.app_settings.token_total_policy.label()))
+    }
+
+    pub fn toggle_token_category(&mut self, category: TokenCategory, cx: &mut Context<Self>) {
+        if !self.hidden_token_categories.remove(&category) {
+            self.hidden_token_categories.insert(category);
+        }
+        cx.notify();
+    }
+
+    fn render_cost_breakdown(&self, analytics: &UsageStats, cx: &mut Context<Self>) -> Div {
+        let theme = self.theme_registry.colors();
+        let category_count = |category: TokenCategory| match category {
+            TokenCategory::Input => analytics.total_input_tokens,
+            TokenCategory::Output => analytics.total_output_tokens,
+            TokenCategory::CacheRead => analytics.total_cache_read_tokens,
+            TokenCategory::CacheCreation => analytics.total_cache_creation_tokens,
+        };
+        let ignore_cache = self.app_settings.ignore_cache;
+        // Percentages are shares of the visible categories regardless of the total policy
+        let category_total: u64 = TokenCategory::all()
+            .into_iter()
+            .filter(|category| !self.hidden_token_categories.contains(category))
+            .filter(|category| !(ignore_cache && category.is_cache()))
+            .map(category_count)
+            .sum();
+
+        div()
+            .flex_1()
+            .p_6()
+            .bg(theme.surface)
+            .rounded_lg()
+            .border_1()
+            .border_color(theme.border)
+            .shadow_sm()
+            .child(
+                div()
+                    .flex()
+                    .justify_between()
+                    .items_center()
+                    .mb_4()
                     .child(
-                        // Manual refresh button
-                        self.render_refresh_button(cx),
+                        div()
+                            .text_xl()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(theme.text)
+                            .child("Token Usage"),
                     )
-                    .child(
-                        // Theme toggle button
-                        self.render_theme_toggle(cx),
-                    ),
+                    .child(self.render_token_policy_toggle(cx)),
+            )
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_3()
+                    .child(self.render_token_breakdown_item(
+                        TokenCategory::Input,
+                        analytics.total_input_tokens,
+                        theme.metric_primary,
+                        category_total,
+                        cx,
+                    ))
+                    .child(self.render_token_breakdown_item(
+                        TokenCategory::Output,
+                        analytics.total_output_tokens,
+                        theme.metric_secondary,
+                        category_total,
+                        cx,
+                    ))
+                    .when(!ignore_cache, |list| {
+                        list.child(self.render_token_breakdown_item(
+                            TokenCategory::CacheRead,
+                            analytics.total_cache_read_tokens,
+                            theme.metric_tertiary,
+                            category_total,
+                            cx,
+                        ))
+                        .child(self.render_token_breakdown_item(
+                            TokenCategory::CacheCreation,
+                            analytics.total_cache_creation_tokens,
+                            theme.metric_quaternary,
+                            category_total,
+                            cx,
+                        ))
+                    }),
             )
     }
 
-    fn render_tab_navigation(&self, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render_token_breakdown_item(
+        &self,
+        category: TokenCategory,
+        count: u64,
+        color: Hsla,
+        total_tokens: u64,
+        cx: &mut Context<Self>,
+    ) -> Div {
         let theme = self.theme_registry.colors();
+        let label_string = category.label().to_string();
+        let is_hidden = self.hidden_token_categories.contains(&category);
+        let percentage = if total_tokens > 0 && !is_hidden {
+            (count as f64 / total_tokens as f64 * 100.0) as u32
+        } else {
+            0
+        };
+        let border_color = theme.border;
 
         div()
             .flex()
             .justify_between()
-            .bg(theme.surface)
-            .border_b_1()
-            .border_color(theme.border)
-            .px_6()
+            .items_center()
+            .p_3()
+            .bg(theme.elevated_surface)
+            .rounded_md()
+            .when(is_hidden, |row| row.opacity(0.4))
             .child(
                 div()
                     .flex()
-                    .children(
-                        DashboardTab::all()
-                            .into_iter()
-                            .enumerate()
-                            .map(|(index, tab)| {
-                                let is_active = self.active_tab == tab;
-                                let key_number = index + 1;
-                                let tab_clone = tab.clone();
-                                let text_accent = theme.text_accent;
-
-                                div()
-                                    .px_4()
-                                    .py_3()
-                                    .cursor_pointer()
-                                    .on_mouse_down(
-                                        gpui::MouseButton::Left,
-                                        cx.listener(move |view, _event, _window, cx| {
-                                            view.set_active_tab(tab_clone.clone(), cx);
-                                        }),
-                                    )
-                                    .border_b_2()
-                                    .border_color(if is_active {
-                                        theme.text_accent
-                                    } else {
-                                        hsla(0.0, 0.0, 0.0, 0.0) // Transparent
-                                    })
-                                    .text_color(if is_active {
-                                        theme.text_accent
-                                    } else {
-                                        theme.text_muted
-                                    })
-                                    .font_weight(if is_active {
-                                        FontWeight::SEMIBOLD
-                                    } else {
-                                        FontWeight::NORMAL
-                                    })
-                                    .hover(|style| style.text_color(text_accent))
-                                    .child(
-                                        div()
-                                            .flex()
-                                            .items_center()
-                                            .gap_2()
-                                            .child(format!("{}", key_number))
-                                            .child(tab.title()),
-                                    )
-                            }),
+                    .items_center()
+                    .gap_2()
+                    .child(
+                        // Legend dot toggles this category's visibility
+                        div()
+                            .w_3()
+                            .h_3()
+                            .bg(if is_hidden { theme.text_muted } else { color })
+                            .rounded_full()
+                            .cursor_pointer()
+                            .hover(move |style| style.border_1().border_color(border_color))
+                            .on_mouse_down(
+                                MouseButton::Left,
+                                cx.listener(move |view: &mut RootView, _event, _window, cx| {
+                                    view.toggle_token_category(category, cx);
+                                }),
+                            ),
+                    )
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_weight(FontWeight::MEDIUM)
+                            .text_color(theme.text)
+                            .child(label_string),
                     ),
             )
             .child(
                 div()
-                    .py_3()
-                    .text_xs()
-                    .text_color(theme.text_muted)
-                    .child("Press 1-5 to switch tabs • Alt+1/2/3 for time ranges"),
+                    .text_right()
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(theme.text)
+                            .child(self.format_number(count)),
+                    )
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(theme.text_muted)
+                            .child(if is_hidden {
+                                "hidden".to_string()
+                            } else {
+                                format!("{}%", percentage)
+                            }),
+                    ),
             )
     }
 
-    fn render_main_content(&self, cx: &mut Context<Self>) -> impl IntoElement {
-        div()
-            .id("main-content")
-            .flex_1()
-            .h_full()
-            .overflow_scroll()
-            .p_6()
-            .child(if self.is_loading {
-                self.render_loading_content()
-            } else {
-                self.render_active_tab_content(cx)
-            })
+    fn format_number(&self, num: u64) -> String {
+        formatting::format_count(num, self.app_settings.number_format)
     }
 
-    fn render_loading_content(&self) -> Div {
+    fn format_cost(&self, amount: f64) -> String {
+        formatting::format_cost(amount, self.app_settings.cost_precision)
+    }
+
+    pub fn cycle_cost_precision(&mut self, cx: &mut Context<Self>) {
+        self.app_settings.cost_precision = self.app_settings.cost_precision.next();
+        self.save_settings(cx);
+        cx.notify();
+    }
+
+    pub fn toggle_number_format(&mut self, cx: &mut Context<Self>) {
+        self.app_settings.number_format = match self.app_settings.number_format {
+            NumberFormat::Abbreviated => NumberFormat::Exact,
+            NumberFormat::Exact => NumberFormat::Abbreviated,
+        };
+        self.save_settings(cx);
+        cx.notify();
+    }
+
+    pub fn toggle_group_models_by_family(&mut self, cx: &mut Context<Self>) {
+        self.app_settings.group_models_by_family = !self.app_settings.group_models_by_family;
+        info!("🧬 Group models by family: {}", self.app_settings.group_models_by_family);
+        self.save_settings(cx);
+        // Model rows are keyed during aggregation, so recompute from the cached entries
+        self.reaggregate_full_data();
+        self.apply_time_filter();
+        cx.notify();
+    }
+
+    pub fn toggle_resolve_project_symlinks(&mut self, cx: &mut Context<Self>) {
+        self.app_settings.resolve_project_symlinks = !self.app_settings.resolve_project_symlinks;
+        info!("🔗 Resolve project symlinks: {}", self.app_settings.resolve_project_symlinks);
+        self.save_settings(cx);
+        // Project rows are keyed during aggregation, so recompute from the cached entries
+        self.reaggregate_full_data();
+        self.apply_time_filter();
+        cx.notify();
+    }
+
+    pub fn toggle_hide_inactive_models(&mut self, cx: &mut Context<Self>) {
+        self.hide_inactive_models = !self.hide_inactive_models;
+        cx.notify();
+    }
+
+    pub fn toggle_cost_per_thousand(&mut self, cx: &mut Context<Self>) {
+        self.show_cost_per_thousand = !self.show_cost_per_thousand;
+        cx.notify();
+    }
+
+    /// A model's blended rate, e.g. "$0.0060 / 1K tokens"; a dash when it has no tokens
+    fn format_cost_per_thousand(&self, model: &ModelStats) -> String {
+        match model.cost_per_thousand_tokens() {
+            // Rates are fractions of a cent, so always use the adaptive precision
+            Some(rate) => format!("{} / 1K tokens", formatting::format_currency(rate)),
+            None => "—".to_string(),
+        }
+    }
+
+    /// Models shown on the Models tab, most expensive first
+    fn visible_models(&self, analytics: &UsageStats) -> Vec<ModelStats> {
+        let mut models = analytics
+            .model_stats
+            .values()
+            .filter(|model| !self.hide_inactive_models || model.request_count > 0)
+            .cloned()
+            .collect::<Vec<_>>();
+        models.sort_by(|a, b| b.total_cost.partial_cmp(&a.total_cost).unwrap_or(std::cmp::Ordering::Equal));
+        models
+    }
+
+    fn render_models_content(&self, cx: &mut Context<Self>) -> Div {
         let theme = self.theme_registry.colors();
+        let analytics = self.get_analytics_data();
+        let models = self.visible_models(&analytics);
+
         div()
             .flex()
             .flex_col()
-            .items_center()
-            .justify_center()
-            .h_96()
             .gap_6()
-            .child(
-                // Loading spinner placeholder (would be an actual spinner in real UI)
-                div()
-                    .w_8()
-                    .h_8()
-                    .border_2()
-                    .border_color(theme.text_accent)
-                    .rounded_full(), // Note: GPUI doesn't have built-in animations, but this represents a spinner
-            )
             .child(
                 div()
-                    .text_lg()
-                    .font_weight(FontWeight::MEDIUM)
+                    .text_3xl()
+                    .font_weight(FontWeight::BOLD)
                     .text_color(theme.text)
-                    .child(self.loading_message.clone()),
-            )
-            .child(
-                div()
-                    .text_sm()
-                    .text_color(theme.text_muted)
-                    .child(match &self.loading_state {
-                        LoadingState::LoadingInitial => {
-                            "Scanning Claude usage files...".to_string()
-                        }
-                        LoadingState::LoadedFull => "Processing usage data...".to_string(),
-                        LoadingState::Error(e) => format!("Error: {}", e),
-                    }),
+                    .child("Model Analytics"),
             )
+            .child(self.render_models_summary(&analytics, &models))
+            .child(self.render_request_count_chart(&models))
+            .child(self.render_efficiency_chart(&models))
+            .child(self.render_models_detailed_list(&models, cx))
+            .child(self.render_pricing_reference())
     }
 
-    fn render_active_tab_content(&self, _cx: &mut Context<Self>) -> Div {
-        match &self.active_tab {
-            DashboardTab::Overview => self.render_overview_content(),
-            DashboardTab::Models => self.render_models_content(),
-            DashboardTab::Projects => self.render_projects_content(),
-            DashboardTab::Sessions => self.render_sessions_content(),
-            DashboardTab::Timeline => self.render_timeline_content(),
-        }
-    }
-
-    fn render_overview_content(&self) -> Div {
+    /// Horizontal bars ranking models by request count, scaled to the busiest model
+    fn render_request_count_chart(&self, models: &[ModelStats]) -> Div {
         let theme = self.theme_registry.colors();
-        let analytics = self.get_analytics_data();
+        let mut ranking: Vec<&ModelStats> = models.iter().filter(|m| m.request_count > 0).collect();
+        ranking.sort_by(|a, b| b.request_count.cmp(&a.request_count).then_with(|| a.display_name.cmp(&b.display_name)));
+        let max_requests = ranking.first().map(|m| m.request_count).unwrap_or(0);
 
         div()
+            .p_6()
+            .bg(theme.surface)
+            .rounded_lg()
+            .border_1()
+            .border_color(theme.border)
+            .shadow_sm()
             .flex()
             .flex_col()
-            .gap_6()
+            .gap_3()
             .child(
                 div()
-                    .text_3xl()
-                    .font_weight(FontWeight::BOLD)
+                    .text_xl()
+                    .font_weight(FontWeight::SEMIBOLD)
                     .text_color(theme.text)
-                    .child("Usage Overview"),
+                    .child("Requests by Model"),
             )
-            .child(
+            .when(ranking.is_empty(), |chart| {
+                chart.child(
+                    div()
+                        .text_sm()
+                        .text_color(theme.text_muted)
+                        .child("No requests in this time range"),
+                )
+            })
+            .children(ranking.iter().map(|model| {
+                let fraction = model.request_count as f32 / max_requests as f32;
+
                 div()
                     .flex()
-                    .gap_4()
-                    .child(self.render_metric_card(
-                        "Total Cost",
-                        format!("${:.2}", analytics.total_cost),
-                        MetricType::Primary,
-                    ))
-                    .child(self.render_metric_card(
-                        "Total Tokens",
-                        self.format_number(analytics.total_tokens),
-                        MetricType::Secondary,
-                    ))
-                    .child(self.render_metric_card(
-                        "Sessions",
-                        analytics.session_count.to_string(),
-                        MetricType::Tertiary,
-                    ))
-                    .child(self.render_metric_card(
-                        "Models Used",
-                        analytics.model_stats.len().to_string(),
-                        MetricType::Quaternary,
-                    )),
+                    .items_center()
+                    .gap_3()
+                    .child(
+                        div()
+                            .w(px(160.0))
+                            .text_sm()
+                            .text_color(theme.text)
+                            .child(model.display_name.clone()),
+                    )
+                    .child(
+                        div()
+                            .flex_1()
+                            .h(px(12.0))
+                            .rounded_sm()
+                            .bg(theme.border)
+                            .child(
+                                div()
+                                    .h_full()
+                                    .w(relative(fraction.max(MIN_SHARE_BAR_FRACTION)))
+                                    .rounded_sm()
+                                    .bg(self.model_color(&model.model)),
+                            ),
+                    )
+                    .child(
+                        div()
+                            .w(px(110.0))
+                            .text_right()
+                            .text_sm()
+                            .text_color(theme.text_muted)
+                            .child(format!("{} requests", self.format_number(model.request_count as u64))),
+                    )
+            }))
+    }
+
+    /// Horizontal bars ranking models by tokens per dollar
+    fn render_efficiency_chart(&self, models: &[ModelStats]) -> Div {
+        let theme = self.theme_registry.colors();
+        let aggregator = UsageAggregator::with_options(self.app_settings.aggregation_options());
+        let ranking = aggregator.model_efficiency(models);
+        let unpriced: Vec<String> = models
+            .iter()
+            .filter(|m| m.total_cost <= 0.0)
+            .map(|m| m.display_name.clone())
+            .collect();
+        let max_ratio = ranking.first().map(|m| m.tokens_per_dollar).unwrap_or(0.0);
+
+        div()
+            .p_6()
+            .bg(theme.surface)
+            .rounded_lg()
+            .border_1()
+            .border_color(theme.border)
+            .shadow_sm()
+            .flex()
+            .flex_col()
+            .gap_3()
+            .child(
+                div()
+                    .text_xl()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(theme.text)
+                    .child("Tokens per Dollar"),
             )
-            .child(self.render_breakdown_section(&analytics))
-    }
+            .when(ranking.is_empty(), |chart| {
+                chart.child(
+                    div()
+                        .text_sm()
+                        .text_color(theme.text_muted)
+                        .child("No priced model usage in this time range"),
+                )
+            })
+            .children(ranking.iter().map(|model| {
+                let fraction = if max_ratio > 0.0 {
+                    (model.tokens_per_dollar / max_ratio) as f32
+                } else {
+                    0.0
+                };
 
-    fn render_breakdown_section(&self, analytics: &UsageStats) -> Div {
-        div()
-            .mt_8()
-            .flex()
-            .gap_6()
-            .child(self.render_model_breakdown(analytics))
-            .child(self.render_cost_breakdown(analytics))
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_3()
+                    .child(
+                        div()
+                            .w(px(160.0))
+                            .text_sm()
+                            .text_color(theme.text)
+                            .child(model.display_name.clone()),
+                    )
+                    .child(
+                        div()
+                            .flex_1()
+                            .h(px(12.0))
+                            .rounded_sm()
+                            .bg(theme.border)
+                            .child(
+                                div()
+                                    .h_full()
+                                    .w(relative(fraction.max(MIN_SHARE_BAR_FRACTION)))
+                                    .rounded_sm()
+                                    .bg(self.model_color(&model.model)),
+                            ),
+                    )
+                    .child(
+                        div()
+                            .w(px(110.0))
+                            .text_right()
+                            .text_sm()
+                            .text_color(theme.text_muted)
+                            .child(format!("{} tok/$", self.format_number(model.tokens_per_dollar as u64))),
+                    )
+            }))
+            .when(!unpriced.is_empty(), |chart| {
+                chart.child(
+                    div()
+                        .text_xs()
+                        .text_color(theme.text_muted)
+                        .child(format!("Not ranked (no cost recorded): {}", unpriced.join(", "))),
+                )
+            })
     }
 
-    fn render_model_breakdown(&self, analytics: &UsageStats) -> Div {
+    /// Reference table of every model in the loaded data with the prices the calculator applies
+    fn render_pricing_reference(&self) -> Div {
         let theme = self.theme_registry.colors();
+        let calculator = self.app_settings.cost_calculator();
+        // All loaded models, not just those active in the current time range
+        let mut model_ids = match self.full_analytics_data {
+            Some(ref data) => data.model_stats.keys().cloned().collect::<Vec<_>>(),
+            None => self.get_analytics_data().model_stats.into_keys().collect::<Vec<_>>(),
+        };
+        model_ids.sort();
+
+        let price = |value: f64| format!("${:.2}", value);
+        let header = |label: &'static str| {
+            div()
+                .w(px(90.0))
+                .text_right()
+                .text_xs()
+                .font_weight(FontWeight::MEDIUM)
+                .text_color(theme.text_muted)
+                .child(label)
+        };
+        let cell = |value: String, muted: bool| {
+            div()
+                .w(px(90.0))
+                .text_right()
+                .text_sm()
+                .text_color(if muted { theme.text_muted } else { theme.text })
+                .child(value)
+        };
+
         div()
-            .flex_1()
             .p_6()
             .bg(theme.surface)
             .rounded_lg()
@@ -858,188 +4255,452 @@ impl RootView {
                     .text_xl()
                     .font_weight(FontWeight::SEMIBOLD)
                     .text_color(theme.text)
+                    .child("Pricing Reference"),
+            )
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(theme.text_muted)
                     .mb_4()
-                    .child("Usage by Model"),
+                    .child("USD per million tokens, as used to compute costs"),
             )
             .child(
-                div().flex().flex_col().gap_3().children(
-                    analytics
-                        .model_stats
-                        .values()
-                        .map(|model| self.render_model_item(model, analytics.total_cost))
-                        .collect::<Vec<_>>(),
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .pb_2()
+                    .border_b_1()
+                    .border_color(theme.border)
+                    .child(div().flex_1().text_xs().font_weight(FontWeight::MEDIUM).text_color(theme.text_muted).child("Model"))
+                    .child(header("Input"))
+                    .child(header("Output"))
+                    .child(header("Cache Read"))
+                    .child(header("Cache Write")),
+            )
+            .children(model_ids.iter().map(|model| {
+                let pricing = calculator.billed_pricing(model);
+                let unpriced = pricing.is_zero();
+
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .py_2()
+                    .child(
+                        div()
+                            .flex_1()
+                            .flex()
+                            .flex_col()
+                            .child(
+                                div()
+                                    .flex()
+                                    .items_center()
+                                    .gap_2()
+                                    .text_sm()
+                                    .text_color(theme.text)
+                                    .child(calculator.get_model_display_name(model))
+                                    .when(unpriced, |row| {
+                                        row.child(
+                                            div()
+                                                .px_2()
+                                                .rounded_sm()
+                                                .bg(theme.warning)
+                                                .text_xs()
+                                                .text_color(theme.background)
+                                                .child("No pricing - costs are $0"),
+                                        )
+                                    }),
+                            )
+                            .child(div().text_xs().text_color(theme.text_muted).child(model.clone())),
+                    )
+                    .child(cell(price(pricing.input_price), unpriced))
+                    .child(cell(price(pricing.output_price), unpriced))
+                    .child(cell(price(pricing.cache_read_price), unpriced))
+                    .child(cell(price(pricing.cache_write_price), unpriced))
+            }))
+    }
+
+    fn render_models_summary(&self, analytics: &UsageStats, models: &[ModelStats]) -> Div {
+        let total_models = analytics.model_stats.len();
+        let (models_label, models_value) = if models.len() < total_models {
+            ("Active Models", format!("{} of {}", models.len(), total_models))
+        } else {
+            ("Total Models", total_models.to_string())
+        };
+
+        self.metric_card_row()
+            .child(self.render_metric_card(models_label, models_value, MetricType::Primary))
+            .child(
+                self.render_metric_card(
+                    "Most Used",
+                    models
+                        .iter()
+                        .max_by_key(|m| m.request_count)
+                        .map(|m| m.display_name.clone())
+                        .unwrap_or("No data".to_string()),
+                    MetricType::Secondary,
+                ),
+            )
+            .child(
+                self.render_metric_card(
+                    "Total Requests",
+                    models
+                        .iter()
+                        .map(|m| m.request_count)
+                        .sum::<usize>()
+                        .to_string(),
+                    MetricType::Tertiary,
+                ),
+            )
+            .child(
+                self.render_metric_card(
+                    "Avg Cost/Request",
+                    self.format_cost(
+                        analytics.total_cost
+                            / models
+                                .iter()
+                                .map(|m| m.request_count)
+                                .sum::<usize>() as f64,
+                    ),
+                    MetricType::Quaternary,
                 ),
             )
     }
 
-    fn render_model_item(&self, model: &ModelStats, total_cost: f64) -> Div {
+    fn render_models_detailed_list(&self, models: &[ModelStats], cx: &mut Context<Self>) -> Div {
         let theme = self.theme_registry.colors();
-        let percentage = if total_cost > 0.0 {
-            (model.total_cost / total_cost * 100.0) as u32
+        let list = div()
+            .id("models-list")
+            .track_scroll(&self.models_scroll)
+            .flex()
+            .flex_col()
+            .gap_4()
+            .h(px(400.0))
+            .overflow_scroll();
+        let list = if models.is_empty() {
+            list.items_center()
+                .justify_center()
+                .text_sm()
+                .text_color(theme.text_muted)
+                .child("No model activity in this time range")
         } else {
-            0
+            list.children(models.iter().map(|model| self.render_detailed_model_card(model, cx)))
         };
 
         div()
-            .flex()
-            .justify_between()
-            .items_center()
-            .p_3()
-            .bg(theme.elevated_surface)
-            .rounded_md()
+            .p_6()
+            .bg(theme.surface)
+            .rounded_lg()
+            .border_1()
+            .border_color(theme.border)
+            .shadow_sm()
             .child(
                 div()
                     .flex()
-                    .flex_col()
+                    .justify_between()
+                    .items_center()
+                    .mb_6()
                     .child(
                         div()
-                            .text_sm()
-                            .font_weight(FontWeight::MEDIUM)
+                            .text_xl()
+                            .font_weight(FontWeight::SEMIBOLD)
                             .text_color(theme.text)
-                            .child(model.display_name.clone()),
+                            .child("Detailed Model Breakdown"),
                     )
                     .child(
                         div()
-                            .text_xs()
-                            .text_color(theme.text_muted)
-                            .child(format!("{} requests", model.request_count)),
+                            .flex()
+                            .gap_2()
+                            .child(self.render_chip_button(
+                                "Per 1K tokens",
+                                self.show_cost_per_thousand,
+                                cx,
+                                |view, cx| view.toggle_cost_per_thousand(cx),
+                            ))
+                            .child(self.render_chip_button(
+                                "Hide inactive",
+                                self.hide_inactive_models,
+                                cx,
+                                |view, cx| view.toggle_hide_inactive_models(cx),
+                            )),
                     ),
             )
+            .when(self.show_cost_per_thousand, |card| {
+                card.child(
+                    div()
+                        .mb_4()
+                        .text_sm()
+                        .text_color(theme.text_muted)
+                        .child("Showing blended cost per 1,000 tokens: total cost over all counted tokens"),
+                )
+            })
+            .child(self.render_scrollable_list(list, &self.models_scroll, models.len()))
+    }
+
+    /// Where the thumb of a scrollbar sits, as fractions of the track: (top, height).
+    /// None when everything fits and there is nothing to scroll.
+    fn scroll_thumb(viewport: f32, max_offset: f32, offset: f32) -> Option<(f32, f32)> {
+        if viewport <= 0.0 || max_offset <= 0.5 {
+            return None;
+        }
+        let content = viewport + max_offset;
+        let height = viewport / content;
+        // Scroll offsets grow negative as the content moves up
+        let top = (-offset).clamp(0.0, max_offset) / content;
+        Some((top, height))
+    }
+
+    /// A scrolling list with a thin themed scrollbar beside it and a
+    /// "Showing 1–5 of 23" caption underneath once it overflows
+    fn render_scrollable_list(&self, list: Stateful<Div>, handle: &ScrollHandle, item_count: usize) -> Div {
+        let theme = self.theme_registry.colors();
+        let thumb = Self::scroll_thumb(
+            handle.bounds().size.height.into(),
+            handle.max_offset().height.into(),
+            handle.offset().y.into(),
+        );
+        // Bounds and offsets come from the previous layout, so the bar trails a scroll by a frame
+        let caption = thumb.filter(|_| item_count > 0).map(|_| {
+            let first = (handle.top_item() + 1).min(item_count);
+            let last = (handle.bottom_item() + 1).clamp(first, item_count);
+            format!("Showing {}–{} of {}", first, last, item_count)
+        });
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
             .child(
                 div()
+                    .flex()
+                    .gap_2()
+                    .child(list.flex_1())
+                    .when_some(thumb, |row, (top, height)| {
+                        row.child(
+                            div()
+                                .relative()
+                                .w(px(4.0))
+                                .rounded_full()
+                                .bg(theme.border)
+                                .child(
+                                    div()
+                                        .absolute()
+                                        .left_0()
+                                        .w_full()
+                                        .top(relative(top))
+                                        .h(relative(height))
+                                        .rounded_full()
+                                        .bg(theme.text_muted),
+                                ),
+                        )
+                    }),
+            )
+            .children(caption.map(|caption| {
+                div()
+                    .text_xs()
+                    .text_color(theme.text_muted)
                     .text_right()
+                    .child(caption)
+            }))
+    }
+
+    fn render_detailed_model_card(&self, model: &ModelStats, cx: &mut Context<Self>) -> Div {
+        let theme = self.theme_registry.colors();
+        let model_id = model.model.clone();
+        div()
+            .p_6()
+            .bg(theme.elevated_surface)
+            .border_1()
+            .border_color(theme.border)
+            .rounded_lg()
+            // Right-click offers excluding this model
+            .on_mouse_down(
+                MouseButton::Right,
+                cx.listener(move |view: &mut RootView, event: &MouseDownEvent, _window, cx| {
+                    view.open_card_menu(CardMenuTarget::Model(model_id.clone()), event.position, cx);
+                }),
+            )
+            .child(
+                div()
+                    .flex()
+                    .justify_between()
+                    .items_start()
+                    .mb_4()
                     .child(
                         div()
-                            .text_sm()
-                            .font_weight(FontWeight::SEMIBOLD)
-                            .text_color(theme.success)
-                            .child(format!("${:.2}", model.total_cost)),
+                            .child(
+                                div()
+                                    .text_lg()
+                                    .font_weight(FontWeight::SEMIBOLD)
+                                    .text_color(theme.text)
+                                    .child(model.display_name.clone()),
+                            )
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .text_color(theme.text_muted)
+                                    .child(model.model.clone()),
+                            ),
                     )
                     .child(
                         div()
-                            .text_xs()
-                            .text_color(theme.text_muted)
-                            .child(format!("{}%", percentage)),
+                            .text_right()
+                            .child(
+                                div()
+                                    .text_2xl()
+                                    .font_weight(FontWeight::BOLD)
+                                    .text_color(theme.success)
+                                    .child(if self.show_cost_per_thousand {
+                                        self.format_cost_per_thousand(model)
+                                    } else {
+                                        self.format_cost(model.total_cost)
+                                    }),
+                            )
+                            .when(self.show_cost_per_thousand, |stats| {
+                                stats.child(
+                                    div()
+                                        .text_sm()
+                                        .text_color(theme.text_muted)
+                                        .child(format!("{} total", self.format_cost(model.total_cost))),
+                                )
+                            })
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .text_color(theme.text_muted)
+                                    .child(format!("{} requests", self.format_number(model.request_count as u64))),
+                            ),
                     ),
             )
+            .child(
+                div()
+                    .flex()
+                    .gap_4()
+                    .child(self.render_token_stat(
+                        "Input",
+                        model.input_tokens,
+                        theme.metric_primary,
+                    ))
+                    .child(self.render_token_stat(
+                        "Output",
+                        model.output_tokens,
+                        theme.metric_secondary,
+                    ))
+                    .when(!self.app_settings.ignore_cache, |stats| {
+                        stats
+                            .child(self.render_token_stat(
+                                "Cache Read",
+                                model.cache_read_tokens,
+                                theme.metric_tertiary,
+                            ))
+                            .child(self.render_token_stat(
+                                "Cache Creation",
+                                model.cache_creation_tokens,
+                                theme.metric_quaternary,
+                            ))
+                    }),
+            )
+            .child(self.render_cost_share_bar(model))
     }
 
-    fn render_cost_breakdown(&self, analytics: &UsageStats) -> Div {
+    /// Stacked bar showing how much of a model's cost each token category contributes
+    fn render_cost_share_bar(&self, model: &ModelStats) -> Div {
         let theme = self.theme_registry.colors();
+        let aggregator = UsageAggregator::with_options(self.app_settings.aggregation_options());
+        let breakdown = match self.analytics_data {
+            Some(ref data) => aggregator.model_cost_breakdown(&data.entries, &model.model),
+            // Sample data has no entries to price one by one
+            None => self.app_settings.cost_calculator().calculate_cost_breakdown(
+                &model.model,
+                model.input_tokens,
+                model.output_tokens,
+                model.cache_read_tokens,
+                model.cache_creation_tokens,
+            ),
+        };
+        let total = breakdown.total();
+        if total <= 0.0 {
+            return div();
+        }
+
+        let mut segments = vec![
+            ("Input", breakdown.input_cost, theme.metric_primary),
+            ("Output", breakdown.output_cost, theme.metric_secondary),
+            ("Cache Read", breakdown.cache_read_cost, theme.metric_tertiary),
+            ("Cache Write", breakdown.cache_write_cost, theme.metric_quaternary),
+        ];
+        if self.app_settings.ignore_cache {
+            segments.truncate(2);
+        }
+
         div()
-            .flex_1()
-            .p_6()
-            .bg(theme.surface)
-            .rounded_lg()
-            .border_1()
-            .border_color(theme.border)
-            .shadow_sm()
+            .mt_4()
+            .flex()
+            .flex_col()
+            .gap_2()
             .child(
                 div()
-                    .text_xl()
-                    .font_weight(FontWeight::SEMIBOLD)
-                    .text_color(theme.text)
-                    .mb_4()
-                    .child("Token Usage"),
+                    .flex()
+                    .w_full()
+                    .h(px(8.0))
+                    .rounded_full()
+                    .overflow_hidden()
+                    .bg(theme.surface)
+                    .children(segments.iter().filter(|(_, cost, _)| *cost > 0.0).map(
+                        |(_, cost, color)| {
+                            div()
+                                .h_full()
+                                .w(relative((cost / total) as f32))
+                                .bg(*color)
+                        },
+                    )),
             )
             .child(
                 div()
                     .flex()
-                    .flex_col()
-                    .gap_3()
-                    .child(self.render_token_breakdown_item(
-                        "Input Tokens",
-                        analytics.total_input_tokens,
-                        theme.metric_primary,
-                        analytics.total_tokens,
-                    ))
-                    .child(self.render_token_breakdown_item(
-                        "Output Tokens",
-                        analytics.total_output_tokens,
-                        theme.metric_secondary,
-                        analytics.total_tokens,
-                    ))
-                    .child(self.render_token_breakdown_item(
-                        "Cache Read",
-                        analytics.total_cache_read_tokens,
-                        theme.metric_tertiary,
-                        analytics.total_tokens,
-                    ))
-                    .child(self.render_token_breakdown_item(
-                        "Cache Creation",
-                        analytics.total_cache_creation_tokens,
-                        theme.metric_quaternary,
-                        analytics.total_tokens,
-                    )),
+                    .gap_4()
+                    .text_xs()
+                    .text_color(theme.text_muted)
+                    .children(segments.iter().map(|(label, cost, _)| {
+                        format!("{} {} ({:.0}%)", label, self.format_cost(*cost), cost / total * 100.0)
+                    })),
             )
     }
 
-    fn render_token_breakdown_item(
-        &self,
-        label: &str,
-        count: u64,
-        color: Hsla,
-        total_tokens: u64,
-    ) -> Div {
+    fn render_token_stat(&self, label: &str, count: u64, color: Hsla) -> Div {
         let theme = self.theme_registry.colors();
         let label_string = label.to_string();
-        let percentage = if total_tokens > 0 {
-            (count as f64 / total_tokens as f64 * 100.0) as u32
-        } else {
-            0
-        };
-
         div()
             .flex()
-            .justify_between()
             .items_center()
+            .gap_3()
             .p_3()
-            .bg(theme.elevated_surface)
+            .bg(theme.surface)
             .rounded_md()
+            .border_1()
+            .border_color(theme.border)
+            .child(div().w_4().h_4().bg(color).rounded_full())
             .child(
                 div()
-                    .flex()
-                    .items_center()
-                    .gap_2()
-                    .child(div().w_3().h_3().bg(color).rounded_full())
+                    .flex_1()
                     .child(
                         div()
                             .text_sm()
-                            .font_weight(FontWeight::MEDIUM)
-                            .text_color(theme.text)
+                            .text_color(theme.text_muted)
                             .child(label_string),
-                    ),
-            )
-            .child(
-                div()
-                    .text_right()
+                    )
                     .child(
                         div()
-                            .text_sm()
+                            .text_lg()
                             .font_weight(FontWeight::SEMIBOLD)
                             .text_color(theme.text)
                             .child(self.format_number(count)),
-                    )
-                    .child(
-                        div()
-                            .text_xs()
-                            .text_color(theme.text_muted)
-                            .child(format!("{}%", percentage)),
                     ),
             )
     }
 
-    fn format_number(&self, num: u64) -> String {
-        if num >= 1_000_000 {
-            format!("{:.1}M", num as f64 / 1_000_000.0)
-        } else if num >= 1_000 {
-            format!("{:.1}K", num as f64 / 1_000.0)
-        } else {
-            num.to_string()
-        }
-    }
-
-    fn render_models_content(&self) -> Div {
+    fn render_projects_content(&self, cx: &mut Context<Self>) -> Div {
         let theme = self.theme_registry.colors();
         let analytics = self.get_analytics_data();
 
@@ -1052,105 +4713,471 @@ impl RootView {
                     .text_3xl()
                     .font_weight(FontWeight::BOLD)
                     .text_color(theme.text)
-                    .child("Model Analytics"),
+                    .child("Project Analytics"),
             )
-            .child(self.render_models_summary(&analytics))
-            .child(self.render_models_detailed_list(&analytics))
+            .child(self.render_projects_summary(&analytics))
+            .child(self.render_project_token_summary(&analytics))
+            .child(if self.compare_projects {
+                self.render_project_comparison(&analytics, cx)
+            } else {
+                self.render_projects_list(&analytics, cx)
+            })
+    }
+
+    pub fn toggle_project_compare(&mut self, cx: &mut Context<Self>) {
+        self.compare_projects = !self.compare_projects;
+        cx.notify();
+    }
+
+    /// Put a project in the first empty comparison column, or replace the right one when both are full
+    pub fn pick_compared_project(&mut self, project_path: String, cx: &mut Context<Self>) {
+        let slot = self.compared_projects.iter().position(Option::is_none).unwrap_or(1);
+        self.compared_projects[slot] = Some(project_path);
+        cx.notify();
+    }
+
+    pub fn clear_compared_project(&mut self, slot: usize, cx: &mut Context<Self>) {
+        self.compared_projects[slot] = None;
+        cx.notify();
     }
 
-    fn render_models_summary(&self, analytics: &UsageStats) -> Div {
+    /// Two projects' stats in columns, with the right column's difference from the left
+    fn render_project_comparison(&self, analytics: &UsageStats, cx: &mut Context<Self>) -> Div {
+        let theme = self.theme_registry.colors();
+        let mut projects = analytics.project_stats.values().cloned().collect::<Vec<_>>();
+        UsageAggregator::new().sort_projects(&mut projects, ProjectSort::TotalCost);
+        let selected: Vec<Option<&ProjectStats>> = self
+            .compared_projects
+            .iter()
+            .map(|path| path.as_ref().and_then(|path| analytics.project_stats.get(path)))
+            .collect();
+
+        let picker = div().flex().flex_wrap().gap_2().children(projects.iter().map(|project| {
+            let path = project.project_path.clone();
+            let is_selected = self.compared_projects.contains(&Some(path.clone()));
+            self.render_chip_button(project.project_name.clone(), is_selected, cx, move |view, cx| {
+                view.pick_compared_project(path.clone(), cx)
+            })
+        }));
+
+        let column_header = |slot: usize, project: Option<&ProjectStats>, cx: &mut Context<Self>| {
+            div()
+                .flex_1()
+                .flex()
+                .items_center()
+                .justify_between()
+                .gap_2()
+                .child(
+                    div()
+                        .text_lg()
+                        .font_weight(FontWeight::SEMIBOLD)
+                        .text_color(if project.is_some() { theme.text } else { theme.text_muted })
+                        .child(project.map(|p| p.project_name.clone()).unwrap_or_else(|| "Pick a project".to_string())),
+                )
+                .when(project.is_some(), |header| {
+                    header.child(self.render_chip_button("Clear", false, cx, move |view, cx| {
+                        view.clear_compared_project(slot, cx)
+                    }))
+                })
+        };
+
+        let mut comparison = div()
+            .flex()
+            .flex_col()
+            .gap_3()
+            .child(
+                div()
+                    .flex()
+                    .gap_4()
+                    .child(column_header(0, selected[0], cx))
+                    .child(column_header(1, selected[1], cx))
+                    .child(div().w(px(110.0)).text_right().text_xs().text_color(theme.text_muted).child("Difference")),
+            );
+
+        if let (Some(left), Some(right)) = (selected[0], selected[1]) {
+            let mut counts = vec![
+                ("Total Tokens", left.total_tokens, right.total_tokens, theme.metric_secondary),
+                ("Requests", left.request_count as u64, right.request_count as u64, theme.metric_primary),
+                ("Sessions", left.session_count as u64, right.session_count as u64, theme.metric_primary),
+                ("Input", left.input_tokens, right.input_tokens, theme.metric_tertiary),
+                ("Output", left.output_tokens, right.output_tokens, theme.metric_quaternary),
+                ("Cache Read", left.cache_read_tokens, right.cache_read_tokens, theme.metric_tertiary),
+                ("Cache Creation", left.cache_creation_tokens, right.cache_creation_tokens, theme.metric_quaternary),
+            ];
+            if self.app_settings.ignore_cache {
+                counts.truncate(5);
+            }
+
+            let delta_cell = |text: String| {
+                div()
+                    .w(px(110.0))
+                    .text_right()
+                    .text_sm()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(theme.text)
+                    .child(text)
+            };
+            let cost_delta = right.total_cost - left.total_cost;
+            let cost_sign = if cost_delta < 0.0 { "-" } else { "+" };
+
+            comparison = comparison
+                .child(
+                    div()
+                        .flex()
+                        .items_center()
+                        .gap_4()
+                        .child(div().flex_1().child(self.render_project_stat("Cost", self.format_cost(left.total_cost), theme.success)))
+                        .child(div().flex_1().child(self.render_project_stat("Cost", self.format_cost(right.total_cost), theme.success)))
+                        .child(delta_cell(format!("{}{}", cost_sign, self.format_cost(cost_delta.abs())))),
+                )
+                .children(counts.into_iter().map(|(label, left_value, right_value, color)| {
+                    let sign = if right_value < left_value { "-" } else { "+" };
+                    div()
+                        .flex()
+                        .items_center()
+                        .gap_4()
+                        .child(div().flex_1().child(self.render_project_stat(label, self.format_number(left_value), color)))
+                        .child(div().flex_1().child(self.render_project_stat(label, self.format_number(right_value), color)))
+                        .child(delta_cell(format!("{}{}", sign, self.format_number(left_value.abs_diff(right_value)))))
+                }));
+        }
+
         div()
+            .p_6()
+            .bg(theme.surface)
+            .rounded_lg()
+            .border_1()
+            .border_color(theme.border)
+            .shadow_sm()
             .flex()
+            .flex_col()
             .gap_4()
+            .child(
+                div()
+                    .flex()
+                    .justify_between()
+                    .items_center()
+                    .child(
+                        div()
+                            .text_xl()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(theme.text)
+                            .child("Compare Projects"),
+                    )
+                    .child(self.render_chip_button("Compare", true, cx, |view, cx| view.toggle_project_compare(cx))),
+            )
+            .child(picker)
+            .child(comparison)
+    }
+
+    fn render_projects_summary(&self, analytics: &UsageStats) -> Div {
+        self.metric_card_row()
             .child(self.render_metric_card(
-                "Total Models",
-                analytics.model_stats.len().to_string(),
+                "Active Projects",
+                analytics.project_stats.len().to_string(),
                 MetricType::Primary,
             ))
             .child(
                 self.render_metric_card(
-                    "Most Used",
+                    "Most Active",
                     analytics
-                        .model_stats
+                        .project_stats
                         .values()
-                        .max_by_key(|m| m.request_count)
-                        .map(|m| m.display_name.clone())
+                        .max_by_key(|p| p.request_count)
+                        .map(|p| p.project_name.clone())
                         .unwrap_or("No data".to_string()),
                     MetricType::Secondary,
                 ),
             )
             .child(
                 self.render_metric_card(
-                    "Total Requests",
+                    "Total Sessions",
                     analytics
-                        .model_stats
+                        .project_stats
                         .values()
-                        .map(|m| m.request_count)
+                        .map(|p| p.session_count)
                         .sum::<usize>()
                         .to_string(),
                     MetricType::Tertiary,
                 ),
             )
+            .child(self.render_metric_card(
+                "Avg Cost/Project",
+                self.format_cost(analytics.total_cost / analytics.project_stats.len() as f64),
+                MetricType::Quaternary,
+            ))
+    }
+
+    /// Token totals per category across every project in the filtered range
+    fn render_project_token_summary(&self, analytics: &UsageStats) -> Div {
+        let total = |category: TokenCategory| -> u64 {
+            analytics.project_stats.values().map(|p| p.tokens(category)).sum()
+        };
+        let card = |category: TokenCategory, metric_type: MetricType| {
+            self.render_metric_card(category.label(), self.format_number(total(category)), metric_type)
+        };
+
+        self.metric_card_row()
+            .child(card(TokenCategory::Input, MetricType::Primary))
+            .child(card(TokenCategory::Output, MetricType::Secondary))
+            .when(!self.app_settings.ignore_cache, |row| {
+                row.child(card(TokenCategory::CacheRead, MetricType::Tertiary))
+                    .child(card(TokenCategory::CacheCreation, MetricType::Quaternary))
+            })
+    }
+
+    pub fn set_project_sort(&mut self, sort: ProjectSort, cx: &mut Context<Self>) {
+        if self.project_sort != sort {
+            debug!("🔄 Sorting projects by: {:?}", sort);
+            self.project_sort = sort;
+            self.app_settings.set_project_sort(sort);
+            self.save_settings(cx);
+            cx.notify();
+        }
+    }
+
+    pub fn toggle_project_pin(&mut self, project_path: &str, cx: &mut Context<Self>) {
+        self.app_settings.toggle_project_pin(project_path);
+        self.save_settings(cx);
+        cx.notify();
+    }
+
+    fn render_pin_button(&self, project_path: &str, cx: &mut Context<Self>) -> Div {
+        let colors = self.theme_registry.colors();
+        let elevated_surface = colors.elevated_surface;
+        let is_pinned = self.app_settings.is_project_pinned(project_path);
+        let path = project_path.to_string();
+
+        div()
+            .flex()
+            .items_center()
+            .justify_center()
+            .w(px(28.0))
+            .h(px(24.0))
+            .bg(if is_pinned { colors.elevated_surface } else { colors.surface })
+            .border_1()
+            .border_color(if is_pinned { colors.text_accent } else { colors.border })
+            .rounded(px(4.0))
+            .text_size(px(12.0))
+            .when(!is_pinned, |button| button.opacity(0.5))
+            .cursor_pointer()
+            .hover(move |style| style.bg(elevated_surface).opacity(1.0))
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(move |view: &mut RootView, _event, _window, cx| {
+                    view.toggle_project_pin(&path, cx);
+                }),
+            )
+            .child("📌")
+    }
+
+    fn render_project_sort_button(&self, sort: ProjectSort, cx: &mut Context<Self>) -> impl IntoElement {
+        self.render_chip_button(sort.label(), self.project_sort == sort, cx, move |view, cx| {
+            view.set_project_sort(sort, cx);
+        })
+    }
+
+    /// Small pill-shaped toggle button shared by the in-card option selectors
+    fn render_chip_button(
+        &self,
+        label: impl Into<SharedString>,
+        is_active: bool,
+        cx: &mut Context<Self>,
+        on_click: impl Fn(&mut RootView, &mut Context<RootView>) + 'static,
+    ) -> Div {
+        let theme = self.theme_registry.colors();
+        let elevated_surface = theme.elevated_surface;
+
+        div()
+            .px_3()
+            .py_1()
+            .text_xs()
+            .font_weight(if is_active {
+                FontWeight::SEMIBOLD
+            } else {
+                FontWeight::NORMAL
+            })
+            .text_color(if is_active {
+                theme.text
+            } else {
+                theme.text_muted
+            })
+            .bg(if is_active {
+                theme.text_accent
+            } else {
+                theme.surface
+            })
+            .border_1()
+            .border_color(if is_active {
+                theme.text_accent
+            } else {
+                theme.border
+            })
+            .rounded_sm()
+            .cursor_pointer()
+            .hover(move |style| {
+                if !is_active {
+                    style.bg(elevated_surface)
+                } else {
+                    style
+                }
+            })
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(move |view: &mut RootView, _event, _window, cx| {
+                    on_click(view, cx);
+                }),
+            )
+            .child(label.into())
+    }
+
+    fn render_projects_list(&self, analytics: &UsageStats, cx: &mut Context<Self>) -> Div {
+        let theme = self.theme_registry.colors();
+        let mut projects = analytics.project_stats.values().cloned().collect::<Vec<_>>();
+        UsageAggregator::new().sort_projects(&mut projects, self.project_sort);
+        // Pinned projects lead in pin order and are never folded into "Other"
+        let (pinned, projects) = UsageAggregator::split_pinned_projects(projects, &self.app_settings.pinned_projects);
+        let (projects, minor) = UsageAggregator::split_minor_contributors(
+            projects,
+            |project| project.total_cost,
+            self.app_settings.minor_contributor_threshold,
+        );
+        let projects: Vec<ProjectStats> = pinned.into_iter().chain(projects).collect();
+        // Cards, the "Other" row and, when expanded, the projects folded into it
+        let row_count = projects.len()
+            + usize::from(!minor.is_empty())
+            + if self.show_other_projects { minor.len() } else { 0 };
+
+        div()
+            .p_6()
+            .bg(theme.surface)
+            .rounded_lg()
+            .border_1()
+            .border_color(theme.border)
+            .shadow_sm()
             .child(
-                self.render_metric_card(
-                    "Avg Cost/Request",
-                    format!(
-                        "${:.3}",
-                        analytics.total_cost
-                            / analytics
-                                .model_stats
-                                .values()
-                                .map(|m| m.request_count)
-                                .sum::<usize>() as f64
+                div()
+                    .flex()
+                    .justify_between()
+                    .items_center()
+                    .mb_6()
+                    .child(
+                        div()
+                            .text_xl()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(theme.text)
+                            .child("Project Breakdown"),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_1()
+                            .child(div().text_xs().text_color(theme.text_muted).mr_1().child("Sort by"))
+                            .child(self.render_project_sort_button(ProjectSort::TotalCost, cx))
+                            .child(self.render_project_sort_button(ProjectSort::CostPerRequest, cx))
+                            .child(div().ml_2().child(self.render_chip_button("Compare", false, cx, |view, cx| {
+                                view.toggle_project_compare(cx)
+                            }))),
                     ),
-                    MetricType::Quaternary,
-                ),
             )
+            .child(self.render_scrollable_list(
+                div()
+                    .id("projects-list")
+                    .track_scroll(&self.projects_scroll)
+                    .flex()
+                    .flex_col()
+                    .gap_4()
+                    .max_h(px(500.0))
+                    .overflow_scroll()
+                    .children(
+                        projects
+                            .iter()
+                            .map(|project| self.render_project_card(project, analytics.total_cost, cx))
+                            .collect::<Vec<_>>(),
+                    )
+                    .when(!minor.is_empty(), |list| list.child(self.render_other_projects_row(&minor, cx)))
+                    .when(self.show_other_projects, |list| {
+                        list.children(
+                            minor
+                                .iter()
+                                .map(|project| div().pl_6().child(self.render_project_card(project, analytics.total_cost, cx)))
+                                .collect::<Vec<_>>(),
+                        )
+                    }),
+                &self.projects_scroll,
+                row_count,
+            ))
     }
 
-    fn render_models_detailed_list(&self, analytics: &UsageStats) -> Div {
+    /// Summed row for the projects grouped under the cost threshold; click to expand
+    fn render_other_projects_row(&self, minor: &[ProjectStats], cx: &mut Context<Self>) -> impl IntoElement {
         let theme = self.theme_registry.colors();
+        let elevated_surface = theme.elevated_surface;
+        let total_cost: f64 = minor.iter().map(|p| p.total_cost).sum();
+        let requests: usize = minor.iter().map(|p| p.request_count).sum();
+        let tokens: u64 = minor.iter().map(|p| p.total_tokens).sum();
+
         div()
+            .id("other-projects")
+            .flex()
+            .items_center()
+            .justify_between()
             .p_6()
-            .bg(theme.surface)
-            .rounded_lg()
+            .bg(theme.elevated_surface)
             .border_1()
             .border_color(theme.border)
-            .shadow_sm()
+            .rounded_lg()
+            .cursor_pointer()
+            .hover(move |style| style.bg(elevated_surface))
+            .on_click(cx.listener(|view, _event, _window, cx| {
+                view.show_other_projects = !view.show_other_projects;
+                cx.notify();
+            }))
             .child(
                 div()
-                    .text_xl()
+                    .text_lg()
                     .font_weight(FontWeight::SEMIBOLD)
                     .text_color(theme.text)
-                    .mb_6()
-                    .child("Detailed Model Breakdown"),
+                    .child(format!(
+                        "{} Other ({} projects)",
+                        if self.show_other_projects { "▾" } else { "▸" },
+                        minor.len()
+                    )),
             )
             .child(
                 div()
-                    .id("models-list")
                     .flex()
-                    .flex_col()
                     .gap_4()
-                    .h(px(400.0))
-                    .overflow_scroll()
-                    .children(
-                        analytics
-                            .model_stats
-                            .values()
-                            .map(|model| self.render_detailed_model_card(model))
-                            .collect::<Vec<_>>(),
-                    ),
+                    .child(self.render_project_stat("Cost", self.format_cost(total_cost), theme.success))
+                    .child(self.render_project_stat("Tokens", self.format_number(tokens), theme.metric_secondary))
+                    .child(self.render_project_stat("Requests", self.format_number(requests as u64), theme.metric_primary)),
             )
     }
 
-    fn render_detailed_model_card(&self, model: &ModelStats) -> Div {
+    fn render_project_card(&self, project: &ProjectStats, total_cost: f64, cx: &mut Context<Self>) -> Div {
         let theme = self.theme_registry.colors();
+        let share = if total_cost > 0.0 { project.total_cost / total_cost } else { 0.0 };
+        let export_path = project.project_path.clone();
+        let export_name = project.project_name.clone();
+
         div()
             .p_6()
             .bg(theme.elevated_surface)
             .border_1()
             .border_color(theme.border)
             .rounded_lg()
+            // Right-click offers exporting or excluding this project
+            .on_mouse_down(
+                MouseButton::Right,
+                cx.listener(move |view: &mut RootView, event: &MouseDownEvent, _window, cx| {
+                    let target = CardMenuTarget::Project {
+                        path: export_path.clone(),
+                        name: export_name.clone(),
+                    };
+                    view.open_card_menu(target, event.position, cx);
+                }),
+            )
             .child(
                 div()
                     .flex()
@@ -1161,17 +5188,29 @@ impl RootView {
                         div()
                             .child(
                                 div()
-                                    .text_lg()
-                                    .font_weight(FontWeight::SEMIBOLD)
-                                    .text_color(theme.text)
-                                    .child(model.display_name.clone()),
+                                    .flex()
+                                    .items_center()
+                                    .gap_2()
+                                    .child(
+                                        div()
+                                            .text_lg()
+                                            .font_weight(FontWeight::SEMIBOLD)
+                                            .text_color(theme.text)
+                                            .child(project.project_name.clone()),
+                                    )
+                                    .child(self.render_copy_path_button(&project.project_path, cx))
+                                    .child(self.render_pin_button(&project.project_path, cx)),
                             )
                             .child(
                                 div()
                                     .text_sm()
                                     .text_color(theme.text_muted)
-                                    .child(model.model.clone()),
-                            ),
+                                    .child(project.project_path.clone()),
+                            )
+                            .child(div().text_xs().text_color(theme.text_muted).child(format!(
+                                "Last used: {}",
+                                project.last_used.format("%Y-%m-%d %H:%M")
+                            ))),
                     )
                     .child(
                         div()
@@ -1181,44 +5220,58 @@ impl RootView {
                                     .text_2xl()
                                     .font_weight(FontWeight::BOLD)
                                     .text_color(theme.success)
-                                    .child(format!("${:.2}", model.total_cost)),
+                                    .child(self.format_cost(project.total_cost)),
                             )
                             .child(
                                 div()
                                     .text_sm()
                                     .text_color(theme.text_muted)
-                                    .child(format!("{} requests", model.request_count)),
-                            ),
+                                    .child(format!("{} sessions", project.session_count)),
+                            )
+                            .child(
+                                div()
+                                    .text_xs()
+                                    .text_color(theme.text_muted)
+                                    .child(format!("{}/request", self.format_cost(project.cost_per_request))),
+                            )
+                            .when(project.session_count > 0, |stats| {
+                                stats.child(div().text_xs().text_color(theme.text_muted).child(format!(
+                                    "{}/session · {:.1} sessions per active day",
+                                    self.format_cost(project.avg_session_cost),
+                                    project.sessions_per_active_day
+                                )))
+                            }),
                     ),
             )
+            .child(div().mb_4().child(self.render_share_bar(share, theme.metric_primary)))
             .child(
                 div()
                     .flex()
                     .gap_4()
-                    .child(self.render_token_stat(
-                        "Input",
-                        model.input_tokens,
+                    .child(self.render_project_stat(
+                        "Requests",
+                        self.format_number(project.request_count as u64),
                         theme.metric_primary,
                     ))
-                    .child(self.render_token_stat(
-                        "Output",
-                        model.output_tokens,
+                    .child(self.render_project_stat(
+                        "Total Tokens",
+                        self.format_number(project.total_tokens),
                         theme.metric_secondary,
                     ))
-                    .child(self.render_token_stat(
-                        "Cache Read",
-                        model.cache_read_tokens,
+                    .child(self.render_project_stat(
+                        "Input",
+                        self.format_number(project.input_tokens),
                         theme.metric_tertiary,
                     ))
-                    .child(self.render_token_stat(
-                        "Cache Creation",
-                        model.cache_creation_tokens,
+                    .child(self.render_project_stat(
+                        "Output",
+                        self.format_number(project.output_tokens),
                         theme.metric_quaternary,
                     )),
             )
     }
 
-    fn render_token_stat(&self, label: &str, count: u64, color: Hsla) -> Div {
+    fn render_project_stat(&self, label: &str, value: String, color: Hsla) -> Div {
         let theme = self.theme_registry.colors();
         let label_string = label.to_string();
         div()
@@ -1230,29 +5283,44 @@ impl RootView {
             .rounded_md()
             .border_1()
             .border_color(theme.border)
-            .child(div().w_4().h_4().bg(color).rounded_full())
+            .child(div().w_3().h_3().bg(color).rounded_full())
             .child(
                 div()
                     .flex_1()
                     .child(
                         div()
-                            .text_sm()
+                            .text_xs()
                             .text_color(theme.text_muted)
                             .child(label_string),
                     )
                     .child(
                         div()
-                            .text_lg()
+                            .text_sm()
                             .font_weight(FontWeight::SEMIBOLD)
                             .text_color(theme.text)
-                            .child(self.format_number(count)),
+                            .child(value),
                     ),
             )
     }
 
-    fn render_projects_content(&self) -> Div {
+    /// Sessions as listed on the Sessions tab: narrowed to the selected session and, in the
+    /// table view, filtered and sorted by the table controls
+    fn displayed_sessions(&self) -> Vec<SessionStats> {
+        let mut sessions = self.get_sessions_data();
+        if let Some((ref project, ref session_id)) = self.selected_session {
+            sessions.retain(|session| &session.project_path == project && &session.session_id == session_id);
+        }
+        if !self.show_session_table {
+            return sessions;
+        }
+        let mut sessions = UsageAggregator::filter_sessions(sessions, &self.session_filter);
+        UsageAggregator::new().sort_sessions(&mut sessions, self.session_sort);
+        sessions
+    }
+
+    fn render_sessions_content(&self, cx: &mut Context<Self>) -> Div {
         let theme = self.theme_registry.colors();
-        let analytics = self.get_analytics_data();
+        let sessions = self.displayed_sessions();
 
         div()
             .flex()
@@ -1260,60 +5328,98 @@ impl RootView {
             .gap_6()
             .child(
                 div()
-                    .text_3xl()
-                    .font_weight(FontWeight::BOLD)
-                    .text_color(theme.text)
-                    .child("Project Analytics"),
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_4()
+                            .child(
+                                div()
+                                    .text_3xl()
+                                    .font_weight(FontWeight::BOLD)
+                                    .text_color(theme.text)
+                                    .child("Session History"),
+                            )
+                            .child(
+                                div()
+                                    .flex()
+                                    .gap_1()
+                                    .child(self.render_chip_button("Cards", !self.show_session_table, cx, |view, cx| {
+                                        view.set_session_table_view(false, cx)
+                                    }))
+                                    .child(self.render_chip_button("Table", self.show_session_table, cx, |view, cx| {
+                                        view.set_session_table_view(true, cx)
+                                    })),
+                            ),
+                    )
+                    .when_some(self.selected_session.clone(), |header, (_, selected)| {
+                        header.child(
+                            div()
+                                .flex()
+                                .items_center()
+                                .gap_2()
+                                .text_sm()
+                                .text_color(theme.text_muted)
+                                .child(format!("Showing session {}", selected))
+                                .child(self.render_chip_button("Show all", false, cx, |view, cx| {
+                                    view.clear_selected_session(cx)
+                                })),
+                        )
+                    }),
             )
-            .child(self.render_projects_summary(&analytics))
-            .child(self.render_projects_list(&analytics))
+            .child(self.render_sessions_summary(&sessions))
+            .when_some(self.selected_session.as_ref(), |content, (project, session_id)| {
+                content
+                    .child(self.render_session_note_editor(session_id, cx))
+                    .child(self.render_session_model_breakdown(project, session_id))
+                    .child(self.render_session_requests(project, session_id, cx))
+            })
+            .child(if self.show_session_table {
+                self.render_sessions_table(sessions, cx)
+            } else {
+                self.render_sessions_timeline(&sessions, cx)
+            })
     }
 
-    fn render_projects_summary(&self, analytics: &UsageStats) -> Div {
-        div()
-            .flex()
-            .gap_4()
-            .child(self.render_metric_card(
-                "Active Projects",
-                analytics.project_stats.len().to_string(),
-                MetricType::Primary,
-            ))
-            .child(
-                self.render_metric_card(
-                    "Most Active",
-                    analytics
-                        .project_stats
-                        .values()
-                        .max_by_key(|p| p.request_count)
-                        .map(|p| p.project_name.clone())
-                        .unwrap_or("No data".to_string()),
-                    MetricType::Secondary,
-                ),
-            )
-            .child(
-                self.render_metric_card(
-                    "Total Sessions",
-                    analytics
-                        .project_stats
-                        .values()
-                        .map(|p| p.session_count)
-                        .sum::<usize>()
-                        .to_string(),
-                    MetricType::Tertiary,
-                ),
-            )
-            .child(self.render_metric_card(
-                "Avg Cost/Project",
-                format!(
-                    "${:.2}",
-                    analytics.total_cost / analytics.project_stats.len() as f64
-                ),
-                MetricType::Quaternary,
-            ))
+    pub fn set_session_table_view(&mut self, show_table: bool, cx: &mut Context<Self>) {
+        self.show_session_table = show_table;
+        cx.notify();
+    }
+
+    pub fn sort_sessions_by(&mut self, column: SessionSortColumn, cx: &mut Context<Self>) {
+        self.session_sort = self.session_sort.toggled(column);
+        debug!("🔄 Sorting sessions by: {:?}", self.session_sort);
+        self.app_settings.set_session_sort(self.session_sort);
+        self.save_settings(cx);
+        cx.notify();
     }
 
-    fn render_projects_list(&self, analytics: &UsageStats) -> Div {
+    /// Sortable, filterable list of every session, one row each
+    fn render_sessions_table(&self, sessions: Vec<SessionStats>, cx: &mut Context<Self>) -> Div {
         let theme = self.theme_registry.colors();
+        let row_count = sessions.len();
+        let list_height = SESSION_TABLE_ROW_HEIGHT * row_count.clamp(1, SESSION_TABLE_VISIBLE_ROWS) as f32;
+        let sessions: Rc<[SessionStats]> = sessions.into();
+
+        let header_cell = |column: SessionSortColumn, cx: &mut Context<Self>| {
+            let arrow = match (self.session_sort.column == column, self.session_sort.descending) {
+                (false, _) => "",
+                (true, true) => " ▼",
+                (true, false) => " ▲",
+            };
+            Self::session_table_cell(column, div())
+                .id(SharedString::from(format!("session-sort-{}", column.label())))
+                .cursor_pointer()
+                .text_color(if self.session_sort.column == column { theme.text } else { theme.text_muted })
+                .child(format!("{}{}", column.label(), arrow))
+                .on_click(cx.listener(move |view: &mut RootView, _event, _window, cx| {
+                    view.sort_sessions_by(column, cx);
+                }))
+        };
+
         div()
             .p_6()
             .bg(theme.surface)
@@ -1321,160 +5427,455 @@ impl RootView {
             .border_1()
             .border_color(theme.border)
             .shadow_sm()
+            .flex()
+            .flex_col()
+            .gap_3()
             .child(
                 div()
-                    .text_xl()
-                    .font_weight(FontWeight::SEMIBOLD)
-                    .text_color(theme.text)
-                    .mb_6()
-                    .child("Project Breakdown"),
+                    .flex()
+                    .justify_between()
+                    .items_center()
+                    .child(
+                        div()
+                            .text_xl()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(theme.text)
+                            .child("All Sessions"),
+                    )
+                    .child(self.render_session_filter(cx)),
             )
             .child(
                 div()
-                    .id("projects-list")
                     .flex()
-                    .flex_col()
-                    .gap_4()
-                    .max_h(px(500.0))
-                    .overflow_scroll()
-                    .children(
-                        analytics
-                            .project_stats
-                            .values()
-                            .map(|project| self.render_project_card(project))
-                            .collect::<Vec<_>>(),
-                    ),
+                    .items_center()
+                    .gap_3()
+                    .px_3()
+                    .pb_2()
+                    .border_b_1()
+                    .border_color(theme.border)
+                    .text_xs()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .children(SessionSortColumn::ALL.map(|column| header_cell(column, cx))),
+            )
+            .when(row_count == 0, |table| {
+                table.child(
+                    div()
+                        .py_4()
+                        .text_sm()
+                        .text_color(theme.text_muted)
+                        .child(if self.session_filter.trim().is_empty() {
+                            "No sessions in this time range".to_string()
+                        } else {
+                            format!("No sessions match \"{}\"", self.session_filter.trim())
+                        }),
+                )
+            })
+            .when(row_count > 0, |table| {
+                // Only the rows scrolled into view are built, so thousands of sessions stay cheap
+                table.child(
+                    uniform_list(
+                        "sessions-table",
+                        row_count,
+                        cx.processor(move |view: &mut RootView, range: Range<usize>, _window, cx| {
+                            sessions[range]
+                                .iter()
+                                .map(|session| view.render_session_table_row(session, cx))
+                                .collect::<Vec<_>>()
+                        }),
+                    )
+                    .h(px(list_height)),
+                )
+            })
+    }
+
+    /// Width and alignment shared by a Sessions table column's header and cells
+    fn session_table_cell<E: Styled>(column: SessionSortColumn, cell: E) -> E {
+        match column {
+            SessionSortColumn::Project => cell.flex_1().overflow_hidden(),
+            SessionSortColumn::Date => cell.w(px(130.0)),
+            SessionSortColumn::Cost | SessionSortColumn::Tokens => cell.w(px(90.0)).text_right(),
+            SessionSortColumn::Requests => cell.w(px(80.0)).text_right(),
+        }
+    }
+
+    fn render_session_table_row(&self, session: &SessionStats, cx: &mut Context<Self>) -> Stateful<Div> {
+        let theme = self.theme_registry.colors();
+        let elevated_surface = theme.elevated_surface;
+        let project_name = session.project_path.rsplit('/').next().unwrap_or("Unknown Project").to_string();
+        let project = session.project_path.clone();
+        let session_id = session.session_id.clone();
+        let cell = |column: SessionSortColumn, text: String| Self::session_table_cell(column, div()).child(text);
+
+        div()
+            .id(SharedString::from(format!("session-row-{}", session.session_id)))
+            .flex()
+            .items_center()
+            .gap_3()
+            .px_3()
+            .h(px(SESSION_TABLE_ROW_HEIGHT))
+            .rounded_md()
+            .cursor_pointer()
+            .text_sm()
+            .text_color(theme.text)
+            .hover(move |style| style.bg(elevated_surface))
+            .on_click(cx.listener(move |view: &mut RootView, _event, _window, cx| {
+                view.show_session(project.clone(), session_id.clone(), cx);
+            }))
+            .child(cell(SessionSortColumn::Project, project_name))
+            .child(
+                cell(SessionSortColumn::Date, session.start_time.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string())
+                    .text_color(theme.text_muted),
+            )
+            .child(cell(SessionSortColumn::Cost, self.format_cost(session.total_cost)).text_color(theme.success))
+            .child(cell(SessionSortColumn::Tokens, self.format_number(session.total_tokens)))
+            .child(cell(SessionSortColumn::Requests, self.format_number(session.request_count as u64)))
+    }
+
+    /// Filter field for the Sessions table; typed keys are collected while it has focus
+    fn render_session_filter(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let colors = self.theme_registry.colors();
+        let accent = colors.text_accent;
+        let is_empty = self.session_filter.is_empty();
+
+        div()
+            .id("session-filter")
+            .key_context("SessionFilter")
+            .track_focus(&self.session_filter_focus)
+            .flex()
+            .items_center()
+            .w(px(220.0))
+            .h(px(28.0))
+            .px_2()
+            .bg(colors.background)
+            .border_1()
+            .border_color(colors.border)
+            .rounded(px(6.0))
+            .cursor_text()
+            .text_xs()
+            .focus(move |style| style.border_color(accent))
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(|view: &mut RootView, _event, window, cx| {
+                    window.focus(&view.session_filter_focus);
+                    cx.notify();
+                }),
+            )
+            .on_key_down(cx.listener(
+                |view: &mut RootView, event: &KeyDownEvent, window: &mut Window, cx: &mut Context<RootView>| {
+                    let modifiers = &event.keystroke.modifiers;
+                    // Leave shortcuts and focus movement to the root handler
+                    if modifiers.platform || modifiers.control || event.keystroke.key == "tab" {
+                        return;
+                    }
+
+                    match event.keystroke.key.as_str() {
+                        "enter" => window.focus(&view.focus_handle),
+                        "escape" => {
+                            view.session_filter.clear();
+                            window.focus(&view.focus_handle);
+                        }
+                        "backspace" => {
+                            view.session_filter.pop();
+                        }
+                        _ => {
+                            if let Some(text) = &event.keystroke.key_char {
+                                view.session_filter.push_str(text);
+                            }
+                        }
+                    }
+                    // Kept in settings as it's typed; written to disk when submitted or cleared
+                    view.app_settings.set_session_filter(&view.session_filter);
+                    if matches!(event.keystroke.key.as_str(), "enter" | "escape") {
+                        view.save_settings(cx);
+                    }
+                    cx.stop_propagation();
+                    cx.notify();
+                },
+            ))
+            .child(
+                div()
+                    .overflow_hidden()
+                    .text_color(if is_empty { colors.text_muted } else { colors.text })
+                    .child(if is_empty {
+                        "Filter by project or session id".to_string()
+                    } else {
+                        self.session_filter.clone()
+                    }),
             )
     }
 
-    fn render_project_card(&self, project: &ProjectStats) -> Div {
+    /// Editable note for the drilled-into session; Enter saves, Escape discards
+    fn render_session_note_editor(&self, session_id: &str, cx: &mut Context<Self>) -> Div {
         let theme = self.theme_registry.colors();
+        let accent = theme.text_accent;
+        let saved_note = self.app_settings.session_note(session_id).map(str::to_string);
+        let editing = self.session_note_draft.is_some();
+        let text = self.session_note_draft.clone().or_else(|| saved_note.clone()).unwrap_or_default();
+        let start_note = saved_note.unwrap_or_default();
+        let note_session = session_id.to_string();
+
         div()
             .p_6()
-            .bg(theme.elevated_surface)
+            .bg(theme.surface)
+            .rounded_lg()
             .border_1()
             .border_color(theme.border)
-            .rounded_lg()
+            .shadow_sm()
+            .flex()
+            .flex_col()
+            .gap_3()
             .child(
                 div()
                     .flex()
+                    .items_center()
                     .justify_between()
-                    .items_start()
-                    .mb_4()
                     .child(
                         div()
-                            .child(
-                                div()
-                                    .text_lg()
-                                    .font_weight(FontWeight::SEMIBOLD)
-                                    .text_color(theme.text)
-                                    .child(project.project_name.clone()),
-                            )
-                            .child(
-                                div()
-                                    .text_sm()
-                                    .text_color(theme.text_muted)
-                                    .child(project.project_path.clone()),
-                            )
-                            .child(div().text_xs().text_color(theme.text_muted).child(format!(
-                                "Last used: {}",
-                                project.last_used.format("%Y-%m-%d %H:%M")
-                            ))),
+                            .text_xl()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(theme.text)
+                            .child("Note"),
                     )
-                    .child(
-                        div()
-                            .text_right()
-                            .child(
-                                div()
-                                    .text_2xl()
-                                    .font_weight(FontWeight::BOLD)
-                                    .text_color(theme.success)
-                                    .child(format!("${:.2}", project.total_cost)),
-                            )
-                            .child(
-                                div()
-                                    .text_sm()
-                                    .text_color(theme.text_muted)
-                                    .child(format!("{} sessions", project.session_count)),
-                            ),
-                    ),
+                    .child(div().text_xs().text_color(theme.text_muted).child(if editing {
+                        "Enter to save · Esc to cancel"
+                    } else {
+                        "Click to edit"
+                    })),
             )
             .child(
                 div()
-                    .flex()
-                    .gap_4()
-                    .child(self.render_project_stat(
-                        "Requests",
-                        project.request_count.to_string(),
-                        theme.metric_primary,
-                    ))
-                    .child(self.render_project_stat(
-                        "Total Tokens",
-                        self.format_number(project.total_tokens),
-                        theme.metric_secondary,
-                    ))
-                    .child(self.render_project_stat(
-                        "Input",
-                        self.format_number(project.input_tokens),
-                        theme.metric_tertiary,
+                    .id("session-note")
+                    .key_context("SessionNote")
+                    .track_focus(&self.session_note_focus)
+                    .min_h(px(36.0))
+                    .px_3()
+                    .py_2()
+                    .bg(theme.background)
+                    .border_1()
+                    .border_color(theme.border)
+                    .rounded(px(6.0))
+                    .cursor_text()
+                    .text_sm()
+                    .focus(move |style| style.border_color(accent))
+                    .on_mouse_down(
+                        MouseButton::Left,
+                        cx.listener(move |view: &mut RootView, _event, window, cx| {
+                            if view.session_note_draft.is_none() {
+                                view.session_note_draft = Some(start_note.clone());
+                            }
+                            window.focus(&view.session_note_focus);
+                            cx.notify();
+                        }),
+                    )
+                    .on_key_down(cx.listener(
+                        move |view: &mut RootView, event: &KeyDownEvent, window: &mut Window, cx: &mut Context<RootView>| {
+                            let modifiers = &event.keystroke.modifiers;
+                            // Leave shortcuts and focus movement to the root handler
+                            if modifiers.platform || modifiers.control || event.keystroke.key == "tab" {
+                                return;
+                            }
+                            let Some(draft) = view.session_note_draft.as_mut() else {
+                                return;
+                            };
+
+                            match event.keystroke.key.as_str() {
+                                "enter" => {
+                                    let note = draft.clone();
+                                    view.save_session_note(&note_session, &note, cx);
+                                    window.focus(&view.focus_handle);
+                                }
+                                "escape" => {
+                                    view.session_note_draft = None;
+                                    window.focus(&view.focus_handle);
+                                }
+                                "backspace" => {
+                                    draft.pop();
+                                }
+                                _ => {
+                                    if let Some(text) = &event.keystroke.key_char {
+                                        draft.push_str(text);
+                                    }
+                                }
+                            }
+                            cx.stop_propagation();
+                            cx.notify();
+                        },
                     ))
-                    .child(self.render_project_stat(
-                        "Output",
-                        self.format_number(project.output_tokens),
-                        theme.metric_quaternary,
-                    )),
+                    .text_color(if text.is_empty() { theme.text_muted } else { theme.text })
+                    .child(if text.is_empty() {
+                        "Add a note, e.g. \"migration spike\" or \"bug hunt\"".to_string()
+                    } else {
+                        text
+                    }),
             )
     }
 
-    fn render_project_stat(&self, label: &str, value: String, color: Hsla) -> Div {
+    /// Which models the drilled-into session used and how its cost and tokens split between them
+    fn render_session_model_breakdown(&self, project: &str, session_id: &str) -> Div {
         let theme = self.theme_registry.colors();
-        let label_string = label.to_string();
+        let aggregator = UsageAggregator::with_options(self.app_settings.aggregation_options());
+        let models = self
+            .analytics_data
+            .as_ref()
+            .map(|data| aggregator.session_model_breakdown(&data.entries, project, session_id))
+            .unwrap_or_default();
+        let total_cost: f64 = models.iter().map(|m| m.total_cost).sum();
+
+        let body = match models.as_slice() {
+            [] => div()
+                .text_sm()
+                .text_color(theme.text_muted)
+                .child("No requests for this session in the selected time range"),
+            [only] => div()
+                .flex()
+                .items_center()
+                .justify_between()
+                .text_sm()
+                .child(
+                    div()
+                        .text_color(theme.text)
+                        .child(format!("All requests used {}", only.display_name)),
+                )
+                .child(div().text_color(theme.text_muted).child(format!(
+                    "{} tokens · {}",
+                    self.format_number(only.total_tokens),
+                    self.format_cost(only.total_cost)
+                ))),
+            models => div().flex().flex_col().gap_3().children(models.iter().map(|model| {
+                self.render_model_item(model, total_cost).child(
+                    div()
+                        .text_xs()
+                        .text_color(theme.text_muted)
+                        .child(format!("{} tokens", self.format_number(model.total_tokens))),
+                )
+            })),
+        };
+
         div()
-            .flex()
-            .items_center()
-            .gap_3()
-            .p_3()
+            .p_6()
             .bg(theme.surface)
-            .rounded_md()
+            .rounded_lg()
             .border_1()
             .border_color(theme.border)
-            .child(div().w_3().h_3().bg(color).rounded_full())
+            .shadow_sm()
             .child(
                 div()
-                    .flex_1()
-                    .child(
-                        div()
-                            .text_xs()
-                            .text_color(theme.text_muted)
-                            .child(label_string),
-                    )
-                    .child(
-                        div()
-                            .text_sm()
-                            .font_weight(FontWeight::SEMIBOLD)
-                            .text_color(theme.text)
-                            .child(value),
-                    ),
+                    .text_xl()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(theme.text)
+                    .mb_4()
+                    .child("Models Used"),
             )
+            .child(body)
+    }
+
+    /// Show a request's raw line in the inspector, or close it when it is already shown
+    pub fn inspect_entry(&mut self, entry: UsageEntry, cx: &mut Context<Self>) {
+        self.inspected_entry = if self.inspected_entry.as_ref() == Some(&entry) {
+            None
+        } else {
+            Some(entry)
+        };
+        cx.notify();
+    }
+
+    /// Raw JSON indented for reading; lines that don't parse (say, with a trailing comma) are shown as-is
+    fn pretty_raw_line(raw: &str) -> String {
+        serde_json::from_str::<serde_json::Value>(raw)
+            .and_then(|value| serde_json::to_string_pretty(&value))
+            .unwrap_or_else(|_| raw.to_string())
     }
 
-    fn render_sessions_content(&self) -> Div {
+    /// The drilled-into session's requests; clicking one opens its raw JSON below the list
+    fn render_session_requests(&self, project: &str, session_id: &str, cx: &mut Context<Self>) -> Div {
         let theme = self.theme_registry.colors();
-        let sessions = self.get_sessions_data();
+        let elevated_surface = theme.elevated_surface;
+        let calculator = CostCalculator::new();
+        let aggregator = UsageAggregator::with_options(self.app_settings.aggregation_options());
+        let entries = self
+            .analytics_data
+            .as_ref()
+            .map(|data| aggregator.session_entries(&data.entries, project, session_id))
+            .unwrap_or_default();
+
+        let rows = entries.iter().enumerate().map(|(index, entry)| {
+            let is_inspected = self.inspected_entry.as_ref() == Some(*entry);
+            let clicked = (*entry).clone();
+            let tokens = entry.input_tokens + entry.output_tokens + entry.cache_read_tokens + entry.cache_creation_tokens;
+            div()
+                .id(("session-request", index))
+                .flex()
+                .items_center()
+                .justify_between()
+                .gap_4()
+                .px_3()
+                .py_2()
+                .rounded_md()
+                .text_sm()
+                .cursor_pointer()
+                .when(is_inspected, |row| row.bg(theme.elevated_surface))
+                .hover(move |style| style.bg(elevated_surface))
+                .on_click(cx.listener(move |view, _event, _window, cx| view.inspect_entry(clicked.clone(), cx)))
+                .child(
+                    div()
+                        .text_color(theme.text_muted)
+                        .child(entry.timestamp.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S").to_string()),
+                )
+                .child(div().flex_1().text_color(theme.text).child(calculator.get_model_display_name(&entry.model)))
+                .child(div().text_color(theme.text_muted).child(format!("{} tokens", self.format_number(tokens))))
+                .child(div().w(px(80.0)).text_right().text_color(theme.success).child(self.format_cost(entry.cost)))
+        });
+
+        let inspector = self.inspected_entry.as_ref().map(|entry| {
+            let (text, captured) = match entry.raw_line {
+                Some(ref raw) => (Self::pretty_raw_line(raw), true),
+                None => ("raw not captured (enable debug mode)".to_string(), false),
+            };
+            div()
+                .mt_4()
+                .p_4()
+                .bg(theme.elevated_surface)
+                .border_1()
+                .border_color(theme.border)
+                .rounded_md()
+                .text_xs()
+                .text_color(if captured { theme.text } else { theme.text_muted })
+                .children(text.lines().map(|line| div().child(line.to_string())).collect::<Vec<_>>())
+        });
 
         div()
-            .flex()
-            .flex_col()
-            .gap_6()
+            .p_6()
+            .bg(theme.surface)
+            .rounded_lg()
+            .border_1()
+            .border_color(theme.border)
+            .shadow_sm()
             .child(
                 div()
-                    .text_3xl()
-                    .font_weight(FontWeight::BOLD)
+                    .text_xl()
+                    .font_weight(FontWeight::SEMIBOLD)
                     .text_color(theme.text)
-                    .child("Session History"),
+                    .mb_4()
+                    .child("Requests"),
             )
-            .child(self.render_sessions_summary(&sessions))
-            .child(self.render_sessions_timeline(&sessions))
+            .when(entries.is_empty(), |card| {
+                card.child(
+                    div()
+                        .text_sm()
+                        .text_color(theme.text_muted)
+                        .child("No requests for this session in the selected time range"),
+                )
+            })
+            .child(
+                div()
+                    .id("session-requests")
+                    .flex()
+                    .flex_col()
+                    .max_h(px(320.0))
+                    .overflow_scroll()
+                    .children(rows.collect::<Vec<_>>()),
+            )
+            .children(inspector)
     }
 
     fn render_sessions_summary(&self, sessions: &[SessionStats]) -> Div {
@@ -1487,9 +5888,7 @@ impl RootView {
             0.0
         };
 
-        div()
-            .flex()
-            .gap_4()
+        self.metric_card_row()
             .child(self.render_metric_card(
                 "Total Sessions",
                 total_sessions.to_string(),
@@ -1497,23 +5896,45 @@ impl RootView {
             ))
             .child(self.render_metric_card(
                 "Total Cost",
-                format!("${:.2}", total_cost),
+                self.format_cost(total_cost),
                 MetricType::Secondary,
             ))
             .child(self.render_metric_card(
                 "Total Requests",
-                total_requests.to_string(),
+                self.format_number(total_requests as u64),
                 MetricType::Tertiary,
             ))
             .child(self.render_metric_card(
                 "Avg Cost/Session",
-                format!("${:.2}", avg_cost_per_session),
+                self.format_cost(avg_cost_per_session),
                 MetricType::Quaternary,
             ))
     }
 
-    fn render_sessions_timeline(&self, sessions: &[SessionStats]) -> Div {
+    pub fn toggle_session_grouping(&mut self, cx: &mut Context<Self>) {
+        self.group_sessions_by_project = !self.group_sessions_by_project;
+        cx.notify();
+    }
+
+    pub fn toggle_session_group_collapsed(&mut self, project_path: String, cx: &mut Context<Self>) {
+        if !self.collapsed_session_groups.remove(&project_path) {
+            self.collapsed_session_groups.insert(project_path);
+        }
+        cx.notify();
+    }
+
+    fn render_sessions_timeline(&self, sessions: &[SessionStats], cx: &mut Context<Self>) -> Div {
         let theme = self.theme_registry.colors();
+        let rows = if self.group_sessions_by_project {
+            self.render_grouped_sessions(sessions, cx)
+        } else {
+            sessions
+                .iter()
+                .map(|session| self.render_session_timeline_item(session, cx))
+                .collect::<Vec<_>>()
+        };
+        let row_count = rows.len();
+
         div()
             .p_6()
             .bg(theme.surface)
@@ -1523,30 +5944,158 @@ impl RootView {
             .shadow_sm()
             .child(
                 div()
-                    .text_xl()
-                    .font_weight(FontWeight::SEMIBOLD)
-                    .text_color(theme.text)
+                    .flex()
+                    .justify_between()
+                    .items_center()
                     .mb_6()
-                    .child("Recent Sessions Timeline"),
+                    .child(
+                        div()
+                            .text_xl()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(theme.text)
+                            .child("Recent Sessions Timeline"),
+                    )
+                    .child(self.render_chip_button(
+                        "Group by Project",
+                        self.group_sessions_by_project,
+                        cx,
+                        |view, cx| view.toggle_session_grouping(cx),
+                    )),
             )
-            .child(
+            .child(self.render_scrollable_list(
                 div()
                     .id("sessions-list")
+                    .track_scroll(&self.sessions_scroll)
                     .flex()
                     .flex_col()
                     .gap_4()
                     .max_h(px(500.0))
                     .overflow_scroll()
-                    .children(
-                        sessions
+                    .children(rows),
+                &self.sessions_scroll,
+                row_count,
+            ))
+    }
+
+    /// Group sessions under per-project headers, ordered by project cost
+    fn render_grouped_sessions(&self, sessions: &[SessionStats], cx: &mut Context<Self>) -> Vec<Div> {
+        let mut groups: Vec<(String, Vec<&SessionStats>)> = Vec::new();
+        for session in sessions {
+            match groups.iter_mut().find(|(path, _)| *path == session.project_path) {
+                Some((_, group)) => group.push(session),
+                None => groups.push((session.project_path.clone(), vec![session])),
+            }
+        }
+        let group_cost = |group: &[&SessionStats]| group.iter().map(|s| s.total_cost).sum::<f64>();
+        groups.sort_by(|a, b| {
+            group_cost(&b.1)
+                .partial_cmp(&group_cost(&a.1))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        groups
+            .into_iter()
+            .map(|(project_path, group)| {
+                let is_collapsed = self.collapsed_session_groups.contains(&project_path);
+                let mut container = div()
+                    .flex()
+                    .flex_col()
+                    .gap_3()
+                    .child(self.render_session_group_header(&project_path, &group, is_collapsed, cx));
+                if !is_collapsed {
+                    container = container.children(
+                        group
                             .iter()
-                            .map(|session| self.render_session_timeline_item(session))
+                            .map(|session| self.render_session_timeline_item(session, cx))
                             .collect::<Vec<_>>(),
+                    );
+                }
+                container
+            })
+            .collect()
+    }
+
+    fn render_session_group_header(
+        &self,
+        project_path: &str,
+        group: &[&SessionStats],
+        is_collapsed: bool,
+        cx: &mut Context<Self>,
+    ) -> Div {
+        let theme = self.theme_registry.colors();
+        let total_cost: f64 = group.iter().map(|s| s.total_cost).sum();
+        let total_requests: usize = group.iter().map(|s| s.request_count).sum();
+        let project_name = project_path
+            .rsplit('/')
+            .next()
+            .unwrap_or("Unknown Project")
+            .to_string();
+        let path = project_path.to_string();
+        let elevated_surface = theme.elevated_surface;
+
+        div()
+            .flex()
+            .justify_between()
+            .items_center()
+            .px_4()
+            .py_3()
+            .bg(theme.surface)
+            .border_1()
+            .border_color(theme.border)
+            .rounded_md()
+            .cursor_pointer()
+            .hover(move |style| style.bg(elevated_surface))
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(move |view: &mut RootView, _event, _window, cx| {
+                    view.toggle_session_group_collapsed(path.clone(), cx);
+                }),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(theme.text_muted)
+                            .child(if is_collapsed { "▸" } else { "▾" }),
+                    )
+                    .child(
+                        div()
+                            .text_lg()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(theme.text)
+                            .child(project_name),
+                    )
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(theme.text_muted)
+                            .child(format!("{} sessions", group.len())),
+                    ),
+            )
+            .child(
+                div()
+                    .text_right()
+                    .child(
+                        div()
+                            .text_lg()
+                            .font_weight(FontWeight::BOLD)
+                            .text_color(theme.success)
+                            .child(self.format_cost(total_cost)),
+                    )
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(theme.text_muted)
+                            .child(format!("{} requests", self.format_number(total_requests as u64))),
                     ),
             )
     }
 
-    fn render_session_timeline_item(&self, session: &SessionStats) -> Div {
+    fn render_session_timeline_item(&self, session: &SessionStats, cx: &mut Context<Self>) -> Div {
         let theme = self.theme_registry.colors();
         let project_name = session
             .project_path
@@ -1554,6 +6103,9 @@ impl RootView {
             .last()
             .unwrap_or("Unknown Project")
             .to_string();
+        let export_path = session.project_path.clone();
+        let export_session = session.session_id.clone();
+        let export_name = format!("{}-{}", project_name, session.session_id);
 
         div()
             .flex()
@@ -1564,6 +6116,18 @@ impl RootView {
             .border_1()
             .border_color(theme.border)
             .rounded_lg()
+            // Right-click exports just this session's entries
+            .on_mouse_down(
+                MouseButton::Right,
+                cx.listener(move |view: &mut RootView, _event, _window, cx| {
+                    view.export_card_entries(
+                        export_path.clone(),
+                        Some(export_session.clone()),
+                        export_name.clone(),
+                        cx,
+                    );
+                }),
+            )
             .child(
                 // Timeline dot and line
                 div().flex().flex_col().items_center().child(
@@ -1589,10 +6153,17 @@ impl RootView {
                                 div()
                                     .child(
                                         div()
-                                            .text_lg()
-                                            .font_weight(FontWeight::SEMIBOLD)
-                                            .text_color(theme.text)
-                                            .child(project_name),
+                                            .flex()
+                                            .items_center()
+                                            .gap_2()
+                                            .child(
+                                                div()
+                                                    .text_lg()
+                                                    .font_weight(FontWeight::SEMIBOLD)
+                                                    .text_color(theme.text)
+                                                    .child(project_name),
+                                            )
+                                            .child(self.render_copy_path_button(&session.project_path, cx)),
                                     )
                                     .child(
                                         div().text_sm().text_color(theme.text_muted).child(
@@ -1600,8 +6171,24 @@ impl RootView {
                                         ),
                                     )
                                     .child(div().text_xs().text_color(theme.text_muted).child(
-                                        session.timestamp.format("%Y-%m-%d %H:%M").to_string(),
-                                    )),
+                                        format!(
+                                            "{} · {}",
+                                            session.start_time.format("%Y-%m-%d %H:%M"),
+                                            formatting::format_duration(session.duration())
+                                        ),
+                                    ))
+                                    .when_some(self.app_settings.session_note(&session.session_id), |info, note| {
+                                        info.child(
+                                            div()
+                                                .mt_1()
+                                                .text_sm()
+                                                .text_color(theme.text_accent)
+                                                .child(format!(
+                                                    "📝 {}",
+                                                    formatting::truncate_text(note, SESSION_NOTE_PREVIEW_CHARS)
+                                                )),
+                                        )
+                                    }),
                             )
                             .child(
                                 div()
@@ -1611,13 +6198,13 @@ impl RootView {
                                             .text_xl()
                                             .font_weight(FontWeight::BOLD)
                                             .text_color(theme.success)
-                                            .child(format!("${:.2}", session.total_cost)),
+                                            .child(self.format_cost(session.total_cost)),
                                     )
                                     .child(
                                         div()
                                             .text_sm()
                                             .text_color(theme.text_muted)
-                                            .child(format!("{} requests", session.request_count)),
+                                            .child(format!("{} requests", self.format_number(session.request_count as u64))),
                                     ),
                             ),
                     )
@@ -1640,13 +6227,15 @@ impl RootView {
                                 self.format_number(session.output_tokens),
                                 theme.metric_tertiary,
                             ))
-                            .child(self.render_session_stat(
-                                "Cache",
-                                self.format_number(
-                                    session.cache_read_tokens + session.cache_creation_tokens,
-                                ),
-                                theme.metric_quaternary,
-                            )),
+                            .when(!self.app_settings.ignore_cache, |stats| {
+                                stats.child(self.render_session_stat(
+                                    "Cache",
+                                    self.format_number(
+                                        session.cache_read_tokens + session.cache_creation_tokens,
+                                    ),
+                                    theme.metric_quaternary,
+                                ))
+                            }),
                     ),
             )
     }
@@ -1683,7 +6272,7 @@ impl RootView {
             )
     }
 
-    fn render_timeline_content(&self) -> Div {
+    fn render_timeline_content(&self, cx: &mut Context<Self>) -> Div {
         let theme = self.theme_registry.colors();
         let daily_usage = self.get_daily_usage_data();
 
@@ -1699,7 +6288,200 @@ impl RootView {
                     .child("Usage Timeline"),
             )
             .child(self.render_timeline_summary(&daily_usage))
-            .child(self.render_daily_usage_timeline(&daily_usage))
+            .child(self.render_cost_calendar(&daily_usage))
+            .child(self.render_weekday_chart())
+            .child(self.render_daily_usage_timeline(&daily_usage, cx))
+            .child(self.render_period_comparison(cx))
+    }
+
+    fn set_comparison_days(&mut self, days: i64, cx: &mut Context<Self>) {
+        if self.comparison_days != days {
+            self.comparison_days = days;
+            cx.notify();
+        }
+    }
+
+    /// The last `comparison_days` against the same span just before it, across all loaded entries
+    fn render_period_comparison(&self, cx: &mut Context<Self>) -> Div {
+        let theme = self.theme_registry.colors();
+        let current = Period::days_ending(chrono::Utc::now(), self.comparison_days);
+        let previous = current.previous();
+        let diff = self.full_analytics_data.as_ref().map(|data| {
+            UsageAggregator::with_options(self.app_settings.aggregation_options()).diff_periods(
+                &data.entries,
+                previous,
+                current,
+            )
+        });
+
+        div()
+            .p_6()
+            .bg(theme.surface)
+            .rounded_lg()
+            .border_1()
+            .border_color(theme.border)
+            .shadow_sm()
+            .flex()
+            .flex_col()
+            .gap_4()
+            .child(
+                div()
+                    .flex()
+                    .justify_between()
+                    .items_center()
+                    .child(
+                        div()
+                            .text_xl()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(theme.text)
+                            .child("Period Comparison"),
+                    )
+                    .child(
+                        div().flex().items_center().gap_1().children(
+                            COMPARISON_PERIOD_OPTIONS
+                                .into_iter()
+                                .map(|days| {
+                                    self.render_chip_button(
+                                        format!("{}d vs previous {}d", days, days),
+                                        self.comparison_days == days,
+                                        cx,
+                                        move |view, cx| view.set_comparison_days(days, cx),
+                                    )
+                                })
+                                .collect::<Vec<_>>(),
+                        ),
+                    ),
+            )
+            .map(|card| match diff {
+                Some(diff) => card
+                    .child(self.render_period_totals(&diff))
+                    .child(
+                        div()
+                            .flex()
+                            .gap_6()
+                            .child(self.render_delta_list("Projects", &diff.projects))
+                            .child(self.render_delta_list("Models", &diff.models)),
+                    ),
+                None => card.child(
+                    div()
+                        .text_sm()
+                        .text_color(theme.text_muted)
+                        .child("Load usage data to compare periods"),
+                ),
+            })
+    }
+
+    fn render_period_totals(&self, diff: &PeriodDiff) -> Div {
+        let theme = self.theme_registry.colors();
+        let change = match diff.cost_change_ratio() {
+            Some(ratio) => format!("{} ({:+.0}%)", self.format_cost_delta(diff.cost_delta()), ratio * 100.0),
+            None => self.format_cost_delta(diff.cost_delta()),
+        };
+
+        div()
+            .flex()
+            .items_center()
+            .gap_6()
+            .text_sm()
+            .text_color(theme.text_muted)
+            .child(format!("Previous: {}", self.format_cost(diff.total_cost_a)))
+            .child(format!("Current: {}", self.format_cost(diff.total_cost_b)))
+            .child(
+                div()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(self.delta_color(diff.cost_delta()))
+                    .child(change),
+            )
+    }
+
+    /// Largest movers first; new and vanished entries are badged instead of showing a bare delta
+    fn render_delta_list(&self, title: &str, deltas: &[EntityDelta]) -> Div {
+        let theme = self.theme_registry.colors();
+        let badge = |label: &'static str, color: Hsla| {
+            div()
+                .px_2()
+                .rounded_sm()
+                .text_xs()
+                .border_1()
+                .border_color(color)
+                .text_color(color)
+                .child(label)
+        };
+
+        div()
+            .flex_1()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .child(
+                div()
+                    .text_sm()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(theme.text)
+                    .child(title.to_string()),
+            )
+            .when(deltas.is_empty(), |list| {
+                list.child(
+                    div()
+                        .text_sm()
+                        .text_color(theme.text_muted)
+                        .child("No usage in either period"),
+                )
+            })
+            .children(deltas.iter().take(COMPARISON_ROWS).map(|delta| {
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .text_sm()
+                    .child(
+                        div()
+                            .flex_1()
+                            .overflow_hidden()
+                            .text_color(theme.text)
+                            .child(delta.name.clone()),
+                    )
+                    .when(delta.is_added(), |row| row.child(badge("New", theme.text_accent)))
+                    .when(delta.is_removed(), |row| row.child(badge("Gone", theme.text_muted)))
+                    .child(
+                        div()
+                            .w(px(80.0))
+                            .text_right()
+                            .text_color(theme.text_muted)
+                            .child(self.format_cost(delta.cost_b)),
+                    )
+                    .child(
+                        div()
+                            .w(px(90.0))
+                            .text_right()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(self.delta_color(delta.cost_delta()))
+                            .child(self.format_cost_delta(delta.cost_delta())),
+                    )
+            }))
+    }
+
+    /// "▲ $1.20" / "▼ $0.40", or "—" when nothing changed
+    fn format_cost_delta(&self, delta: f64) -> String {
+        if delta.abs() < 0.005 {
+            "—".to_string()
+        } else if delta > 0.0 {
+            format!("▲ {}", self.format_cost(delta))
+        } else {
+            format!("▼ {}", self.format_cost(-delta))
+        }
+    }
+
+    /// Spending more is the warning direction
+    fn delta_color(&self, delta: f64) -> Hsla {
+        let theme = self.theme_registry.colors();
+        if delta.abs() < 0.005 {
+            theme.text_muted
+        } else if delta > 0.0 {
+            theme.warning
+        } else {
+            theme.success
+        }
     }
 
     /// Get daily usage data - real data if loaded, sample data as fallback
@@ -1815,9 +6597,10 @@ impl RootView {
             0.0
         };
 
-        div()
-            .flex()
-            .gap_4()
+        // Idle days count the whole calendar span, not just days present in the data
+        let frequency = UsageAggregator::usage_frequency(daily_usage);
+
+        let totals = self.metric_card_row()
             .child(self.render_metric_card(
                 "Total Days",
                 total_days.to_string(),
@@ -1830,21 +6613,152 @@ impl RootView {
             ))
             .child(self.render_metric_card(
                 "Total Cost",
-                format!("${:.2}", total_cost),
+                self.format_cost(total_cost),
                 MetricType::Tertiary,
             ))
             .child(self.render_metric_card(
                 "Avg Daily Cost",
-                format!("${:.2}", avg_daily_cost),
+                self.format_cost(avg_daily_cost),
                 MetricType::Quaternary,
+            ));
+        let regularity = self.metric_card_row()
+            .child(self.render_metric_card(
+                "Idle Days",
+                frequency
+                    .map(|f| format!("{} of {}", f.idle_days, f.span_days))
+                    .unwrap_or_else(|| "—".to_string()),
+                MetricType::Primary,
             ))
+            .child(self.render_metric_card(
+                "Usage Frequency",
+                frequency
+                    .map(|f| format!("{:.0}% of days", f.frequency() * 100.0))
+                    .unwrap_or_else(|| "—".to_string()),
+                MetricType::Secondary,
+            ));
+
+        div().flex().flex_col().gap_4().child(totals).child(regularity)
+    }
+
+    /// Linear blend from `from` (t = 0) to `to` (t = 1)
+    fn mix_colors(from: Hsla, to: Hsla, t: f32) -> Hsla {
+        hsla(
+            from.h + (to.h - from.h) * t,
+            from.s + (to.s - from.s) * t,
+            from.l + (to.l - from.l) * t,
+            from.a + (to.a - from.a) * t,
+        )
+    }
+
+    /// Spend per weekday over the filtered range, starting on the configured first day of the week
+    fn render_weekday_chart(&self) -> Div {
+        let (min_height, max_height) = WEEKDAY_CHART_HEIGHT_RANGE;
+        let bar_height = (f32::from(self.viewport_height) * 0.15).clamp(min_height, max_height);
+
+        let theme = self.theme_registry.colors();
+        let Some(ref data) = self.analytics_data else {
+            return div();
+        };
+        let aggregator = UsageAggregator::with_options(self.app_settings.aggregation_options());
+        let totals = aggregator.cost_by_weekday(&data.entries);
+        let weekdays = UsageAggregator::weekdays_from(self.app_settings.week_start);
+        let max_cost = totals.iter().copied().fold(0.0f64, f64::max);
+
+        div()
+            .p_6()
+            .bg(theme.surface)
+            .rounded_lg()
+            .border_1()
+            .border_color(theme.border)
+            .shadow_sm()
+            .child(
+                div()
+                    .text_xl()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(theme.text)
+                    .mb_4()
+                    .child("Cost by Weekday"),
+            )
+            .child(div().flex().items_end().gap_3().children(weekdays.into_iter().zip(totals).map(
+                |(weekday, cost)| {
+                    let fraction = if max_cost > 0.0 { (cost / max_cost) as f32 } else { 0.0 };
+
+                    div()
+                        .flex_1()
+                        .flex()
+                        .flex_col()
+                        .items_center()
+                        .gap_1()
+                        .child(div().text_xs().text_color(theme.text_muted).child(self.format_cost(cost)))
+                        .child(
+                            div().w_full().h(px(bar_height)).flex().flex_col().justify_end().child(
+                                div()
+                                    .w_full()
+                                    .h(px(bar_height * fraction.max(MIN_SHARE_BAR_FRACTION)))
+                                    .rounded_sm()
+                                    .bg(Self::mix_colors(theme.border, theme.text_accent, fraction)),
+                            ),
+                        )
+                        .child(
+                            div()
+                                .text_sm()
+                                .font_weight(FontWeight::SEMIBOLD)
+                                .text_color(theme.text)
+                                .child(weekday.to_string()),
+                        )
+                },
+            )))
     }
 
-    fn render_daily_usage_timeline(&self, daily_usage: &[DailyUsage]) -> Div {
+    /// GitHub-style grid of the filtered range: one column per week, one cell per day shaded by cost
+    fn render_cost_calendar(&self, daily: &[DailyUsage]) -> Div {
+        const CELL_SIZE: f32 = 12.0;
+        const SHADES: f32 = 4.0;
+
         let theme = self.theme_registry.colors();
+        let week_start = self.app_settings.week_start;
+        let calendar = UsageAggregator::fill_missing_days(daily);
+        let days: Vec<(chrono::NaiveDate, &DailyUsage)> = calendar
+            .iter()
+            .filter_map(|day| {
+                chrono::NaiveDate::parse_from_str(&day.date, "%Y-%m-%d")
+                    .ok()
+                    .map(|date| (date, day))
+            })
+            .collect();
+        let (Some(&(first, _)), Some(&(last, _))) = (days.first(), days.last()) else {
+            return div();
+        };
+        let max_cost = days.iter().map(|(_, day)| day.total_cost).fold(0.0f64, f64::max);
+
+        // Leading and trailing partial weeks keep their missing days as empty slots
+        let week_count = UsageAggregator::calendar_cell(last, first, week_start).0 + 1;
+        let mut weeks: Vec<[Option<(chrono::NaiveDate, f64)>; 7]> = vec![[None; 7]; week_count];
+        for (date, day) in &days {
+            let (column, row) = UsageAggregator::calendar_cell(*date, first, week_start);
+            weeks[column][row] = Some((*date, day.total_cost));
+        }
 
-        // Group data by month
-        let monthly_data = self.group_daily_usage_by_month(daily_usage);
+        // Zero-cost days get the lightest shade; spend is bucketed like a contribution graph
+        let shade = |cost: f64| {
+            let level = if cost > 0.0 && max_cost > 0.0 {
+                ((cost / max_cost) as f32 * SHADES).ceil().clamp(1.0, SHADES)
+            } else {
+                0.0
+            };
+            Self::mix_colors(theme.border, theme.text_accent, level / SHADES)
+        };
+        let weekday_labels: Vec<Div> = (0..7)
+            .map(|row| {
+                let weekday = (0..row).fold(week_start, |day, _| day.succ());
+                div()
+                    .h(px(CELL_SIZE))
+                    .text_size(px(9.0))
+                    .line_height(px(CELL_SIZE))
+                    .text_color(theme.text_muted)
+                    .when(row % 2 == 0, |label| label.child(weekday.to_string()))
+            })
+            .collect();
 
         div()
             .p_6()
@@ -1858,10 +6772,281 @@ impl RootView {
                     .text_xl()
                     .font_weight(FontWeight::SEMIBOLD)
                     .text_color(theme.text)
-                    .mb_6()
-                    .child("Usage by Month"),
-            )
-            .child(self.render_monthly_bar_chart(monthly_data))
+                    .mb_4()
+                    .child("Cost Calendar"),
+            )
+            .child(
+                div()
+                    .flex()
+                    .gap(px(3.0))
+                    .child(div().flex().flex_col().gap(px(3.0)).pr_1().children(weekday_labels))
+                    .children(weeks.into_iter().map(|week| {
+                        div().flex().flex_col().gap(px(3.0)).children(week.into_iter().map(|cell| {
+                            let slot = div().size(px(CELL_SIZE)).rounded_sm();
+                            match cell {
+                                Some((date, cost)) => {
+                                    let tooltip = format!("{} · {}", date.format("%a %b %d"), self.format_cost(cost));
+                                    let (background, border, text) =
+                                        (theme.elevated_surface, theme.border, theme.text);
+                                    slot.id(SharedString::from(format!("calendar-{}", date)))
+                                        .bg(shade(cost))
+                                        .tooltip(move |_window, cx| {
+                                            cx.new(|_| TextTooltip::new(tooltip.clone(), background, border, text))
+                                                .into()
+                                        })
+                                        .into_any_element()
+                                }
+                                None => slot.into_any_element(),
+                            }
+                        }))
+                    })),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_end()
+                    .gap_1()
+                    .mt_3()
+                    .text_xs()
+                    .text_color(theme.text_muted)
+                    .child("Less")
+                    .children((0..=SHADES as usize).map(|level| {
+                        div()
+                            .size(px(CELL_SIZE))
+                            .rounded_sm()
+                            .bg(shade(max_cost * level as f64 / SHADES as f64))
+                    }))
+                    .child("More"),
+            )
+    }
+
+    pub fn set_timeline_granularity(&mut self, granularity: TimelineGranularity, cx: &mut Context<Self>) {
+        if self.timeline_granularity != granularity {
+            self.timeline_granularity = granularity;
+            // Remembered so the Timeline reopens on the same granularity
+            self.app_settings.timeline_granularity = granularity;
+            self.save_settings(cx);
+            cx.notify();
+        }
+    }
+
+    fn render_daily_usage_timeline(&self, daily_usage: &[DailyUsage], cx: &mut Context<Self>) -> Div {
+        let theme = self.theme_registry.colors();
+        let (title, chart) = match self.timeline_granularity {
+            TimelineGranularity::Daily => ("Usage by Day", self.render_daily_list(daily_usage)),
+            TimelineGranularity::Weekly => {
+                let aggregator = UsageAggregator::with_options(self.app_settings.aggregation_options());
+                let weekly_data = aggregator.group_daily_usage_by_week(daily_usage);
+                ("Usage by Week", self.render_weekly_bar_chart(weekly_data))
+            }
+            TimelineGranularity::Monthly => {
+                // Group data by month
+                let monthly_data = self.group_daily_usage_by_month(daily_usage);
+                ("Usage by Month", self.render_monthly_bar_chart(monthly_data))
+            }
+        };
+
+        div()
+            .p_6()
+            .bg(theme.surface)
+            .rounded_lg()
+            .border_1()
+            .border_color(theme.border)
+            .shadow_sm()
+            .child(
+                div()
+                    .flex()
+                    .justify_between()
+                    .items_center()
+                    .mb_6()
+                    .child(
+                        div()
+                            .text_xl()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(theme.text)
+                            .child(title),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_1()
+                            .children(
+                                [
+                                    TimelineGranularity::Daily,
+                                    TimelineGranularity::Weekly,
+                                    TimelineGranularity::Monthly,
+                                ]
+                                    .into_iter()
+                                    .map(|granularity| {
+                                        self.render_chip_button(
+                                            granularity.label(),
+                                            self.timeline_granularity == granularity,
+                                            cx,
+                                            move |view, cx| view.set_timeline_granularity(granularity, cx),
+                                        )
+                                    })
+                                    .collect::<Vec<_>>(),
+                            ),
+                    ),
+            )
+            .child(chart)
+    }
+
+    fn render_daily_list(&self, daily_usage: &[DailyUsage]) -> Div {
+        let mut days: Vec<&DailyUsage> = daily_usage.iter().collect();
+        days.sort_by(|a, b| a.date.cmp(&b.date));
+        let max_cost = days
+            .iter()
+            .map(|d| d.total_cost)
+            .fold(0.0f64, |a, b| a.max(b))
+            .max(1.0);
+        let aggregator = UsageAggregator::with_options(self.app_settings.aggregation_options());
+        let anomalous_days: HashSet<String> =
+            aggregator.anomalous_days(daily_usage).into_iter().collect();
+        // Average over calendar days, so quiet days pull the trend down
+        let calendar = UsageAggregator::fill_missing_days(daily_usage);
+        let rolling_averages: HashMap<String, f64> = calendar
+            .iter()
+            .map(|day| day.date.clone())
+            .zip(UsageAggregator::rolling_average(&calendar, ROLLING_AVERAGE_DAYS))
+            .collect();
+        let theme = self.theme_registry.colors();
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_4()
+            .when(!days.is_empty(), |list| {
+                list.child(
+                    div()
+                        .flex()
+                        .items_center()
+                        .gap_2()
+                        .text_xs()
+                        .text_color(theme.text_muted)
+                        .child(div().w(px(2.0)).h_3().bg(theme.warning))
+                        .child(format!("{}-day average", ROLLING_AVERAGE_DAYS)),
+                )
+            })
+            .children(
+            days.iter()
+                .map(|day| {
+                    let footer = self
+                        .render_model_chips(&day.models_used)
+                        .when(anomalous_days.contains(&day.date), |footer| {
+                            footer.child(self.render_anomaly_badge())
+                        });
+                    let label = chrono::NaiveDate::parse_from_str(&day.date, "%Y-%m-%d")
+                        .map(|date| date.format("%a %b %d").to_string())
+                        .unwrap_or_else(|_| day.date.clone());
+                    self.render_period_bar(
+                        label,
+                        day.total_cost,
+                        day.total_tokens,
+                        day.request_count,
+                        1,
+                        max_cost,
+                        rolling_averages.get(&day.date).copied(),
+                        Some(footer),
+                    )
+                })
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// Warning badge for days with spend far above the norm
+    fn render_anomaly_badge(&self) -> Div {
+        let theme = self.theme_registry.colors();
+        div()
+            .px_2()
+            .py(px(2.0))
+            .text_xs()
+            .font_weight(FontWeight::SEMIBOLD)
+            .text_color(theme.warning)
+            .border_1()
+            .border_color(theme.warning)
+            .rounded_full()
+            .child("⚠ Unusual spend")
+    }
+
+    /// Convert the calculator's hex model color into a theme-compatible color
+    fn model_color(&self, model: &str) -> Hsla {
+        let hex = CostCalculator::new().get_model_color(model);
+        u32::from_str_radix(hex.trim_start_matches('#'), 16)
+            .map(|value| rgb(value).into())
+            .unwrap_or(self.theme_registry.colors().text_muted)
+    }
+
+    /// Small colored chips for the models used, capped with a "+N more" overflow
+    fn render_model_chips(&self, models: &[String]) -> Div {
+        const MAX_MODEL_CHIPS: usize = 3;
+
+        let theme = self.theme_registry.colors();
+        let calculator = CostCalculator::new();
+        let overflow = models.len().saturating_sub(MAX_MODEL_CHIPS);
+
+        div()
+            .flex()
+            .flex_wrap()
+            .items_center()
+            .gap_2()
+            .children(models.iter().take(MAX_MODEL_CHIPS).map(|model| {
+                let color = self.model_color(model);
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_1()
+                    .px_2()
+                    .py(px(2.0))
+                    .text_xs()
+                    .text_color(theme.text)
+                    .bg(theme.surface)
+                    .border_1()
+                    .border_color(color)
+                    .rounded_full()
+                    .child(div().w_2().h_2().rounded_full().bg(color))
+                    .child(calculator.get_model_display_name(model))
+            }))
+            .when(overflow > 0, |chips| {
+                chips.child(
+                    div()
+                        .text_xs()
+                        .text_color(theme.text_muted)
+                        .child(format!("+{} more", overflow)),
+                )
+            })
+    }
+
+    fn render_weekly_bar_chart(&self, weekly_data: Vec<WeeklyUsage>) -> Div {
+        let max_cost = weekly_data
+            .iter()
+            .map(|w| w.total_cost)
+            .fold(0.0f64, |a, b| a.max(b))
+            .max(1.0);
+
+        div().flex().flex_col().gap_4().children(
+            weekly_data
+                .iter()
+                .map(|week| {
+                    // Label weeks by their first day so the configured week start is visible
+                    let label = chrono::NaiveDate::parse_from_str(&week.week_start, "%Y-%m-%d")
+                        .map(|date| date.format("%a %b %d").to_string())
+                        .unwrap_or_else(|_| week.week_start.clone());
+                    self.render_period_bar(
+                        label,
+                        week.total_cost,
+                        week.total_tokens,
+                        week.request_count,
+                        week.days_count,
+                        max_cost,
+                        None,
+                        None,
+                    )
+                })
+                .collect::<Vec<_>>(),
+        )
     }
 
     fn group_daily_usage_by_month(&self, daily_usage: &[DailyUsage]) -> Vec<MonthlyUsage> {
@@ -1911,27 +7096,56 @@ impl RootView {
     }
 
     fn render_monthly_bar(&self, month: &MonthlyUsage, max_cost: f64) -> Div {
+        self.render_period_bar(
+            month.month.clone(),
+            month.total_cost,
+            month.total_tokens,
+            month.request_count,
+            month.days_count,
+            max_cost,
+            None,
+            None,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn render_period_bar(
+        &self,
+        label: String,
+        total_cost: f64,
+        total_tokens: u64,
+        request_count: usize,
+        days_count: usize,
+        max_cost: f64,
+        average_cost: Option<f64>,
+        footer: Option<Div>,
+    ) -> Div {
         let theme = self.theme_registry.colors();
-        let _percentage = ((month.total_cost / max_cost) * 100.0) as u32;
-        let bar_width = (month.total_cost / max_cost * 300.0).max(10.0) as f32;
+        let bar_span = Self::chart_bar_span(f32::from(self.viewport_width)) as f64;
+        let bar_width = (total_cost / max_cost * bar_span).max(10.0) as f32;
+        // Marker on the same scale as the bar; stacked rows read as a trend line
+        let average_marker = average_cost.map(|average| {
+            div()
+                .absolute()
+                .top_0()
+                .left(px((average / max_cost * bar_span) as f32))
+                .w(px(2.0))
+                .h_full()
+                .bg(theme.warning)
+        });
 
-        div()
+        let row = div()
             .flex()
             .items_center()
             .gap_4()
-            .p_4()
-            .bg(theme.elevated_surface)
-            .border_1()
-            .border_color(theme.border)
-            .rounded_lg()
             .child(
-                // Month label
+                // Period label
                 div()
-                    .w_20()
+                    .w_32()
                     .text_sm()
                     .font_weight(FontWeight::MEDIUM)
                     .text_color(theme.text)
-                    .child(month.month.clone()),
+                    .child(label),
             )
             .child(
                 // Bar chart area
@@ -1939,8 +7153,9 @@ impl RootView {
                     div()
                         .flex()
                         .items_center()
+                        .relative()
                         .w_full()
-                        .h_6()
+                        .h(px(Self::chart_row_height(f32::from(self.viewport_height))))
                         .bg(theme.border)
                         .rounded(px(3.0))
                         .overflow_hidden()
@@ -1950,7 +7165,8 @@ impl RootView {
                                 .h_full()
                                 .bg(theme.metric_primary)
                                 .rounded(px(3.0)),
-                        ),
+                        )
+                        .children(average_marker),
                 ),
             )
             .child(
@@ -1967,13 +7183,17 @@ impl RootView {
                                     .text_sm()
                                     .font_weight(FontWeight::SEMIBOLD)
                                     .text_color(theme.success)
-                                    .child(format!("${:.2}", month.total_cost)),
+                                    .child(self.format_cost(total_cost)),
                             )
                             .child(
                                 div()
                                     .text_xs()
                                     .text_color(theme.text_muted)
-                                    .child(format!("{} days", month.days_count)),
+                                    .child(if days_count == 1 {
+                                        "1 day".to_string()
+                                    } else {
+                                        format!("{} days", days_count)
+                                    }),
                             ),
                     )
                     .child(
@@ -1983,19 +7203,314 @@ impl RootView {
                                 div()
                                     .text_sm()
                                     .text_color(theme.text)
-                                    .child(self.format_number(month.total_tokens)),
+                                    .child(self.format_number(total_tokens)),
                             )
                             .child(
                                 div()
                                     .text_xs()
                                     .text_color(theme.text_muted)
-                                    .child(format!("{} requests", month.request_count)),
+                                    .child(format!("{} requests", self.format_number(request_count as u64))),
                             ),
                     ),
-            )
+            );
+
+        let card = div()
+            .p_4()
+            .bg(theme.elevated_surface)
+            .border_1()
+            .border_color(theme.border)
+            .rounded_lg();
+
+        match footer {
+            Some(footer) => card.flex().flex_col().gap_2().child(row).child(footer),
+            None => card.child(row),
+        }
     }
     // Removed unused render_daily_usage_bar method during cleanup (replaced by monthly chart)
 
+    /// Callout for the project with the highest cost over the last 7 days
+    fn render_busiest_project_card(&self) -> impl IntoElement {
+        let theme = self.theme_registry.colors();
+        let busiest = self.full_analytics_data.as_ref().and_then(|data| {
+            let aggregator = UsageAggregator::with_options(self.app_settings.aggregation_options());
+            let recent = aggregator.counted_entries(aggregator.filter_by_time_range(&data.entries, TimeRange::Last7Days));
+            aggregator.busiest_project(&recent)
+        });
+
+        div()
+            .flex()
+            .items_center()
+            .justify_between()
+            .bg(theme.surface)
+            .rounded_lg()
+            .p_6()
+            .border_1()
+            .border_color(theme.border)
+            .shadow_sm()
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_2()
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_weight(FontWeight::MEDIUM)
+                            .text_color(theme.text_muted)
+                            .child("Busiest Project This Week"),
+                    )
+                    .child(
+                        div()
+                            .text_2xl()
+                            .font_weight(FontWeight::BOLD)
+                            .text_color(if busiest.is_some() {
+                                theme.text
+                            } else {
+                                theme.text_muted
+                            })
+                            .child(
+                                busiest
+                                    .as_ref()
+                                    .map(|p| p.project_name.clone())
+                                    .unwrap_or_else(|| "No recent activity".to_string()),
+                            ),
+                    ),
+            )
+            .children(busiest.map(|project| {
+                div()
+                    .text_right()
+                    .child(
+                        div()
+                            .text_2xl()
+                            .font_weight(FontWeight::BOLD)
+                            .text_color(theme.success)
+                            .child(self.format_cost(project.total_cost)),
+                    )
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(theme.text_muted)
+                            .child(format!(
+                                "{} requests",
+                                self.format_number(project.request_count as u64)
+                            )),
+                    )
+            }))
+    }
+
+    /// Yearly cost at the recent daily average, over all loaded data regardless of the time range
+    fn render_run_rate_card(&self) -> Option<Div> {
+        let theme = self.theme_registry.colors();
+        let data = self.full_analytics_data.as_ref()?;
+        let aggregator = UsageAggregator::with_options(self.app_settings.aggregation_options());
+        let rate = aggregator.annualized_run_rate(&data.entries, RUN_RATE_WINDOW_DAYS)?;
+        let window = if rate.days_used < RUN_RATE_WINDOW_DAYS {
+            format!("only {} days of history so far", rate.days_used)
+        } else {
+            format!("the last {} days", rate.days_used)
+        };
+
+        Some(
+            div()
+                .flex()
+                .items_center()
+                .justify_between()
+                .bg(theme.surface)
+                .rounded_lg()
+                .p_6()
+                .border_1()
+                .border_color(theme.border)
+                .shadow_sm()
+                .child(
+                    div()
+                        .flex()
+                        .flex_col()
+                        .gap_2()
+                        .child(
+                            div()
+                                .text_sm()
+                                .font_weight(FontWeight::MEDIUM)
+                                .text_color(theme.text_muted)
+                                .child("Annualized Run-Rate (estimate)"),
+                        )
+                        .child(
+                            div()
+                                .text_sm()
+                                .text_color(theme.text_muted)
+                                .child(format!("{}/day on average over {}, times 365", self.format_cost(rate.daily_average), window)),
+                        ),
+                )
+                .child(
+                    div()
+                        .text_2xl()
+                        .font_weight(FontWeight::BOLD)
+                        .text_color(theme.text)
+                        .child(format!("≈ {} / year", self.format_cost(rate.annual_cost))),
+                ),
+        )
+    }
+
+    /// Cache writes versus reads for the filtered data, with a verdict on reuse
+    fn render_cache_efficiency_card(&self, analytics: &UsageStats) -> Div {
+        let theme = self.theme_registry.colors();
+        let aggregator = UsageAggregator::with_options(self.app_settings.aggregation_options());
+        let cache = aggregator.cache_efficiency(&analytics.entries);
+        let ratio = cache
+            .reads_per_creation()
+            .map(|ratio| format!("{:.1} reads per write", ratio))
+            .unwrap_or_else(|| "No cache writes".to_string());
+
+        div()
+            .flex()
+            .items_center()
+            .justify_between()
+            .bg(theme.surface)
+            .rounded_lg()
+            .p_6()
+            .border_1()
+            .border_color(theme.border)
+            .shadow_sm()
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_2()
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_weight(FontWeight::MEDIUM)
+                            .text_color(theme.text_muted)
+                            .child("Cache Reuse"),
+                    )
+                    .child(
+                        div()
+                            .text_2xl()
+                            .font_weight(FontWeight::BOLD)
+                            .text_color(theme.text)
+                            .child(cache.label()),
+                    )
+                    .child(div().text_sm().text_color(theme.text_muted).child(ratio)),
+            )
+            .child(
+                div()
+                    .flex()
+                    .gap_4()
+                    .child(self.render_project_stat(
+                        "Cache Writes",
+                        format!("{} · {}", self.format_number(cache.creation_tokens), self.format_cost(cache.creation_cost)),
+                        theme.metric_quaternary,
+                    ))
+                    .child(self.render_project_stat(
+                        "Cache Reads",
+                        format!("{} · {}", self.format_number(cache.read_tokens), self.format_cost(cache.read_cost)),
+                        theme.metric_tertiary,
+                    )),
+            )
+    }
+
+    /// Costliest single request plus how many requests crossed the alert threshold
+    fn render_expensive_request_card(&self, analytics: &UsageStats, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let theme = self.theme_registry.colors();
+        let elevated_surface = theme.elevated_surface;
+        let request = analytics.most_expensive_request.as_ref()?;
+        let over_alert = self.app_settings.request_cost_alert.map(|threshold| {
+            format!(
+                "{} requests over {}",
+                self.format_number(analytics.requests_over_cost_alert as u64),
+                self.format_cost(threshold)
+            )
+        });
+        let tokens = request.input_tokens
+            + request.output_tokens
+            + request.cache_read_tokens
+            + request.cache_creation_tokens;
+
+        Some(
+            div()
+                .id("most-expensive-request")
+                .flex()
+                .items_center()
+                .justify_between()
+                .bg(theme.surface)
+                .rounded_lg()
+                .p_6()
+                .border_1()
+                .border_color(if analytics.requests_over_cost_alert > 0 { theme.warning } else { theme.border })
+                .shadow_sm()
+                .cursor_pointer()
+                .hover(move |style| style.bg(elevated_surface))
+                .on_click(cx.listener(|view, _event, _window, cx| view.show_most_expensive_request(cx)))
+                .child(
+                    div()
+                        .flex()
+                        .flex_col()
+                        .gap_2()
+                        .child(
+                            div()
+                                .text_sm()
+                                .font_weight(FontWeight::MEDIUM)
+                                .text_color(theme.text_muted)
+                                .child("Most Expensive Request"),
+                        )
+                        .child(
+                            div()
+                                .text_2xl()
+                                .font_weight(FontWeight::BOLD)
+                                .text_color(theme.text)
+                                .child(CostCalculator::new().get_model_display_name(&request.model)),
+                        )
+                        .child(
+                            div()
+                                .text_sm()
+                                .text_color(theme.text_muted)
+                                .child(format!(
+                                    "{} tokens · {}",
+                                    self.format_number(tokens),
+                                    request.timestamp.with_timezone(&Local).format("%Y-%m-%d %H:%M")
+                                )),
+                        ),
+                )
+                .child(
+                    div()
+                        .text_right()
+                        .child(
+                            div()
+                                .text_2xl()
+                                .font_weight(FontWeight::BOLD)
+                                .text_color(theme.success)
+                                .child(self.format_cost(request.cost)),
+                        )
+                        .children(over_alert.map(|text| {
+                            div().text_sm().text_color(theme.text_muted).child(text)
+                        })),
+                ),
+        )
+    }
+
+    /// Pixels a bar at the period maximum spans, so bars fill the room a wide window gives them
+    fn chart_bar_span(viewport_width: f32) -> f32 {
+        let (min, max) = CHART_BAR_SPAN_RANGE;
+        (viewport_width - CHART_ROW_CHROME_WIDTH).clamp(min, max)
+    }
+
+    /// Height of a timeline bar; about a fortieth of the window keeps a month of days on screen
+    fn chart_row_height(viewport_height: f32) -> f32 {
+        let (min, max) = CHART_ROW_HEIGHT_RANGE;
+        (viewport_height / 40.0).clamp(min, max)
+    }
+
+    fn is_narrow_layout(&self) -> bool {
+        self.viewport_width < px(NARROW_LAYOUT_WIDTH)
+    }
+
+    /// Row of metric cards that wraps to two per line on narrow windows
+    fn metric_card_row(&self) -> Div {
+        div()
+            .flex()
+            .gap_4()
+            .when(self.is_narrow_layout(), |row| row.flex_wrap())
+    }
+
     fn render_metric_card(
         &self,
         title: &'static str,
@@ -2011,6 +7526,8 @@ impl RootView {
         };
 
         div()
+            // Two cards per line when wrapped: each grows from a basis just under half the row
+            .when(self.is_narrow_layout(), |card| card.flex_grow().flex_basis(relative(0.4)))
             .bg(theme.surface)
             .rounded_lg()
             .p_6()
@@ -2041,7 +7558,11 @@ impl RootView {
 }
 
 impl Render for RootView {
-    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        self.viewport_width = window.viewport_size().width;
+        self.viewport_height = window.viewport_size().height;
+
+
         let theme = self.theme_registry.colors();
 
         div()
@@ -2049,76 +7570,58 @@ impl Render for RootView {
             .flex_col()
             .size_full()
             .bg(theme.background)
+            .key_context("Dashboard")
             .track_focus(&self.focus_handle)
+            .on_action(cx.listener(|view: &mut RootView, _: &Export, _window, cx| {
+                view.export_current_view(cx);
+            }))
+            .on_action(cx.listener(|view: &mut RootView, _: &CopyVisibleData, _window, cx| {
+                view.copy_visible_data(cx);
+            }))
+            .on_action(cx.listener(|view: &mut RootView, _: &MarkAllSeen, _window, cx| {
+                view.mark_all_tabs_seen(cx);
+            }))
+            .on_action(cx.listener(|view: &mut RootView, _: &ShareSummary, _window, cx| {
+                view.share_summary(cx);
+            }))
+            .on_action(cx.listener(|view: &mut RootView, _: &ExportParseErrors, _window, cx| {
+                view.export_parse_errors(cx);
+            }))
             .on_key_down(cx.listener(
                 |view: &mut RootView,
                  event: &KeyDownEvent,
-                 _window: &mut Window,
+                 window: &mut Window,
                  cx: &mut Context<RootView>| {
-                    // Tab navigation using number keys 1-5
-                    // Time range filtering using alt+1, alt+2, alt+3
-                    if event.keystroke.modifiers.platform {
-                        match event.keystroke.key.as_str() {
-                            "h" => {
-                                // Handle CMD+H to hide application (standard macOS behavior)
-                                println!("🙈 CMD+H pressed - hiding application");
-                                cx.hide();
-                            }
-                            "q" => {
-                                // Handle CMD+Q to quit application (standard macOS behavior)
-                                println!("🚪 CMD+Q pressed - quitting application");
-                                cx.quit();
-                            }
-                            "w" => {
-                                // Handle CMD+W to close window (standard macOS behavior)
-                                println!("🪟 CMD+W pressed - closing window");
-                                cx.hide();  // Hide the window instead of quitting
-                            }
-                            _ => {}
-                        }
-                    } else if event.keystroke.modifiers.alt {
-                        match event.keystroke.key.as_str() {
-                            "1" => {
-                                view.set_time_range(TimeRange::AllTime, cx);
-                            }
-                            "2" => {
-                                view.set_time_range(TimeRange::Last30Days, cx);
-                            }
-                            "3" => {
-                                view.set_time_range(TimeRange::Last7Days, cx);
-                            }
-                            _ => {}
+                    if event.keystroke.key == "tab" && !event.keystroke.modifiers.platform {
+                        // Move keyboard focus between tabs, time ranges and the theme toggle
+                        if event.keystroke.modifiers.shift {
+                            window.focus_prev();
+                        } else {
+                            window.focus_next();
                         }
-                    } else {
-                        match event.keystroke.key.as_str() {
-                            "1" => {
-                                view.active_tab = DashboardTab::Overview;
-                                cx.notify();
-                            }
-                            "2" => {
-                                view.active_tab = DashboardTab::Models;
-                                cx.notify();
-                            }
-                            "3" => {
-                                view.active_tab = DashboardTab::Projects;
-                                cx.notify();
-                            }
-                            "4" => {
-                                view.active_tab = DashboardTab::Sessions;
-                                cx.notify();
-                            }
-                            "5" => {
-                                view.active_tab = DashboardTab::Timeline;
-                                cx.notify();
-                            }
-                            _ => {}
+                    } else if event.keystroke.key == "escape" {
+                        if view.show_shortcut_overlay {
+                            view.toggle_shortcut_overlay(cx);
+                        } else if view.show_onboarding {
+                            view.complete_onboarding(cx);
                         }
+                    } else if let Some(shortcut) = shortcuts::find(&event.keystroke) {
+                        view.run_shortcut(&shortcut.command, window, cx);
                     }
                 },
             ))
             .child(self.render_header(cx))
             .child(self.render_tab_navigation(cx))
             .child(self.render_main_content(cx))
+            .child(self.render_export_menu(cx))
+            .child(self.render_auto_refresh_menu(cx))
+            .child(self.render_card_menu(cx))
+            .child(self.render_settings_panel(cx))
+            .child(self.render_debug_panel())
+            .child(self.render_onboarding(cx))
+            .child(self.render_shortcut_overlay(cx))
+            .child(self.render_refresh_indicator())
+            .child(self.render_toast())
     }
 }
 
@@ -2127,3 +7630,286 @@ impl Focusable for RootView {
         self.focus_handle.clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    // Named imports only: a glob of gpui would shadow the built-in `#[test]` attribute
+    use super::{
+        AppSettings, DashboardTab, RootView, ThemeRegistry, ThemeSettings, CHART_BAR_SPAN_RANGE,
+        CHART_ROW_HEIGHT_RANGE,
+    };
+    use crate::analytics::aggregator::UsageAggregator;
+    use crate::analytics::models::{TimeRange, TimelineGranularity, UsageEntry};
+    use gpui::{AppContext as _, TestAppContext};
+
+    fn entry(project: &str, days_ago: i64, cost: f64) -> UsageEntry {
+        UsageEntry {
+            timestamp: chrono::Utc::now() - chrono::Duration::days(days_ago),
+            model: "claude-sonnet-4-20250514".to_string(),
+            project_path: Some(project.to_string()),
+            session_id: Some(format!("{}-session", project)),
+            request_id: None,
+            input_tokens: 100,
+            output_tokens: 50,
+            cache_read_tokens: 0,
+            cache_creation_tokens: 0,
+            cost,
+            computed_cost: cost,
+            logged_cost: None,
+            future_dated: false,
+            raw_line: None,
+        }
+    }
+
+    #[test]
+    fn test_scroll_thumb_fractions() {
+        // Everything fits: no scrollbar
+        assert_eq!(RootView::scroll_thumb(400.0, 0.0, 0.0), None);
+        assert_eq!(RootView::scroll_thumb(0.0, 100.0, 0.0), None);
+
+        // 400px visible out of 1600px: a quarter-height thumb that moves with the offset
+        assert_eq!(RootView::scroll_thumb(400.0, 1200.0, 0.0), Some((0.0, 0.25)));
+        assert_eq!(RootView::scroll_thumb(400.0, 1200.0, -600.0), Some((0.375, 0.25)));
+        assert_eq!(RootView::scroll_thumb(400.0, 1200.0, -1200.0), Some((0.75, 0.25)));
+        // Overscroll never pushes the thumb off the track
+        assert_eq!(RootView::scroll_thumb(400.0, 1200.0, -1500.0), Some((0.75, 0.25)));
+    }
+
+    #[test]
+    fn test_chart_size_follows_the_window_within_bounds() {
+        assert_eq!(RootView::chart_bar_span(1280.0), 800.0);
+        assert_eq!(RootView::chart_bar_span(400.0), CHART_BAR_SPAN_RANGE.0);
+        assert_eq!(RootView::chart_bar_span(4000.0), CHART_BAR_SPAN_RANGE.1);
+
+        assert_eq!(RootView::chart_row_height(960.0), 24.0);
+        assert_eq!(RootView::chart_row_height(300.0), CHART_ROW_HEIGHT_RANGE.0);
+        // Before the first render the viewport is unknown and reads as huge
+        assert_eq!(RootView::chart_row_height(f32::MAX), CHART_ROW_HEIGHT_RANGE.1);
+    }
+
+    #[gpui::test]
+    fn test_time_range_filters_injected_entries(cx: &mut TestAppContext) {
+        let entries = vec![
+            entry("/Users/dev/recent", 1, 1.0),
+            entry("/Users/dev/older", 20, 2.0),
+            entry("/Users/dev/ancient", 200, 4.0),
+        ];
+        let view = cx.new(|cx| RootView::with_entries(entries, cx));
+
+        // The view starts on the 30 day range
+        view.read_with(cx, |view, _| {
+            let stats = view.analytics_data.as_ref().unwrap();
+            assert!((stats.total_cost - 3.0).abs() < 1e-9);
+            assert_eq!(stats.project_stats.len(), 2);
+        });
+
+        view.update(cx, |view, cx| view.set_time_range(TimeRange::AllTime, cx));
+        view.read_with(cx, |view, _| {
+            assert!((view.analytics_data.as_ref().unwrap().total_cost - 7.0).abs() < 1e-9);
+        });
+
+        view.update(cx, |view, cx| view.set_time_range(TimeRange::Last7Days, cx));
+        view.read_with(cx, |view, _| {
+            let stats = view.analytics_data.as_ref().unwrap();
+            assert!((stats.total_cost - 1.0).abs() < 1e-9);
+            assert_eq!(stats.session_count, 1);
+        });
+    }
+
+    #[gpui::test]
+    fn test_sessions_sharing_an_id_are_both_listed(cx: &mut TestAppContext) {
+        let shared = |project: &str, cost: f64| UsageEntry {
+            session_id: Some("shared".to_string()),
+            ..entry(project, 1, cost)
+        };
+        let entries = vec![shared("/Users/dev/api", 1.0), shared("/Users/dev/web", 2.0)];
+        let view = cx.new(|cx| RootView::with_entries(entries, cx));
+
+        view.read_with(cx, |view, _| {
+            let sessions = view.get_sessions_data();
+            assert_eq!(sessions.len(), 2);
+            let mut projects: Vec<&str> = sessions.iter().map(|s| s.project_path.as_str()).collect();
+            projects.sort();
+            assert_eq!(projects, vec!["/Users/dev/api", "/Users/dev/web"]);
+        });
+    }
+
+    #[gpui::test]
+    fn test_background_refresh_keeps_data_and_has_its_own_status(cx: &mut TestAppContext) {
+        let view = cx.new(|cx| RootView::with_entries(vec![entry("/Users/dev/app", 1, 1.0)], cx));
+
+        view.update(cx, |view, cx| {
+            let ready = view.status_dot_color();
+            assert!(view.begin_refresh(cx).is_some());
+            // Data already on screen stays there and the dot switches to "refreshing"
+            assert!(view.is_background_refresh());
+            assert!(view.analytics_data.is_some());
+            let refreshing = view.status_dot_color();
+            assert_ne!(refreshing, ready);
+
+            // A load with nothing to show yet is "loading", not a background refresh
+            view.is_loading = true;
+            assert!(!view.is_background_refresh());
+            assert_ne!(view.status_dot_color(), refreshing);
+        });
+    }
+
+    #[gpui::test]
+    fn test_go_to_session_matches_id_prefix(cx: &mut TestAppContext) {
+        let entries = vec![entry("alpha", 1, 1.0), entry("beta", 2, 2.0)];
+        let view = cx.new(|cx| RootView::with_entries(entries, cx));
+
+        view.update(cx, |view, cx| assert!(view.go_to_session("bet", cx)));
+        view.read_with(cx, |view, _| {
+            assert_eq!(view.selected_session, Some(("beta".to_string(), "beta-session".to_string())));
+            assert_eq!(view.active_tab, DashboardTab::Sessions);
+        });
+
+        // A miss keeps the current selection and reports it instead
+        view.update(cx, |view, cx| assert!(!view.go_to_session("gamma", cx)));
+        view.read_with(cx, |view, _| {
+            assert_eq!(view.selected_session, Some(("beta".to_string(), "beta-session".to_string())));
+            assert_eq!(view.toast_message.as_deref(), Some("Session not found: gamma"));
+        });
+    }
+
+    #[gpui::test]
+    fn test_compared_project_selection(cx: &mut TestAppContext) {
+        let view = cx.new(|cx| RootView::with_entries(vec![entry("alpha", 1, 1.0), entry("beta", 1, 2.0)], cx));
+
+        // Picking the same project twice fills both columns
+        view.update(cx, |view, cx| {
+            view.pick_compared_project("alpha".to_string(), cx);
+            view.pick_compared_project("alpha".to_string(), cx);
+        });
+        view.read_with(cx, |view, _| {
+            assert_eq!(view.compared_projects, [Some("alpha".to_string()), Some("alpha".to_string())]);
+        });
+
+        // With both full the right column is replaced; a cleared column is refilled first
+        view.update(cx, |view, cx| view.pick_compared_project("beta".to_string(), cx));
+        view.update(cx, |view, cx| view.clear_compared_project(0, cx));
+        view.read_with(cx, |view, _| {
+            assert_eq!(view.compared_projects, [None, Some("beta".to_string())]);
+        });
+        view.update(cx, |view, cx| view.pick_compared_project("alpha".to_string(), cx));
+        view.read_with(cx, |view, _| {
+            assert_eq!(view.compared_projects, [Some("alpha".to_string()), Some("beta".to_string())]);
+        });
+    }
+
+    #[gpui::test]
+    fn test_header_rollup_follows_filters(cx: &mut TestAppContext) {
+        let entries = vec![entry("alpha", 1, 1.0), entry("beta", 20, 2.0), entry("gamma", 200, 4.0)];
+        let view = cx.new(|cx| RootView::with_entries(entries, cx));
+
+        let values = |view: &RootView| view.header_rollup().map(|(_, value)| value);
+        view.read_with(cx, |view, _| assert_eq!(values(view), ["$3.00", "300", "2"]));
+
+        view.update(cx, |view, cx| view.set_time_range(TimeRange::AllTime, cx));
+        view.read_with(cx, |view, _| assert_eq!(values(view), ["$7.00", "450", "3"]));
+
+        view.update(cx, |view, _| view.is_loading = true);
+        view.read_with(cx, |view, _| assert_eq!(values(view), ["—", "—", "—"]));
+    }
+
+    #[gpui::test]
+    fn test_excluded_project_leaves_every_view(cx: &mut TestAppContext) {
+        let entries = vec![entry("alpha", 1, 1.0), entry("noise", 1, 5.0)];
+        let view = cx.new(|cx| RootView::with_entries(entries, cx));
+
+        view.update(cx, |view, _| {
+            view.app_settings.excluded_projects.push("noise".to_string());
+            view.apply_time_filter();
+        });
+        view.read_with(cx, |view, _| {
+            let stats = view.analytics_data.as_ref().unwrap();
+            assert!((stats.total_cost - 1.0).abs() < 1e-9);
+            assert!(!stats.project_stats.contains_key("noise"));
+            assert!(view.get_sessions_data().iter().all(|s| s.project_path != "noise"));
+            // The unfiltered cache still holds the entries so they can be included again
+            assert_eq!(view.full_analytics_data.as_ref().unwrap().entries.len(), 2);
+        });
+    }
+
+    #[gpui::test]
+    fn test_tab_badge_shows_spend_since_last_viewed(cx: &mut TestAppContext) {
+        let view = cx.new(|cx| RootView::with_entries(vec![entry("alpha", 1, 1.0)], cx));
+
+        // Leave Overview, then a refresh brings in more spend
+        view.update(cx, |view, cx| {
+            view.set_active_tab(DashboardTab::Models, cx);
+            let aggregator = UsageAggregator::with_options(view.app_settings.aggregation_options());
+            let stats = aggregator.aggregate_entries(vec![entry("alpha", 1, 1.0), entry("beta", 1, 2.5)]);
+            view.finish_refresh(Ok(stats), cx);
+        });
+        view.read_with(cx, |view, _| {
+            assert!((view.tab_spend_delta(&DashboardTab::Overview).unwrap() - 2.5).abs() < 1e-9);
+            // Never left this session, so nothing to compare against
+            assert_eq!(view.tab_spend_delta(&DashboardTab::Projects), None);
+            assert_eq!(view.tab_spend_delta(&DashboardTab::Models), None);
+        });
+
+        // Viewing the tab clears its badge
+        view.update(cx, |view, cx| view.set_active_tab(DashboardTab::Overview, cx));
+        view.read_with(cx, |view, _| {
+            assert_eq!(view.tab_spend_delta(&DashboardTab::Overview), None);
+            assert_eq!(view.tab_spend_delta(&DashboardTab::Models), None);
+        });
+    }
+
+    #[gpui::test]
+    fn test_mark_all_seen_clears_every_badge(cx: &mut TestAppContext) {
+        let view = cx.new(|cx| RootView::with_entries(vec![entry("alpha", 1, 1.0)], cx));
+        let refresh_with = |view: &mut RootView, entries: Vec<UsageEntry>, cx: &mut gpui::Context<RootView>| {
+            let aggregator = UsageAggregator::with_options(view.app_settings.aggregation_options());
+            view.finish_refresh(Ok(aggregator.aggregate_entries(entries)), cx);
+        };
+
+        // Visit two tabs, then new spend arrives
+        view.update(cx, |view, cx| {
+            view.set_active_tab(DashboardTab::Models, cx);
+            view.set_active_tab(DashboardTab::Projects, cx);
+            refresh_with(view, vec![entry("alpha", 1, 1.0), entry("beta", 1, 2.0)], cx);
+        });
+        view.read_with(cx, |view, _| {
+            assert!(view.tab_spend_delta(&DashboardTab::Overview).is_some());
+            assert!(view.tab_spend_delta(&DashboardTab::Models).is_some());
+            assert!(view.has_unseen_spend());
+        });
+
+        view.update(cx, |view, cx| view.mark_all_tabs_seen(cx));
+        view.read_with(cx, |view, _| {
+            assert!(!view.has_unseen_spend());
+            assert!(DashboardTab::all().iter().all(|tab| view.tab_spend_delta(tab).is_none()));
+        });
+
+        // Later spend is measured from the new baseline, including tabs never left
+        view.update(cx, |view, cx| {
+            refresh_with(
+                view,
+                vec![entry("alpha", 1, 1.0), entry("beta", 1, 2.0), entry("gamma", 1, 0.5)],
+                cx,
+            );
+        });
+        view.read_with(cx, |view, _| {
+            assert!((view.tab_spend_delta(&DashboardTab::Overview).unwrap() - 0.5).abs() < 1e-9);
+            assert!((view.tab_spend_delta(&DashboardTab::Timeline).unwrap() - 0.5).abs() < 1e-9);
+            assert_eq!(view.tab_spend_delta(&DashboardTab::Projects), None);
+        });
+    }
+
+    #[gpui::test]
+    fn test_initial_timeline_granularity_follows_settings(cx: &mut TestAppContext) {
+        for granularity in [TimelineGranularity::Daily, TimelineGranularity::Weekly, TimelineGranularity::Monthly] {
+            let settings = AppSettings {
+                timeline_granularity: granularity,
+                ..AppSettings::default()
+            };
+            let view = cx.new(|cx| {
+                RootView::with_settings(settings, ThemeRegistry::with_settings(ThemeSettings::default()), cx)
+            });
+            view.read_with(cx, |view, _| assert_eq!(view.timeline_granularity, granularity));
+        }
+    }
+}
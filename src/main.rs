@@ -5,18 +5,32 @@ mod ui;
 mod utils;
 
 use gpui::*;
+use log::info;
+use app::shortcuts;
+use app::settings::AppSettings;
 use app::views::root::RootView;
+use utils::logging;
 
 fn main() {
-    println!("🎯 Initializing Claude Code Usage Dashboard (GPUI)...");
+    let settings = AppSettings::load();
+    logging::init(settings.log_verbosity);
+    info!("🎯 Initializing Claude Code Usage Dashboard (GPUI)...");
     
     // Initialize GPUI application with proper quit behavior
     let app = Application::new();
-    app.run(|cx: &mut App| {
-        println!("🚀 Creating dashboard window...");
+    app.run(move |cx: &mut App| {
+        info!("🚀 Creating dashboard window...");
         
-        // Set up window bounds - larger size for dashboard
-        let bounds = Bounds::centered(None, size(px(1200.0), px(800.0)), cx);
+        cx.bind_keys(shortcuts::action_bindings());
+        
+        // Restore the last window bounds if they are still on a connected display,
+        // otherwise fall back to a centered default size
+        let displays: Vec<Bounds<Pixels>> = cx.displays().iter().map(|display| display.bounds()).collect();
+        let bounds = settings
+            .window_geometry
+            .filter(|geometry| geometry.is_visible_on(&displays))
+            .map(|geometry| geometry.to_bounds())
+            .unwrap_or_else(|| Bounds::centered(None, size(px(1200.0), px(800.0)), cx));
         
         // Create the main window with proper window management
         let window_handle = cx.open_window(
@@ -31,8 +45,8 @@ fn main() {
                 is_movable: true,
                 ..Default::default()
             },
-            |_window, cx| {
-                cx.new(|cx| RootView::new(cx))
+            |window, cx| {
+                cx.new(|cx| RootView::new(window, cx))
             }
         )
         .unwrap();
@@ -42,13 +56,13 @@ fn main() {
         
         // Handle application quit properly - quit when last window closes
         let _quit_subscription = cx.on_app_quit(|_| async move {
-            println!("🔴 Application quit requested");
+            info!("🔴 Application quit requested");
         });
         
         // Handle window close events - let the window close normally without quitting
         // This allows the app to stay in the dock (standard macOS behavior)
         let _window_close_subscription = cx.on_window_closed(|_closed_window_id| {
-            println!("🪟 Window closed - app remains in dock");
+            info!("🪟 Window closed - app remains in dock");
             // Don't force quit - let macOS handle the app lifecycle
         });
         
@@ -57,6 +71,6 @@ fn main() {
         
         // Simple approach: Handle Cmd+Q and window close at the OS level
         
-        println!("✅ Claude Code Usage Dashboard started successfully!");
+        info!("✅ Claude Code Usage Dashboard started successfully!");
     });
 }
\ No newline at end of file
@@ -27,6 +27,7 @@ pub struct ThemeColors {
     
     // Status colors
     pub success: Hsla,
+    pub warning: Hsla,
     
     // Metric colors for dashboard cards
     pub metric_primary: Hsla,
@@ -56,6 +57,7 @@ impl ThemeColors {
             
             // Status colors (same for light/dark)
             success: hsla(145.0 / 360.0, 0.53, 0.42, 1.0),        // #30a46c - green
+            warning: hsla(20.0 / 360.0, 0.9, 0.5, 1.0),           // #f2620d - red-orange
             
             // Metric colors for dashboard cards
             metric_primary: hsla(210.0 / 360.0, 1.0, 0.5, 1.0),     // Blue
@@ -85,6 +87,7 @@ impl ThemeColors {
             
             // Status colors (adjusted for dark mode)
             success: hsla(145.0 / 360.0, 0.53, 0.47, 1.0),        // #33b074 - green
+            warning: hsla(20.0 / 360.0, 0.9, 0.58, 1.0),          // #f47a33 - red-orange
             
             // Metric colors for dashboard cards (adjusted for dark mode)
             metric_primary: hsla(210.0 / 360.0, 1.0, 0.62, 1.0),    // Lighter blue
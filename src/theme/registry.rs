@@ -16,7 +16,11 @@ pub struct ThemeRegistry {
 impl ThemeRegistry {
     /// Create a new theme registry with loaded settings
     pub fn new() -> Self {
-        let settings = ThemeSettings::load();
+        Self::with_settings(ThemeSettings::load())
+    }
+    
+    /// Create a registry from explicit settings without reading the theme file
+    pub fn with_settings(settings: ThemeSettings) -> Self {
         let current_colors = Self::colors_for_mode(settings.effective_mode());
         
         Self {
@@ -58,6 +62,22 @@ impl ThemeRegistry {
         Ok(())
     }
     
+    /// Restore default theme settings and persist them
+    pub fn reset_to_defaults(&mut self) -> anyhow::Result<()> {
+        let settings = ThemeSettings::default();
+        let result = settings.save();
+        
+        self.current_colors = Arc::new(Self::colors_for_mode(settings.effective_mode()));
+        self.settings = Arc::new(settings);
+        
+        result
+    }
+    
+    /// Get the persisted theme settings
+    pub fn settings(&self) -> &ThemeSettings {
+        &self.settings
+    }
+    
     /// Check if current theme is dark
     pub fn is_dark(&self) -> bool {
         matches!(self.mode(), ThemeMode::Dark)
@@ -2,8 +2,7 @@
 // Handles user theme preferences and system theme detection
 
 use serde::{Deserialize, Serialize};
-use std::fs;
-use std::path::PathBuf;
+use crate::utils::config::ConfigStore;
 use crate::theme::colors::ThemeMode;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,13 +21,8 @@ impl Default for ThemeSettings {
 }
 
 impl ThemeSettings {
-    /// Get the settings file path
-    fn settings_path() -> anyhow::Result<PathBuf> {
-        let home_dir = dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Could not find home directory"))?;
-        let config_dir = home_dir.join(".config").join("claude-code-usage-dashboard");
-        fs::create_dir_all(&config_dir)?;
-        Ok(config_dir.join("theme.json"))
-    }
+    /// Settings file name within the config directory
+    const FILE_NAME: &str = "theme.json";
     
     /// Load theme settings from disk
     pub fn load() -> Self {
@@ -44,18 +38,15 @@ impl ThemeSettings {
     }
     
     fn load_from_disk() -> anyhow::Result<Self> {
-        let path = Self::settings_path()?;
-        let content = fs::read_to_string(path)?;
+        let content = ConfigStore::global().read(Self::FILE_NAME)?;
         let settings: ThemeSettings = serde_json::from_str(&content)?;
         Ok(settings)
     }
     
     /// Save theme settings to disk
     pub fn save(&self) -> anyhow::Result<()> {
-        let path = Self::settings_path()?;
         let content = serde_json::to_string_pretty(self)?;
-        fs::write(path, content)?;
-        Ok(())
+        ConfigStore::global().write(Self::FILE_NAME, &content)
     }
     
     /// Get the effective theme mode (resolving System to Light/Dark)
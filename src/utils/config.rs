@@ -0,0 +1,164 @@
+/// Shared access to the dashboard's config directory
+///
+/// All persisted settings go through a single `ConfigStore`, rooted at
+/// ~/.config/claude-code-usage-dashboard unless `USAGE_DASHBOARD_CONFIG_DIR`
+/// points somewhere else. If the directory
+/// turns out to be unwritable, the store switches to an in-memory-only mode for
+/// the rest of the session instead of failing on every save.
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use log::warn;
+
+use crate::utils::system;
+
+/// Environment variable that relocates the config directory (for tests and portable installs)
+pub const CONFIG_DIR_ENV: &str = "USAGE_DASHBOARD_CONFIG_DIR";
+
+pub struct ConfigStore {
+    dir: Option<PathBuf>,
+    read_only: AtomicBool,
+    notice_pending: AtomicBool,
+}
+
+impl ConfigStore {
+    /// Store rooted at `dir` (None keeps every setting in memory)
+    pub fn at(dir: Option<PathBuf>) -> Self {
+        Self {
+            dir,
+            read_only: AtomicBool::new(false),
+            notice_pending: AtomicBool::new(false),
+        }
+    }
+
+    /// The process-wide store at `default_dir`
+    pub fn global() -> &'static ConfigStore {
+        static STORE: OnceLock<ConfigStore> = OnceLock::new();
+        STORE.get_or_init(|| ConfigStore::at(Some(Self::default_dir())))
+    }
+
+    /// $USAGE_DASHBOARD_CONFIG_DIR when set (created if missing), otherwise
+    /// ~/.config/claude-code-usage-dashboard
+    pub fn default_dir() -> PathBuf {
+        Self::dir_from_env(|name| std::env::var(name).ok())
+    }
+
+    /// `default_dir` with the environment looked up through `env`
+    pub(crate) fn dir_from_env(env: impl Fn(&str) -> Option<String>) -> PathBuf {
+        let dir = resolve_config_dir(&env);
+        if env(CONFIG_DIR_ENV).is_some() {
+            if let Err(e) = fs::create_dir_all(&dir) {
+                warn!("⚠️ Could not create config directory {}: {}", dir.display(), e);
+            }
+        }
+        dir
+    }
+
+    /// Read a config file's contents
+    pub fn read(&self, file_name: &str) -> anyhow::Result<String> {
+        let dir = self.dir.as_ref().ok_or_else(|| anyhow::anyhow!("Could not find home directory"))?;
+        Ok(fs::read_to_string(dir.join(file_name))?)
+    }
+
+    /// Whether a config file has been written before
+    pub fn exists(&self, file_name: &str) -> bool {
+        self.dir.as_ref().is_some_and(|dir| dir.join(file_name).exists())
+    }
+
+    /// Write a config file, or keep settings in memory only once the directory is known to be unwritable
+    pub fn write(&self, file_name: &str, contents: &str) -> anyhow::Result<()> {
+        if self.is_read_only() {
+            return Ok(());
+        }
+
+        let result = self.try_write(file_name, contents);
+        if let Err(ref e) = result {
+            warn!("⚠️ Config directory is not writable ({}); settings will not be saved this session", e);
+            self.read_only.store(true, Ordering::SeqCst);
+            self.notice_pending.store(true, Ordering::SeqCst);
+        }
+        // The failure is reported once via the notice; callers keep their in-memory copy
+        Ok(())
+    }
+
+    fn try_write(&self, file_name: &str, contents: &str) -> anyhow::Result<()> {
+        let dir = self.dir.as_ref().ok_or_else(|| anyhow::anyhow!("Could not find home directory"))?;
+        fs::create_dir_all(dir)?;
+        fs::write(dir.join(file_name), contents)?;
+        Ok(())
+    }
+
+    /// Whether saves are being skipped for the rest of the session
+    pub fn is_read_only(&self) -> bool {
+        self.read_only.load(Ordering::SeqCst)
+    }
+
+    /// Take the one-time notice about falling back to in-memory settings
+    pub fn take_notice(&self) -> Option<String> {
+        if self.notice_pending.swap(false, Ordering::SeqCst) {
+            Some("Settings folder is read-only; changes won't persist this session".to_string())
+        } else {
+            None
+        }
+    }
+}
+
+fn resolve_config_dir(env: impl Fn(&str) -> Option<String>) -> PathBuf {
+    env(CONFIG_DIR_ENV)
+        .filter(|value| !value.trim().is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| system::home_dir().join(".config").join("claude-code-usage-dashboard"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unwritable_dir_falls_back_to_memory() {
+        // A regular file where the config directory should be makes every write fail
+        let blocker = std::env::temp_dir().join(format!("usage-dashboard-config-{}", std::process::id()));
+        fs::write(&blocker, "not a directory").unwrap();
+
+        let store = ConfigStore::at(Some(blocker.join("config")));
+        assert!(!store.is_read_only());
+
+        assert!(store.write("settings.json", "{}").is_ok());
+        assert!(store.is_read_only());
+        assert!(store.take_notice().is_some());
+
+        // Later saves are skipped quietly and the notice is only surfaced once
+        assert!(store.write("settings.json", "{}").is_ok());
+        assert!(store.take_notice().is_none());
+
+        let _ = fs::remove_file(&blocker);
+    }
+
+    #[test]
+    fn test_config_dir_env_override() {
+        let portable = std::env::temp_dir().join("portable-dashboard");
+        let overridden = resolve_config_dir(|name| {
+            (name == CONFIG_DIR_ENV).then(|| portable.display().to_string())
+        });
+        assert_eq!(overridden, portable);
+
+        let default = system::home_dir().join(".config").join("claude-code-usage-dashboard");
+        assert_eq!(resolve_config_dir(|_| None), default);
+        assert_eq!(resolve_config_dir(|_| Some("  ".to_string())), default);
+    }
+
+    #[test]
+    fn test_writable_dir_round_trip() {
+        let dir = std::env::temp_dir().join(format!("usage-dashboard-config-ok-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        let store = ConfigStore::at(Some(dir.clone()));
+        store.write("theme.json", "{\"mode\":\"Dark\"}").unwrap();
+        assert_eq!(store.read("theme.json").unwrap(), "{\"mode\":\"Dark\"}");
+        assert!(!store.is_read_only());
+        assert!(store.take_notice().is_none());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
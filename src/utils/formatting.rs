@@ -1,8 +1,95 @@
 /// Utilities for formatting numbers, currency, and dates
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// How counts (tokens, requests) are rendered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NumberFormat {
+    /// Compact form such as 1.5K or 2.5M
+    Abbreviated,
+    /// Full value with thousands separators such as 1,234,567
+    Exact,
+}
+
+impl NumberFormat {
+    pub fn label(&self) -> &'static str {
+        match self {
+            NumberFormat::Abbreviated => "Abbreviated",
+            NumberFormat::Exact => "Exact",
+        }
+    }
+}
+
+/// Format a count according to the chosen number format
+pub fn format_count(value: u64, format: NumberFormat) -> String {
+    match format {
+        NumberFormat::Abbreviated => {
+            if value >= 1_000_000 {
+                format!("{:.1}M", value as f64 / 1_000_000.0)
+            } else if value >= 1_000 {
+                format!("{:.1}K", value as f64 / 1_000.0)
+            } else {
+                value.to_string()
+            }
+        }
+        NumberFormat::Exact => format_grouped(value),
+    }
+}
+
+/// Decimal places used when rendering costs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CostPrecision {
+    /// More digits for small amounts, two for anything over a dollar
+    Auto,
+    Two,
+    Three,
+    Four,
+}
+
+impl CostPrecision {
+    pub fn label(&self) -> &'static str {
+        match self {
+            CostPrecision::Auto => "Auto",
+            CostPrecision::Two => "2 decimals",
+            CostPrecision::Three => "3 decimals",
+            CostPrecision::Four => "4 decimals",
+        }
+    }
+
+    /// Next option when cycling through precisions
+    pub fn next(&self) -> Self {
+        match self {
+            CostPrecision::Auto => CostPrecision::Two,
+            CostPrecision::Two => CostPrecision::Three,
+            CostPrecision::Three => CostPrecision::Four,
+            CostPrecision::Four => CostPrecision::Auto,
+        }
+    }
+}
+
+/// Format a cost according to the chosen precision
+pub fn format_cost(amount: f64, precision: CostPrecision) -> String {
+    match precision {
+        CostPrecision::Auto => format_currency(amount),
+        CostPrecision::Two => format!("${:.2}", amount),
+        CostPrecision::Three => format!("${:.3}", amount),
+        CostPrecision::Four => format!("${:.4}", amount),
+    }
+}
+
+/// Format an integer with comma thousands separators (e.g. 1,234,567)
+pub fn format_grouped(value: u64) -> String {
+    let digits = value.to_string();
+    let groups: Vec<&str> = digits
+        .as_bytes()
+        .rchunks(3)
+        .rev()
+        .map(|chunk| std::str::from_utf8(chunk).unwrap_or_default())
+        .collect();
+    groups.join(",")
+}
 
 /// Format currency values with appropriate precision
-#[allow(dead_code)] // Utility function for future features
 pub fn format_currency(amount: f64) -> String {
     if amount == 0.0 {
         "$0.00".to_string()
@@ -66,6 +153,30 @@ pub fn format_relative_time(timestamp: &DateTime<Utc>) -> String {
     }
 }
 
+/// Format a span of time compactly, e.g. "2h 15m" or "45m"
+pub fn format_duration(duration: chrono::Duration) -> String {
+    let total_minutes = duration.num_minutes().max(0);
+    let (hours, minutes) = (total_minutes / 60, total_minutes % 60);
+
+    if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m", minutes)
+    } else {
+        "<1m".to_string()
+    }
+}
+
+/// Shorten free text to at most `max_chars` characters, ending with an ellipsis when cut
+pub fn truncate_text(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        text.to_string()
+    } else {
+        let kept: String = text.chars().take(max_chars.saturating_sub(1)).collect();
+        format!("{}…", kept.trim_end())
+    }
+}
+
 /// Truncate project paths for display
 #[allow(dead_code)] // Utility function for future features
 pub fn truncate_project_path(path: &str, max_length: usize) -> String {
@@ -109,4 +220,52 @@ mod tests {
         assert_eq!(truncate_project_path("/very/long/path/to/project", 15), ".../project");
         assert_eq!(truncate_project_path("toolongname", 8), "toolo...");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_truncate_text() {
+        assert_eq!(truncate_text("bug hunt", 20), "bug hunt");
+        assert_eq!(truncate_text("migration spike", 10), "migration…");
+        assert_eq!(truncate_text("größenänderung", 5), "größ…");
+    }
+
+    #[test]
+    fn test_format_grouped() {
+        assert_eq!(format_grouped(0), "0");
+        assert_eq!(format_grouped(999), "999");
+        assert_eq!(format_grouped(1000), "1,000");
+        assert_eq!(format_grouped(100_000), "100,000");
+        assert_eq!(format_grouped(1_234_567), "1,234,567");
+    }
+
+    #[test]
+    fn test_format_count_modes() {
+        assert_eq!(format_count(0, NumberFormat::Abbreviated), "0");
+        assert_eq!(format_count(999, NumberFormat::Abbreviated), "999");
+        assert_eq!(format_count(1000, NumberFormat::Abbreviated), "1.0K");
+        assert_eq!(format_count(1_500_000, NumberFormat::Abbreviated), "1.5M");
+
+        assert_eq!(format_count(0, NumberFormat::Exact), "0");
+        assert_eq!(format_count(999, NumberFormat::Exact), "999");
+        assert_eq!(format_count(1000, NumberFormat::Exact), "1,000");
+        assert_eq!(format_count(1_500_000, NumberFormat::Exact), "1,500,000");
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(chrono::Duration::seconds(20)), "<1m");
+        assert_eq!(format_duration(chrono::Duration::minutes(45)), "45m");
+        assert_eq!(format_duration(chrono::Duration::minutes(135)), "2h 15m");
+        assert_eq!(format_duration(chrono::Duration::hours(26)), "26h 0m");
+    }
+
+    #[test]
+    fn test_format_cost_precisions() {
+        let amounts = [0.0, 0.0042, 0.256, 1234.5678];
+
+        let render = |precision| amounts.iter().map(|&a| format_cost(a, precision)).collect::<Vec<_>>();
+        assert_eq!(render(CostPrecision::Auto), ["$0.00", "$0.0042", "$0.256", "$1234.57"]);
+        assert_eq!(render(CostPrecision::Two), ["$0.00", "$0.00", "$0.26", "$1234.57"]);
+        assert_eq!(render(CostPrecision::Three), ["$0.000", "$0.004", "$0.256", "$1234.568"]);
+        assert_eq!(render(CostPrecision::Four), ["$0.0000", "$0.0042", "$0.2560", "$1234.5678"]);
+    }
+}
@@ -0,0 +1,75 @@
+/// Console logging for the dashboard
+///
+/// Everything goes through the `log` macros; this logger prints to stdout and its level
+/// follows the `log_verbosity` setting, so a default install stays quiet.
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use serde::{Deserialize, Serialize};
+
+/// How much the dashboard writes to the console
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum LogVerbosity {
+    /// Warnings and errors only
+    #[default]
+    Quiet,
+    /// Also loading, refresh and export progress
+    Normal,
+    /// Also per-interaction detail such as filtering and file-by-file processing
+    Verbose,
+}
+
+impl LogVerbosity {
+    pub fn level_filter(&self) -> LevelFilter {
+        match self {
+            LogVerbosity::Quiet => LevelFilter::Warn,
+            LogVerbosity::Normal => LevelFilter::Info,
+            LogVerbosity::Verbose => LevelFilter::Debug,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LogVerbosity::Quiet => "Quiet",
+            LogVerbosity::Normal => "Normal",
+            LogVerbosity::Verbose => "Verbose",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            LogVerbosity::Quiet => LogVerbosity::Normal,
+            LogVerbosity::Normal => LogVerbosity::Verbose,
+            LogVerbosity::Verbose => LogVerbosity::Quiet,
+        }
+    }
+}
+
+struct ConsoleLogger;
+
+impl Log for ConsoleLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        // Dependencies (gpui and friends) only get through with warnings and errors
+        metadata.level() <= log::max_level()
+            && (metadata.level() <= Level::Warn || metadata.target().starts_with(env!("CARGO_CRATE_NAME")))
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            println!("{}", record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: ConsoleLogger = ConsoleLogger;
+
+/// Install the console logger; later calls only change the verbosity
+pub fn init(verbosity: LogVerbosity) {
+    let _ = log::set_logger(&LOGGER);
+    set_verbosity(verbosity);
+}
+
+/// Apply a new verbosity immediately
+pub fn set_verbosity(verbosity: LogVerbosity) {
+    log::set_max_level(verbosity.level_filter());
+}
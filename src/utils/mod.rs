@@ -1,3 +1,6 @@
+pub mod config;
 pub mod formatting;
+pub mod logging;
+pub mod system;
 
 // pub use formatting::*; // Currently unused
\ No newline at end of file
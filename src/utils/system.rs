@@ -0,0 +1,99 @@
+/// Helpers for handing paths off to the operating system
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use log::{info, warn};
+
+/// The user's home directory, falling back to $HOME / %USERPROFILE% and finally the temp dir
+///
+/// Headless CI runners and containers often have no passwd entry, so this never fails.
+pub fn home_dir() -> PathBuf {
+    resolve_home_dir(dirs::home_dir(), |name| std::env::var(name).ok())
+}
+
+fn resolve_home_dir(platform_home: Option<PathBuf>, env: impl Fn(&str) -> Option<String>) -> PathBuf {
+    platform_home
+        .or_else(|| {
+            ["HOME", "USERPROFILE"]
+                .iter()
+                .filter_map(|name| env(name))
+                .find(|value| !value.trim().is_empty())
+                .map(PathBuf::from)
+        })
+        .unwrap_or_else(|| {
+            let fallback = std::env::temp_dir();
+            warn!("⚠️ No home directory found, using {}", fallback.display());
+            fallback
+        })
+}
+
+/// Closest path (the path itself or one of its parents) that exists on disk
+pub fn nearest_existing_ancestor(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .find(|candidate| candidate.exists())
+        .map(Path::to_path_buf)
+}
+
+/// Open a directory in the platform file manager, falling back to its nearest existing ancestor
+pub fn open_in_file_manager(path: &Path) -> anyhow::Result<PathBuf> {
+    let target = nearest_existing_ancestor(path)
+        .ok_or_else(|| anyhow::anyhow!("No existing directory found for {}", path.display()))?;
+
+    #[cfg(target_os = "macos")]
+    let program = "open";
+    #[cfg(target_os = "windows")]
+    let program = "explorer";
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let program = "xdg-open";
+
+    Command::new(program).arg(&target).spawn()?;
+    Ok(target)
+}
+
+/// Show a native desktop notification
+#[cfg(target_os = "macos")]
+pub fn send_notification(title: &str, body: &str) -> anyhow::Result<()> {
+    let quote = |text: &str| text.replace('\\', "\\\\").replace('"', "\\\"");
+    let script = format!("display notification \"{}\" with title \"{}\"", quote(body), quote(title));
+    Command::new("osascript").arg("-e").arg(script).spawn()?;
+    Ok(())
+}
+
+/// Show a native desktop notification (only supported on macOS)
+#[cfg(not(target_os = "macos"))]
+pub fn send_notification(title: &str, body: &str) -> anyhow::Result<()> {
+    info!("🔔 {}: {} (desktop notifications are only supported on macOS)", title, body);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nearest_existing_ancestor() {
+        let base = std::env::temp_dir();
+        let missing = base.join("usage-dashboard-missing").join("projects");
+        assert_eq!(nearest_existing_ancestor(&missing), Some(base.clone()));
+        assert_eq!(nearest_existing_ancestor(&base), Some(base));
+    }
+
+    #[test]
+    fn test_home_dir_fallback_chain() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| vars.iter().find(|(key, _)| *key == name).map(|(_, value)| value.to_string())
+        };
+
+        let platform = PathBuf::from("/home/dev");
+        assert_eq!(resolve_home_dir(Some(platform.clone()), env(&[("HOME", "/other")])), platform);
+
+        // No platform home: environment variables are tried in order, skipping blank ones
+        assert_eq!(resolve_home_dir(None, env(&[("HOME", "/root")])), PathBuf::from("/root"));
+        assert_eq!(
+            resolve_home_dir(None, env(&[("HOME", " "), ("USERPROFILE", "C:\\Users\\dev")])),
+            PathBuf::from("C:\\Users\\dev")
+        );
+
+        // Nothing at all still yields a usable directory
+        assert_eq!(resolve_home_dir(None, env(&[])), std::env::temp_dir());
+    }
+}